@@ -0,0 +1,657 @@
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::path::{Path, PathBuf};
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as NotificationTrait, PublishDiagnostics,
+};
+use lsp_types::request::{
+    Completion, DocumentSymbolRequest, GotoDefinition, HoverRequest, References,
+    Request as RequestTrait,
+};
+use lsp_types::*;
+use tatam::{common::*, error::Lint, expr::*, load_file_with_sources, model::*, parser};
+
+//------------------------- Text -------------------------
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Identifier at the (0-based) position, with its start and end columns
+fn word_at(text: &str, line: usize, column: usize) -> Option<(String, usize, usize)> {
+    let chars: Vec<char> = text.lines().nth(line)?.chars().collect();
+    let mut start = column.min(chars.len());
+    while start > 0 && is_identifier_char(chars[start - 1]) {
+        start -= 1;
+    }
+    let mut end = column.min(chars.len());
+    while end < chars.len() && is_identifier_char(chars[end]) {
+        end += 1;
+    }
+    if start == end {
+        None
+    } else {
+        Some((chars[start..end].iter().collect(), start, end))
+    }
+}
+
+/// File of an 'include "file"' when the position is on it
+fn include_at(text: &str, line: usize) -> Option<String> {
+    let line = text.lines().nth(line)?.trim();
+    let rest = line.strip_prefix("include")?.trim();
+    let rest = rest.strip_prefix('"')?;
+    let end = rest.find('"')?;
+    Some(rest[..end].to_string())
+}
+
+//------------------------- Position -------------------------
+
+fn absolute(file: &str) -> PathBuf {
+    let path = Path::new(file);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(path)
+    }
+}
+
+fn file_uri(file: &str) -> Option<Url> {
+    Url::from_file_path(absolute(file)).ok()
+}
+
+/// Range of a tatam position (1-based line/column), extended to the identifier at this position
+fn to_range(text: Option<&String>, position: &parser::Position) -> Range {
+    let line = position.line.saturating_sub(1);
+    let column = position.column.saturating_sub(1);
+    let end = text
+        .and_then(|text| word_at(text, line, column))
+        .filter(|(_, start, _)| *start == column)
+        .map(|(_, _, end)| end)
+        .unwrap_or(column + 1);
+    Range::new(
+        lsp_types::Position::new(line as u32, column as u32),
+        lsp_types::Position::new(line as u32, end as u32),
+    )
+}
+
+//------------------------- Symbol -------------------------
+
+struct Symbol {
+    name: String,
+    kind: SymbolKind,
+    detail: String,
+    position: Option<parser::Position>,
+}
+
+fn symbols(model: &Model) -> Vec<Symbol> {
+    let mut v = vec![];
+    for e in model.enumerates().iter() {
+        v.push(Symbol {
+            name: e.name().to_string(),
+            kind: SymbolKind::ENUM,
+            detail: e.to_lang(model),
+            position: e.position().clone(),
+        });
+        for elt in e.elements().iter() {
+            v.push(Symbol {
+                name: elt.name().to_string(),
+                kind: SymbolKind::ENUM_MEMBER,
+                detail: format!("{}: {}", elt.name(), e.name()),
+                position: elt.position().clone(),
+            });
+        }
+    }
+    for x in model.intervals().iter().filter(|x| !x.is_anonymous()) {
+        v.push(Symbol {
+            name: x.name().to_string(),
+            kind: SymbolKind::CLASS,
+            detail: x.to_lang(model),
+            position: x.position().clone(),
+        });
+    }
+    for x in model.declarations().iter() {
+        v.push(Symbol {
+            name: x.name().to_string(),
+            kind: if x.is_constant() {
+                SymbolKind::CONSTANT
+            } else {
+                SymbolKind::VARIABLE
+            },
+            detail: x.to_lang(model),
+            position: x.position().clone(),
+        });
+    }
+    for x in model.definitions().iter() {
+        v.push(Symbol {
+            name: x.name().to_string(),
+            kind: SymbolKind::FUNCTION,
+            detail: x.to_lang(model),
+            position: x.position().clone(),
+        });
+    }
+    for x in model.ltl_definitions().iter() {
+        v.push(Symbol {
+            name: x.name().to_string(),
+            kind: SymbolKind::FUNCTION,
+            detail: x.to_lang(model),
+            position: x.position().clone(),
+        });
+    }
+    for x in model.initials().iter() {
+        v.push(Symbol {
+            name: x.name().to_string(),
+            kind: SymbolKind::EVENT,
+            detail: format!("init {}", x.name()),
+            position: x.position().clone(),
+        });
+    }
+    for x in model.invariants().iter() {
+        v.push(Symbol {
+            name: x.name().to_string(),
+            kind: SymbolKind::EVENT,
+            detail: format!("inv {}", x.name()),
+            position: x.position().clone(),
+        });
+    }
+    for x in model.transitions().iter() {
+        v.push(Symbol {
+            name: x.name().to_string(),
+            kind: SymbolKind::EVENT,
+            detail: format!("trans {}", x.name()),
+            position: x.position().clone(),
+        });
+    }
+    for x in model.triggers().iter() {
+        v.push(Symbol {
+            name: x.name().to_string(),
+            kind: SymbolKind::EVENT,
+            detail: format!("trig {}", x.name()),
+            position: x.position().clone(),
+        });
+    }
+    v
+}
+
+//------------------------- Reference -------------------------
+
+/// An identifier of an expression, with the position of what it names
+struct Reference {
+    position: parser::Position,
+    target: Option<parser::Position>,
+    /// the parameter (or local name) it names, if it is not a symbol
+    parameter: Option<Parameter>,
+}
+
+fn add_references(model: &Model, expr: &Expr, res: &mut Vec<Reference>) {
+    let target = match expr.expression() {
+        Expression::EnumerateElement(id) => model.get(*id).map(|x| x.position().clone()),
+        Expression::Declaration(id) => model.get(*id).map(|x| x.position().clone()),
+        Expression::Definition(id) => model.get(*id).map(|x| x.position().clone()),
+        Expression::FunDec(id) => model.get(*id).map(|x| x.position().clone()),
+        Expression::FunDef(id) => model.get(*id).map(|x| x.position().clone()),
+        Expression::LtlDefinition(id) => model.get(*id).map(|x| x.position().clone()),
+        Expression::Parameter(p) => Some(p.position().clone()),
+        _ => None,
+    };
+    if let (Some(target), Some(position)) = (target, expr.position()) {
+        let parameter = match expr.expression() {
+            Expression::Parameter(p) => Some(p.clone()),
+            _ => None,
+        };
+        res.push(Reference {
+            position: position.clone(),
+            target,
+            parameter,
+        });
+    }
+    for kid in expr.kids() {
+        add_references(model, kid, res);
+    }
+}
+
+/// The identifiers of all the expressions of the model
+fn references(model: &Model) -> Vec<Reference> {
+    let mut res = vec![];
+    for expr in model.exprs() {
+        add_references(model, expr, &mut res);
+    }
+    res
+}
+
+/// The position is the start of the identifier at the (0-based) line and column of the file
+fn is_at(position: &Option<parser::Position>, file: &str, line: usize, column: usize) -> bool {
+    match position {
+        Some(position) => {
+            position.line == line + 1
+                && position.column == column + 1
+                && absolute(&position.file) == absolute(file)
+        }
+        None => false,
+    }
+}
+
+fn completion_kind(kind: SymbolKind) -> CompletionItemKind {
+    match kind {
+        SymbolKind::ENUM => CompletionItemKind::ENUM,
+        SymbolKind::ENUM_MEMBER => CompletionItemKind::ENUM_MEMBER,
+        SymbolKind::CLASS => CompletionItemKind::CLASS,
+        SymbolKind::CONSTANT => CompletionItemKind::CONSTANT,
+        SymbolKind::VARIABLE => CompletionItemKind::VARIABLE,
+        SymbolKind::FUNCTION => CompletionItemKind::FUNCTION,
+        _ => CompletionItemKind::TEXT,
+    }
+}
+
+//------------------------- Server -------------------------
+
+struct Server {
+    connection: Connection,
+    /// content of the open documents
+    documents: HashMap<String, String>,
+    /// last model loaded without error, for each open document
+    models: HashMap<String, Model>,
+    /// files with diagnostics, for each open document
+    diagnostics: HashMap<String, Vec<String>>,
+}
+
+impl Server {
+    fn new(connection: Connection) -> Self {
+        Self {
+            connection,
+            documents: HashMap::new(),
+            models: HashMap::new(),
+            diagnostics: HashMap::new(),
+        }
+    }
+
+    fn sources(&self) -> HashMap<String, String> {
+        let cwd = std::env::current_dir().unwrap_or_default();
+        let mut sources = HashMap::new();
+        for (file, text) in self.documents.iter() {
+            sources.insert(file.clone(), text.clone());
+            if let Ok(relative) = Path::new(file).strip_prefix(&cwd) {
+                sources.insert(relative.to_string_lossy().to_string(), text.clone());
+            }
+        }
+        sources
+    }
+
+    fn text(&self, file: &str) -> Option<&String> {
+        self.documents
+            .get(&absolute(file).to_string_lossy().to_string())
+    }
+
+    fn publish(&self, file: &str, diagnostics: Vec<Diagnostic>) -> Result<(), Box<dyn StdError>> {
+        if let Some(uri) = file_uri(file) {
+            let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+            let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+            self.connection
+                .sender
+                .send(Message::Notification(notification))?;
+        }
+        Ok(())
+    }
+
+    fn check(&mut self, file: &str) -> Result<(), Box<dyn StdError>> {
+        let mut model = Model::empty();
+        let mut pretty = d_stuff::Pretty::new();
+        let result = load_file_with_sources(&mut pretty, &mut model, file, &self.sources(), 0);
+
+        // Clear previous diagnostics
+        for previous in self.diagnostics.remove(file).unwrap_or_default() {
+            self.publish(&previous, vec![])?;
+        }
+
+        // diagnostics (message, position, severity)
+        let mut list = vec![];
+        match &result {
+            Ok(_) => {
+                for warning in model.lint(&Lint::all()) {
+                    for (message, position) in warning.messages(&model) {
+                        list.push((message, position, DiagnosticSeverity::WARNING));
+                    }
+                }
+            }
+            Err(e) => {
+                for e in e.errors() {
+                    list.push((e.message(&model), e.position(), DiagnosticSeverity::ERROR));
+                }
+            }
+        }
+
+        // diagnostics by file
+        let mut targets: Vec<(String, Vec<Diagnostic>)> = vec![];
+        for (message, position, severity) in list {
+            let (target, range) = match position {
+                Some(position) => (
+                    absolute(&position.file).to_string_lossy().to_string(),
+                    to_range(self.text(&position.file), &position),
+                ),
+                None => (file.to_string(), Range::default()),
+            };
+            let diagnostic = Diagnostic {
+                range,
+                severity: Some(severity),
+                source: Some("tatam".to_string()),
+                message,
+                ..Default::default()
+            };
+            match targets.iter_mut().find(|(t, _)| *t == target) {
+                Some((_, l)) => l.push(diagnostic),
+                None => targets.push((target, vec![diagnostic])),
+            }
+        }
+        if targets.iter().all(|(t, _)| t != file) {
+            self.publish(file, vec![])?;
+        }
+        let files = targets.iter().map(|(t, _)| t.clone()).collect();
+        for (target, diagnostics) in targets {
+            self.publish(&target, diagnostics)?;
+        }
+        self.diagnostics.insert(file.to_string(), files);
+
+        if result.is_ok() {
+            self.models.insert(file.to_string(), model);
+        }
+        Ok(())
+    }
+
+    //---------- Requests ----------
+
+    /// The symbol of the identifier at the position.
+    /// It is resolved through the model: a parameter is not taken for a global of the same name.
+    fn symbol_at(&self, file: &str, position: lsp_types::Position) -> Option<Symbol> {
+        let text = self.documents.get(file)?;
+        let model = self.models.get(file)?;
+        let line = position.line as usize;
+        let (word, start, _) = word_at(text, line, position.character as usize)?;
+        let mut symbols = symbols(model);
+
+        // Declaration
+        if let Some(i) = symbols
+            .iter()
+            .position(|s| is_at(&s.position, file, line, start))
+        {
+            return Some(symbols.swap_remove(i));
+        }
+        // Reference, or declaration of a parameter
+        for reference in references(model) {
+            let position = Some(reference.position);
+            if is_at(&position, file, line, start) || is_at(&reference.target, file, line, start) {
+                return match reference.parameter {
+                    Some(parameter) => Some(Symbol {
+                        name: parameter.name().to_string(),
+                        kind: SymbolKind::VARIABLE,
+                        detail: parameter.to_lang(model),
+                        position: reference.target,
+                    }),
+                    None => symbols.into_iter().find(|s| s.position == reference.target),
+                };
+            }
+        }
+        // Type (the types do not keep their positions)
+        symbols
+            .into_iter()
+            .find(|s| s.name == word && [SymbolKind::ENUM, SymbolKind::CLASS].contains(&s.kind))
+    }
+
+    fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let params = params.text_document_position_params;
+        let file = params.text_document.uri.to_file_path().ok()?;
+        let file = file.to_string_lossy().to_string();
+        let text = self.documents.get(&file)?;
+
+        // Include
+        if let Some(include) = include_at(text, params.position.line as usize) {
+            let mut path = absolute(&include);
+            if !path.exists() {
+                if let Some(dir) = Path::new(&file).parent() {
+                    path = dir.join(&include);
+                }
+            }
+            let uri = Url::from_file_path(path).ok()?;
+            return Some(GotoDefinitionResponse::Scalar(Location::new(
+                uri,
+                Range::default(),
+            )));
+        }
+
+        let symbol = self.symbol_at(&file, params.position)?;
+        let position = symbol.position?;
+        let uri = file_uri(&position.file)?;
+        let range = to_range(self.text(&position.file), &position);
+        Some(GotoDefinitionResponse::Scalar(Location::new(uri, range)))
+    }
+
+    fn references(&self, params: ReferenceParams) -> Option<Vec<Location>> {
+        let params_position = params.text_document_position;
+        let file = params_position.text_document.uri.to_file_path().ok()?;
+        let file = file.to_string_lossy().to_string();
+        let model = self.models.get(&file)?;
+        let symbol = self.symbol_at(&file, params_position.position)?;
+        let target = symbol.position?;
+
+        let mut positions = vec![];
+        if params.context.include_declaration {
+            positions.push(target.clone());
+        }
+        for reference in references(model) {
+            if reference.target.as_ref() == Some(&target)
+                && !positions.contains(&reference.position)
+            {
+                positions.push(reference.position);
+            }
+        }
+        let locations = positions
+            .iter()
+            .filter_map(|position| {
+                let uri = file_uri(&position.file)?;
+                Some(Location::new(
+                    uri,
+                    to_range(self.text(&position.file), position),
+                ))
+            })
+            .collect();
+        Some(locations)
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let params = params.text_document_position_params;
+        let file = params.text_document.uri.to_file_path().ok()?;
+        let file = file.to_string_lossy().to_string();
+        let symbol = self.symbol_at(&file, params.position)?;
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("```tatam\n{}\n```", symbol.detail),
+            }),
+            range: None,
+        })
+    }
+
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let uri = params.text_document_position.text_document.uri;
+        let file = uri.to_file_path().ok()?.to_string_lossy().to_string();
+        let mut items: Vec<CompletionItem> = parser::KEYWORDS
+            .iter()
+            .map(|k| CompletionItem {
+                label: k.to_string(),
+                kind: Some(CompletionItemKind::KEYWORD),
+                ..Default::default()
+            })
+            .collect();
+        if let Some(model) = self.models.get(&file) {
+            for symbol in symbols(model) {
+                if symbol.kind == SymbolKind::EVENT {
+                    continue;
+                }
+                items.push(CompletionItem {
+                    label: symbol.name,
+                    kind: Some(completion_kind(symbol.kind)),
+                    detail: Some(symbol.detail),
+                    ..Default::default()
+                });
+            }
+        }
+        Some(CompletionResponse::Array(items))
+    }
+
+    fn document_symbols(&self, params: DocumentSymbolParams) -> Option<DocumentSymbolResponse> {
+        let file = params.text_document.uri.to_file_path().ok()?;
+        let file = file.to_string_lossy().to_string();
+        let model = self.models.get(&file)?;
+        let text = self.documents.get(&file);
+        let mut list = vec![];
+        for symbol in symbols(model) {
+            if symbol.kind == SymbolKind::ENUM_MEMBER {
+                continue;
+            }
+            if let Some(position) = &symbol.position {
+                if absolute(&position.file).to_string_lossy() != file {
+                    continue;
+                }
+                let range = to_range(text, position);
+                #[allow(deprecated)]
+                list.push(DocumentSymbol {
+                    name: symbol.name,
+                    detail: Some(symbol.detail),
+                    kind: symbol.kind,
+                    tags: None,
+                    deprecated: None,
+                    range,
+                    selection_range: range,
+                    children: None,
+                });
+            }
+        }
+        Some(DocumentSymbolResponse::Nested(list))
+    }
+
+    /// The response to a request (an error if its parameters are invalid)
+    fn answer<P, T>(&self, request: Request, handler: impl Fn(&Self, P) -> Option<T>) -> Response
+    where
+        P: serde::de::DeserializeOwned,
+        T: serde::Serialize,
+    {
+        match serde_json::from_value::<P>(request.params) {
+            Ok(params) => Response::new_ok(request.id, handler(self, params)),
+            Err(error) => Response::new_err(
+                request.id,
+                ErrorCode::InvalidParams as i32,
+                format!("invalid parameters for '{}': {}", request.method, error),
+            ),
+        }
+    }
+
+    fn request(&mut self, request: Request) -> Result<(), Box<dyn StdError>> {
+        let response = match request.method.as_str() {
+            GotoDefinition::METHOD => self.answer(request, Self::definition),
+            References::METHOD => self.answer(request, Self::references),
+            HoverRequest::METHOD => self.answer(request, Self::hover),
+            Completion::METHOD => self.answer(request, Self::completion),
+            DocumentSymbolRequest::METHOD => self.answer(request, Self::document_symbols),
+            method => Response::new_err(
+                request.id.clone(),
+                ErrorCode::MethodNotFound as i32,
+                format!("unknown method '{}'", method),
+            ),
+        };
+        self.connection.sender.send(Message::Response(response))?;
+        Ok(())
+    }
+
+    //---------- Notifications ----------
+
+    fn notification(&mut self, notification: Notification) -> Result<(), Box<dyn StdError>> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                if let Ok(path) = params.text_document.uri.to_file_path() {
+                    let file = path.to_string_lossy().to_string();
+                    self.documents
+                        .insert(file.clone(), params.text_document.text);
+                    self.check(&file)?;
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                if let Ok(path) = params.text_document.uri.to_file_path() {
+                    let file = path.to_string_lossy().to_string();
+                    if let Some(change) = params.content_changes.into_iter().last() {
+                        self.documents.insert(file.clone(), change.text);
+                    }
+                    // the other documents may include this one
+                    let files: Vec<String> = self.documents.keys().cloned().collect();
+                    for file in files.iter() {
+                        self.check(file)?;
+                    }
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                if let Ok(path) = params.text_document.uri.to_file_path() {
+                    let file = path.to_string_lossy().to_string();
+                    self.documents.remove(&file);
+                    self.models.remove(&file);
+                    for previous in self.diagnostics.remove(&file).unwrap_or_default() {
+                        self.publish(&previous, vec![])?;
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn run(&mut self) -> Result<(), Box<dyn StdError>> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    self.request(request)?;
+                }
+                Message::Response(_) => {}
+                Message::Notification(notification) => self.notification(notification)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn main() -> Result<(), Box<dyn StdError>> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions::default()),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    };
+    let params = connection.initialize(serde_json::to_value(capabilities)?)?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+
+    // included files are relative to the workspace root (as for the tatam command)
+    #[allow(deprecated)]
+    let root = params
+        .workspace_folders
+        .and_then(|folders| folders.first().map(|f| f.uri.clone()))
+        .or(params.root_uri);
+    if let Some(path) = root.and_then(|uri| uri.to_file_path().ok()) {
+        let _ = std::env::set_current_dir(path);
+    }
+
+    Server::new(connection).run()?;
+    io_threads.join()?;
+    Ok(())
+}
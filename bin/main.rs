@@ -1,15 +1,31 @@
 use clap::Parser;
-use tatam::{common::*, load_file, model::*, solve::*, Args};
+use std::time::Instant;
+use tatam::{common::*, error::*, format::*, load_file, model::*, solve::*};
+use tatam::{Args, FmtArgs, Output};
 
 fn main() {
     let mut pretty = d_stuff::Pretty::new();
 
-    let args: Args = Args::parse();
+    // tatam fmt [--check] FILES
+    if std::env::args().nth(1).as_deref() == Some("fmt") {
+        let args = FmtArgs::parse_from(std::env::args().skip(1));
+        std::process::exit(format_files(&mut pretty, &args));
+    }
+
+    let mut args: Args = Args::parse();
+    // keep stdout for the JSON document only
+    if args.output == Output::Json {
+        args.verbose = 0;
+    }
 
     let mut model = Model::empty();
 
-    match load_file(&mut pretty, &mut model, &args.file, args.verbose) {
+    let load_start = Instant::now();
+    match load_file(&mut pretty, &mut model, &args.file, args.verbose)
+        .and_then(|_| define_constants(&mut model, &args))
+    {
         Ok(_) => {
+            let load_time = load_start.elapsed().as_secs_f64();
             if args.verbose >= 3 {
                 pretty.add(model.to_debug_entry());
                 pretty.print();
@@ -17,18 +33,187 @@ fn main() {
                 pretty.add(model.to_entry());
                 pretty.print();
             }
+            let warnings = model.lint(&Lint::without(&args.allow));
+            if args.verbose > 0 && !warnings.is_empty() {
+                for warning in warnings.iter() {
+                    pretty.add(warning.to_entry(&model));
+                }
+                pretty.print();
+            }
+            if let Some(file) = &args.dot_model {
+                if let Err(e) = std::fs::write(file, model.to_dot()) {
+                    eprintln!("cannot write '{}': {}", file, e);
+                }
+            }
+            //
+            if let Some((name, min, max)) = args.sweep.as_ref().and_then(|s| split_sweep(s)) {
+                match resolve_sweep(&model, &args, &name, min, max) {
+                    Ok(results) => print_sweep(&model, &args, &name, &results),
+                    Err(e) => print_error(&mut pretty, &model, &args, e),
+                }
+                return;
+            }
             //
+            // the property is flattened by resolve
+            let original = args.vacuity.then(|| model.clone());
+            let solve_start = Instant::now();
             let response = resolve(&mut model, &mut pretty, &args);
-            if args.verbose > 0 {
-                pretty.add(response.to_entry(&model));
+            let solve_time = solve_start.elapsed().as_secs_f64();
+            let vacuity = original.map(|original| resolve_vacuity(&original, &args));
+            if let Some(solution) = response.solution() {
+                if let Some(file) = &args.vcd {
+                    if let Err(e) = std::fs::write(file, solution.to_vcd(&model)) {
+                        eprintln!("cannot write '{}': {}", file, e);
+                    }
+                }
+                if let Some(file) = &args.csv {
+                    if let Err(e) = std::fs::write(file, solution.to_csv(&model)) {
+                        eprintln!("cannot write '{}': {}", file, e);
+                    }
+                }
+                if let Some(file) = &args.dot {
+                    if let Err(e) = std::fs::write(file, solution.to_dot(&model)) {
+                        eprintln!("cannot write '{}': {}", file, e);
+                    }
+                }
+            }
+            match args.output {
+                Output::Json => {
+                    let mut json = response.to_json(&model);
+                    json["version"] = JSON_SCHEMA_VERSION.into();
+                    json["file"] = args.file.clone().into();
+                    json["time"] = serde_json::json!({
+                        "load": load_time,
+                        "solve": solve_time,
+                    });
+                    if let Some(results) = &vacuity {
+                        json["vacuity"] = vacuity_to_json(&response, results);
+                    }
+                    println!("{}", serde_json::to_string_pretty(&json).unwrap());
+                }
+                Output::Text => {
+                    if args.verbose > 0 {
+                        pretty.add(response.to_entry(&model));
+                        if let Some(results) = &vacuity {
+                            pretty.add(vacuity_to_entry(&response, results));
+                        }
+                        pretty.print();
+                    } else {
+                        println!("{}", response.to_lang(&model));
+                        if let Some(results) = &vacuity {
+                            for result in results.iter().filter(|r| r.is_vacuous(&response)) {
+                                println!("vacuous: {}", result.atom);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Err(e) => print_error(&mut pretty, &model, &args, e),
+    }
+}
+
+fn format_files(pretty: &mut d_stuff::Pretty, args: &FmtArgs) -> i32 {
+    let mut code = 0;
+    for file in args.files.iter() {
+        match format_file(file, args.check) {
+            Ok(true) if args.check => {
+                println!("{} is not formatted", file);
+                code = 1;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                for e in e.errors() {
+                    pretty.add(e.to_entry(&Model::empty()));
+                }
                 pretty.print();
-            } else {
-                println!("{}", response.to_lang(&model));
+                code = 1;
             }
         }
-        Err(e) => {
+    }
+    code
+}
+
+fn define_constants(model: &mut Model, args: &Args) -> Result<(), Error> {
+    for definition in args.defines.iter() {
+        match split_definition(definition) {
+            Some((name, value)) => model.define_constant(&name, &value)?,
+            None => {
+                return Err(Error::Parse {
+                    message: "Invalid constant definition".to_string(),
+                    token: Some(definition.clone()),
+                    position: None,
+                    expected: vec!["NAME=VALUE".to_string()],
+                })
+            }
+        }
+    }
+    if let Some(sweep) = &args.sweep {
+        if split_sweep(sweep).is_none() {
+            return Err(Error::Parse {
+                message: "Invalid sweep".to_string(),
+                token: Some(sweep.clone()),
+                position: None,
+                expected: vec!["NAME=MIN..MAX".to_string()],
+            });
+        }
+    }
+    Ok(())
+}
+
+fn print_sweep(model: &Model, args: &Args, name: &str, results: &[SweepResult]) {
+    match args.output {
+        Output::Json => {
+            let list: Vec<serde_json::Value> = results
+                .iter()
+                .map(|result| {
+                    let mut json = result.response.to_json(model);
+                    json["value"] = result.value.into();
+                    json["time"] = serde_json::json!({ "solve": result.duration });
+                    json
+                })
+                .collect();
+            let json = serde_json::json!({
+                "version": JSON_SCHEMA_VERSION,
+                "file": args.file,
+                "sweep": name,
+                "results": list,
+            });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        }
+        Output::Text => print!("{}", sweep_to_table(model, name, results)),
+    }
+}
+
+fn print_error(pretty: &mut d_stuff::Pretty, model: &Model, args: &Args, e: Error) {
+    match args.output {
+        Output::Json => {
+            let errors: Vec<serde_json::Value> = e
+                .errors()
+                .iter()
+                .map(|e| {
+                    serde_json::json!({
+                        "category": e.category(),
+                        "message": e.message(model),
+                        "position": e.position().map(|p| p.to_string()),
+                    })
+                })
+                .collect();
+            let json = serde_json::json!({
+                "version": JSON_SCHEMA_VERSION,
+                "file": args.file,
+                "response": "error",
+                "k": null,
+                "solution": null,
+                "errors": errors,
+            });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        }
+        Output::Text => {
             if args.verbose > 0 {
-                pretty.add(e.to_entry(&model));
+                for e in e.errors() {
+                    pretty.add(e.to_entry(model));
+                }
                 pretty.print();
             }
         }
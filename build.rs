@@ -1,5 +1,36 @@
 extern crate lalrpop;
 
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// The alphabetic terminals of the grammar, outside the comments
+fn keywords(grammar: &str) -> BTreeSet<&str> {
+    let mut res = BTreeSet::new();
+    for line in grammar.lines() {
+        let line = line.split("//").next().unwrap();
+        for (i, part) in line.split('"').enumerate() {
+            let is_word = part.starts_with(|c: char| c.is_ascii_alphabetic())
+                && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if i % 2 == 1 && is_word {
+                res.insert(part);
+            }
+        }
+    }
+    res
+}
+
 fn main() {
     lalrpop::process_root().unwrap();
+
+    let grammar = std::fs::read_to_string("src/parser/grammar.lalrpop").unwrap();
+    let list: Vec<String> = keywords(&grammar)
+        .iter()
+        .map(|k| format!("{:?}", k))
+        .collect();
+    let out = std::env::var("OUT_DIR").unwrap();
+    std::fs::write(
+        Path::new(&out).join("keywords.rs"),
+        format!("&[{}]", list.join(", ")),
+    )
+    .unwrap();
 }
@@ -378,6 +378,117 @@ impl ToEntry for Error {
                     messages,
                 )
             }
+            Error::Match {
+                message,
+                name,
+                position,
+            } => {
+                let mut messages = vec![];
+
+                messages.push(Message::new(
+                    Some(d_stuff::Text::new(
+                        message,
+                        termion::style::Reset.to_string(),
+                        termion::color::Red.fg_str(),
+                    )),
+                    d_stuff::Text::new(
+                        format!("'{}'", name),
+                        termion::style::Reset.to_string(),
+                        termion::color::LightBlue.fg_str(),
+                    ),
+                ));
+                if let Some(position) = position {
+                    messages.push(position.to_message());
+                }
+
+                d_stuff::Entry::new(
+                    d_stuff::Status::Failure,
+                    d_stuff::Text::new(
+                        "Match",
+                        termion::style::Bold.to_string(),
+                        termion::color::Blue.fg_str(),
+                    ),
+                    Some(d_stuff::Text::new(
+                        "ERROR",
+                        termion::style::Reset.to_string(),
+                        termion::color::Red.fg_str(),
+                    )),
+                    messages,
+                )
+            }
+            Error::Cycle { path } => {
+                let mut messages = vec![];
+
+                for (i, (name, position)) in path.iter().enumerate() {
+                    let title = if i == 0 { "Cyclic Definition" } else { "Uses" };
+                    messages.push(Message::new(
+                        Some(d_stuff::Text::new(
+                            title,
+                            termion::style::Reset.to_string(),
+                            termion::color::Red.fg_str(),
+                        )),
+                        d_stuff::Text::new(
+                            format!("'{}'", name),
+                            termion::style::Reset.to_string(),
+                            termion::color::LightBlue.fg_str(),
+                        ),
+                    ));
+                    if let Some(position) = position {
+                        messages.push(position.to_message());
+                    }
+                }
+
+                d_stuff::Entry::new(
+                    d_stuff::Status::Failure,
+                    d_stuff::Text::new(
+                        "Cycle",
+                        termion::style::Bold.to_string(),
+                        termion::color::Blue.fg_str(),
+                    ),
+                    Some(d_stuff::Text::new(
+                        "ERROR",
+                        termion::style::Reset.to_string(),
+                        termion::color::Red.fg_str(),
+                    )),
+                    messages,
+                )
+            }
+            Error::List(l) => {
+                let mut messages = vec![];
+                for e in l.iter() {
+                    let title = match e.position() {
+                        Some(position) => position.to_string(),
+                        None => "Error".to_string(),
+                    };
+                    messages.push(Message::new(
+                        Some(d_stuff::Text::new(
+                            title,
+                            termion::style::Reset.to_string(),
+                            termion::color::Cyan.fg_str(),
+                        )),
+                        d_stuff::Text::new(
+                            e.message(model),
+                            termion::style::Reset.to_string(),
+                            termion::color::Red.fg_str(),
+                        ),
+                    ));
+                }
+
+                d_stuff::Entry::new(
+                    d_stuff::Status::Failure,
+                    d_stuff::Text::new(
+                        "Errors",
+                        termion::style::Bold.to_string(),
+                        termion::color::Blue.fg_str(),
+                    ),
+                    Some(d_stuff::Text::new(
+                        format!("{} ERRORS", l.len()),
+                        termion::style::Reset.to_string(),
+                        termion::color::Red.fg_str(),
+                    )),
+                    messages,
+                )
+            }
         }
     }
 }
@@ -1,6 +1,8 @@
 use crate::expr::Expr;
+use crate::model::Model;
 use crate::parser::Position;
 use crate::typing::Type;
+use crate::{ToLang, WithPosition};
 use lalrpop_util::lexer::Token;
 use lalrpop_util::ParseError;
 use line_col::LineColLookup;
@@ -45,6 +47,15 @@ pub enum Error {
         name: String,
         position: Option<Position>,
     },
+    Match {
+        message: String,
+        name: String,
+        position: Option<Position>,
+    },
+    Cycle {
+        path: Vec<(String, Option<Position>)>,
+    },
+    List(Vec<Error>),
 }
 
 impl Error {
@@ -87,3 +98,144 @@ impl Error {
         }
     }
 }
+
+//------------------------- List -------------------------
+
+impl Error {
+    /// Ok if there is no error, the error or the list of errors otherwise.
+    pub fn from_list(errors: Vec<Error>) -> Result<(), Error> {
+        let mut errors: Vec<Error> = errors.into_iter().flat_map(|e| e.into_errors()).collect();
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.pop().unwrap()),
+            _ => Err(Error::List(errors)),
+        }
+    }
+
+    /// The errors (the error itself if it is not a list)
+    pub fn errors(&self) -> Vec<&Error> {
+        match self {
+            Error::List(l) => l.iter().flat_map(|e| e.errors()).collect(),
+            _ => vec![self],
+        }
+    }
+
+    pub fn into_errors(self) -> Vec<Error> {
+        match self {
+            Error::List(l) => l.into_iter().flat_map(|e| e.into_errors()).collect(),
+            _ => vec![self],
+        }
+    }
+}
+
+//------------------------- Message -------------------------
+
+impl Error {
+    /// Kind of the error, as in the text output (`parse`, `type`, ...)
+    pub fn category(&self) -> &str {
+        match self {
+            Error::File { .. } => "file",
+            Error::Parse { .. } => "parse",
+            Error::Interval { .. } => "interval",
+            Error::Duplicate { .. } => "duplicate",
+            Error::Resolve { .. } => "resolve",
+            Error::Type { .. } => "type",
+            Error::Time { .. } => "time",
+            Error::Bounded { .. } => "bounded",
+            Error::Match { .. } => "match",
+            Error::Cycle { .. } => "cycle",
+            Error::List(_) => "list",
+        }
+    }
+
+    /// Main position of the error
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            Error::File { .. } => None,
+            Error::Parse { position, .. } => position.clone(),
+            Error::Interval { position, .. } => position.clone(),
+            Error::Duplicate { second, first, .. } => second.clone().or(first.clone()),
+            Error::Resolve { position, .. } => position.clone(),
+            Error::Type { expr, .. } => expr.position().clone(),
+            Error::Time { position, expr, .. } => expr.position().clone().or(position.clone()),
+            Error::Bounded { position, .. } => position.clone(),
+            Error::Match { position, .. } => position.clone(),
+            Error::Cycle { path } => path.first().and_then(|(_, position)| position.clone()),
+            Error::List(l) => l.first().and_then(|e| e.position()),
+        }
+    }
+
+    /// One line message (without position)
+    pub fn message(&self, model: &Model) -> String {
+        match self {
+            Error::File { filename, message } => {
+                format!("cannot read file '{}': {}", filename, message)
+            }
+            Error::Parse {
+                message,
+                token,
+                position: _,
+                expected,
+            } => {
+                let mut s = message.clone();
+                if let Some(token) = token {
+                    s += &format!(" '{}'", token);
+                }
+                if !expected.is_empty() {
+                    s += &format!(", expected: {}", expected.join(" "));
+                }
+                s
+            }
+            Error::Interval { name, position: _ } => format!("malformed interval '{}'", name),
+            Error::Duplicate {
+                name,
+                first,
+                second: _,
+            } => match first {
+                Some(first) => format!("'{}' defined twice (first at {})", name, first),
+                None => format!("'{}' defined twice", name),
+            },
+            Error::Resolve {
+                category,
+                name,
+                position: _,
+            } => format!("undefined {} '{}'", category, name),
+            Error::Type {
+                expr,
+                typ,
+                expected,
+            } => {
+                let mut s = format!(
+                    "type error '{}': {}",
+                    expr.to_lang(model),
+                    typ.to_lang(model)
+                );
+                if !expected.is_empty() {
+                    let l: Vec<String> = expected.iter().map(|t| t.to_lang(model)).collect();
+                    s += &format!(", expected: {}", l.join(" "));
+                }
+                s
+            }
+            Error::Time {
+                message,
+                name,
+                position: _,
+                expr,
+            } => format!("{} {}: '{}'", message, name, expr.to_lang(model)),
+            Error::Bounded { name, position: _ } => format!("unbounded type '{}'", name),
+            Error::Match {
+                message,
+                name,
+                position: _,
+            } => format!("{} '{}'", message, name),
+            Error::Cycle { path } => {
+                let l: Vec<&str> = path.iter().map(|(name, _)| name.as_str()).collect();
+                format!("cyclic definition: {}", l.join(" -> "))
+            }
+            Error::List(l) => {
+                let l: Vec<String> = l.iter().map(|e| e.message(model)).collect();
+                l.join("\n")
+            }
+        }
+    }
+}
@@ -1,57 +1,175 @@
 use crate::common::*;
 use crate::model::*;
+use crate::parser::Position;
+use clap::ValueEnum;
 use d_stuff::*;
 
+//------------------------- Lint -------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Lint {
+    /// var, cst or let never used
+    Unused,
+    /// variable never changed (x') by a transition
+    NeverPrimed,
+    /// constant not constrained by an init
+    Unconstrained,
+    /// transition reduced to false
+    UnsatTransition,
+    /// variable of a scope |x|(...) never changed (x') in the scope
+    UnassignedScope,
+    /// all the lints
+    All,
+}
+
+impl Lint {
+    pub fn all() -> Vec<Lint> {
+        vec![
+            Lint::Unused,
+            Lint::NeverPrimed,
+            Lint::Unconstrained,
+            Lint::UnsatTransition,
+            Lint::UnassignedScope,
+        ]
+    }
+
+    /// All the lints but the allowed ones
+    pub fn without(allowed: &[Lint]) -> Vec<Lint> {
+        if allowed.contains(&Lint::All) {
+            vec![]
+        } else {
+            Self::all()
+                .into_iter()
+                .filter(|l| !allowed.contains(l))
+                .collect()
+        }
+    }
+}
+
+//------------------------- Warning -------------------------
+
 pub enum Warning {
     UnboundedDec(Vec<DeclarationId>),
     UnboundedFun(Vec<FunDecId>),
+    UnusedDec(Vec<DeclarationId>),
+    UnusedDef(Vec<DefinitionId>),
+    NeverPrimed(Vec<DeclarationId>),
+    Unconstrained(Vec<DeclarationId>),
+    UnsatTransition(Vec<TransitionId>),
+    UnassignedScope(Vec<(DeclarationId, Option<Position>)>),
+}
+
+fn naming<I: Id, T: Named<I>>(x: &T) -> (String, Option<Position>) {
+    (x.name().to_string(), x.position().clone())
 }
 
 impl Warning {
-    pub fn to_entry(&self, model: &Model) -> d_stuff::Entry {
+    pub fn title(&self) -> &str {
         match self {
-            Warning::UnboundedDec(v) => {
-                let mut messages = vec![];
-
-                for dec in v {
-                    let dec = model.get(*dec).unwrap();
-                    messages.push(Message::new(
-                        Some(d_stuff::Text::new(
-                            format!("'{}'", dec.name()),
-                            termion::style::Reset.to_string(),
-                            termion::color::LightBlue.fg_str(),
-                        )),
-                        match dec.position() {
-                            Some(pos) => d_stuff::Text::new(
-                                format!("{}", pos),
-                                termion::style::Reset.to_string(),
-                                termion::color::Cyan.fg_str(),
-                            ),
-                            None => d_stuff::Text::new(
-                                "",
-                                termion::style::Reset.to_string(),
-                                termion::color::Cyan.fg_str(),
-                            ),
-                        },
-                    ));
-                }
-
-                d_stuff::Entry::new(
-                    d_stuff::Status::Info,
-                    d_stuff::Text::new(
-                        "Unbounded",
-                        termion::style::Bold.to_string(),
-                        termion::color::Blue.fg_str(),
-                    ),
-                    Some(d_stuff::Text::new(
-                        "Warning",
-                        termion::style::Reset.to_string(),
-                        termion::color::Red.fg_str(),
-                    )),
-                    messages,
+            Warning::UnboundedDec(_) => "Unbounded",
+            Warning::UnboundedFun(_) => "Unbounded",
+            Warning::UnusedDec(_) => "Unused Declaration",
+            Warning::UnusedDef(_) => "Unused Definition",
+            Warning::NeverPrimed(_) => "Never Changed Variable",
+            Warning::Unconstrained(_) => "Unconstrained Constant",
+            Warning::UnsatTransition(_) => "Unsatisfiable Transition",
+            Warning::UnassignedScope(_) => "Unchanged Scope Variable",
+        }
+    }
+
+    /// Names and positions
+    pub fn items(&self, model: &Model) -> Vec<(String, Option<Position>)> {
+        match self {
+            Warning::UnboundedDec(v)
+            | Warning::UnusedDec(v)
+            | Warning::NeverPrimed(v)
+            | Warning::Unconstrained(v) => {
+                v.iter().map(|id| naming(model.get(*id).unwrap())).collect()
+            }
+            Warning::UnboundedFun(v) => {
+                v.iter().map(|id| naming(model.get(*id).unwrap())).collect()
+            }
+            Warning::UnusedDef(v) => v.iter().map(|id| naming(model.get(*id).unwrap())).collect(),
+            Warning::UnsatTransition(v) => {
+                v.iter().map(|id| naming(model.get(*id).unwrap())).collect()
+            }
+            Warning::UnassignedScope(v) => v
+                .iter()
+                .map(|(id, position)| {
+                    (model.get(*id).unwrap().name().to_string(), position.clone())
+                })
+                .collect(),
+        }
+    }
+
+    /// One line message for each item
+    pub fn messages(&self, model: &Model) -> Vec<(String, Option<Position>)> {
+        let message = |name: &str| match self {
+            Warning::UnboundedDec(_) => format!("variable '{}' has an unbounded type", name),
+            Warning::UnboundedFun(_) => format!("function '{}' has an unbounded type", name),
+            Warning::UnusedDec(_) => format!("'{}' is never used", name),
+            Warning::UnusedDef(_) => format!("definition '{}' is never used", name),
+            Warning::NeverPrimed(_) => {
+                format!(
+                    "variable '{}' is never changed ({}') by a transition",
+                    name, name
+                )
+            }
+            Warning::Unconstrained(_) => {
+                format!("constant '{}' is not constrained by an init", name)
+            }
+            Warning::UnsatTransition(_) => format!("transition '{}' is always false", name),
+            Warning::UnassignedScope(_) => {
+                format!(
+                    "'{}' is in the scope but never changed ({}') in it",
+                    name, name
                 )
             }
-            Warning::UnboundedFun(_) => todo!(),
+        };
+        self.items(model)
+            .into_iter()
+            .map(|(name, position)| (message(&name), position))
+            .collect()
+    }
+
+    pub fn to_entry(&self, model: &Model) -> d_stuff::Entry {
+        let mut messages = vec![];
+
+        for (name, position) in self.items(model) {
+            messages.push(Message::new(
+                Some(d_stuff::Text::new(
+                    format!("'{}'", name),
+                    termion::style::Reset.to_string(),
+                    termion::color::LightBlue.fg_str(),
+                )),
+                match position {
+                    Some(pos) => d_stuff::Text::new(
+                        format!("{}", pos),
+                        termion::style::Reset.to_string(),
+                        termion::color::Cyan.fg_str(),
+                    ),
+                    None => d_stuff::Text::new(
+                        "",
+                        termion::style::Reset.to_string(),
+                        termion::color::Cyan.fg_str(),
+                    ),
+                },
+            ));
         }
+
+        d_stuff::Entry::new(
+            d_stuff::Status::Info,
+            d_stuff::Text::new(
+                self.title(),
+                termion::style::Bold.to_string(),
+                termion::color::Blue.fg_str(),
+            ),
+            Some(d_stuff::Text::new(
+                "Warning",
+                termion::style::Reset.to_string(),
+                termion::color::Red.fg_str(),
+            )),
+            messages,
+        )
     }
 }
@@ -9,6 +9,14 @@ use fraction::Fraction;
 pub enum PrefixUnaryOperator {
     Not,
     Neg,
+    BitNot,
+    Card,
+    Abs,
+    ToReal,
+    ToInt,
+    Succ,
+    Pred,
+    Ord,
 }
 
 impl PrefixUnaryOperator {
@@ -22,6 +30,14 @@ impl std::fmt::Display for PrefixUnaryOperator {
         match self {
             PrefixUnaryOperator::Not => write!(f, "not"),
             PrefixUnaryOperator::Neg => write!(f, "-"),
+            PrefixUnaryOperator::BitNot => write!(f, "lnot"),
+            PrefixUnaryOperator::Card => write!(f, "card"),
+            PrefixUnaryOperator::Abs => write!(f, "abs"),
+            PrefixUnaryOperator::ToReal => write!(f, "to_real"),
+            PrefixUnaryOperator::ToInt => write!(f, "to_int"),
+            PrefixUnaryOperator::Succ => write!(f, "succ"),
+            PrefixUnaryOperator::Pred => write!(f, "pred"),
+            PrefixUnaryOperator::Ord => write!(f, "ord"),
         }
     }
 }
@@ -40,6 +56,21 @@ pub enum BinaryOperator {
     Implies,
     Min,
     Max,
+    // division
+    Div,
+    IntDiv,
+    Mod,
+    // bit-vectors
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
+    // sets
+    In,
+    Union,
+    Inter,
+    Diff,
 }
 
 impl BinaryOperator {
@@ -62,6 +93,21 @@ impl std::fmt::Display for BinaryOperator {
             //
             Self::Min => write!(f, "min"),
             Self::Max => write!(f, "max"),
+            //
+            Self::Div => write!(f, "/"),
+            Self::IntDiv => write!(f, "div"),
+            Self::Mod => write!(f, "mod"),
+            //
+            Self::BitAnd => write!(f, "land"),
+            Self::BitOr => write!(f, "lor"),
+            Self::BitXor => write!(f, "lxor"),
+            Self::Shl => write!(f, "shl"),
+            Self::Shr => write!(f, "shr"),
+            //
+            Self::In => write!(f, "in"),
+            Self::Union => write!(f, "union"),
+            Self::Inter => write!(f, "inter"),
+            Self::Diff => write!(f, "diff"),
         }
     }
 }
@@ -128,6 +174,34 @@ impl std::fmt::Display for QtOperator {
     }
 }
 
+//-------------------------------------------------- Trace --------------------------------------------------
+
+/// Aggregate of an expression over all the states of the trace
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum TraceOperator {
+    Sum,
+    Min,
+    Max,
+    Count,
+}
+
+impl TraceOperator {
+    pub fn new(&self, e: Expr) -> Expression {
+        Expression::Trace(*self, Box::new(e))
+    }
+}
+
+impl std::fmt::Display for TraceOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TraceOperator::Sum => write!(f, "sum_trace"),
+            TraceOperator::Min => write!(f, "min_trace"),
+            TraceOperator::Max => write!(f, "max_trace"),
+            TraceOperator::Count => write!(f, "count_trace"),
+        }
+    }
+}
+
 //-------------------------------------------------- LTL Operator --------------------------------------------------
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
@@ -249,6 +323,13 @@ pub enum Expression {
     //
     Apply(Box<Expr>, Vec<Expr>),
     //
+    Index(Box<Expr>, Box<Expr>),
+    Update(Box<Expr>, Box<Expr>, Box<Expr>),
+    /// array value: the element of each index
+    ArrayValue(Vec<(Expr, Expr)>),
+    /// set value: the elements of the set
+    SetValue(Vec<Expr>),
+    //
     As(Box<Expr>, Type, Box<Expr>),
     //
     Following(Box<Expr>),
@@ -256,7 +337,10 @@ pub enum Expression {
     Scope(Vec<Expr>, Box<Expr>),
     //
     IfThenElse(Box<Expr>, Box<Expr>, Vec<(Expr, Expr)>, Box<Expr>),
+    Match(Box<Expr>, Vec<(Expr, Expr)>, Option<Box<Expr>>),
+    Let(Parameter, Box<Expr>, Box<Expr>),
     Quantifier(QtOperator, Vec<Parameter>, Box<Expr>),
+    Trace(TraceOperator, Box<Expr>),
     //
     // LTL
     LTLunary(LTLUnaryOperator, Box<Expr>),
@@ -264,6 +348,8 @@ pub enum Expression {
     //
     LTLVariable(LTLVariableId),
     //
+    First(Type),
+    Last(Type),
     Unresolved(String),
 }
 
@@ -317,6 +403,27 @@ impl ToLang for Expression {
                 res
             }
             //
+            Expression::Index(array, index) => {
+                format!("{}[{}]", array.to_lang(model), index.to_lang(model))
+            }
+            Expression::Update(array, index, value) => format!(
+                "({} with [{}] = {})",
+                array.to_lang(model),
+                index.to_lang(model),
+                value.to_lang(model)
+            ),
+            Expression::ArrayValue(list) => {
+                let l: Vec<String> = list
+                    .iter()
+                    .map(|(i, v)| format!("{}: {}", i.to_lang(model), v.to_lang(model)))
+                    .collect();
+                format!("[{}]", l.join(", "))
+            }
+            Expression::SetValue(list) => {
+                let l: Vec<String> = list.iter().map(|e| e.to_lang(model)).collect();
+                format!("{{{}}}", l.join(", "))
+            }
+            //
             Expression::As(kid, typ, default) => format!(
                 "{} as {} default {}",
                 kid.to_lang(model),
@@ -360,6 +467,22 @@ impl ToLang for Expression {
                 s.push_str(&format!(" else {} end", e.to_lang(model)));
                 s
             }
+            Expression::Match(e, cases, default) => {
+                let mut l: Vec<String> = cases
+                    .iter()
+                    .map(|(p, x)| format!("{} -> {}", p.to_lang(model), x.to_lang(model)))
+                    .collect();
+                if let Some(default) = default {
+                    l.push(format!("_ -> {}", default.to_lang(model)));
+                }
+                format!("match {} with {} end", e.to_lang(model), l.join(" | "))
+            }
+            Expression::Let(p, e, body) => format!(
+                "(let {} = {} in {})",
+                p.name(),
+                e.to_lang(model),
+                body.to_lang(model)
+            ),
             Expression::Quantifier(op, p, e) => {
                 let mut s = format!("{} ", op);
                 if let Some((first, others)) = p.split_first() {
@@ -371,6 +494,7 @@ impl ToLang for Expression {
                 s.push_str(&format!(" | {} end", e.to_lang(model)));
                 s
             }
+            Expression::Trace(op, e) => format!("{}({})", op, e.to_lang(model)),
             //
             Expression::LTLunary(op, e) => format!("({} {})", op, e.to_lang(model)),
             Expression::LTLbinary(l, o, r) => {
@@ -379,6 +503,8 @@ impl ToLang for Expression {
             //
             Expression::LTLVariable(id) => model.get(*id).unwrap().name(),
             //
+            Expression::First(typ) => format!("first({})", typ.to_lang(model)),
+            Expression::Last(typ) => format!("last({})", typ.to_lang(model)),
             Expression::Unresolved(name) => format!("?{}", name),
         }
     }
@@ -434,6 +560,27 @@ impl ToDebug for Expression {
                 res
             }
             //
+            Expression::Index(array, index) => {
+                format!("{}[{}]", array.to_debug(model), index.to_debug(model))
+            }
+            Expression::Update(array, index, value) => format!(
+                "({} with [{}] = {})",
+                array.to_debug(model),
+                index.to_debug(model),
+                value.to_debug(model)
+            ),
+            Expression::ArrayValue(list) => {
+                let l: Vec<String> = list
+                    .iter()
+                    .map(|(i, v)| format!("{}: {}", i.to_debug(model), v.to_debug(model)))
+                    .collect();
+                format!("[{}]", l.join(", "))
+            }
+            Expression::SetValue(list) => {
+                let l: Vec<String> = list.iter().map(|e| e.to_debug(model)).collect();
+                format!("{{{}}}", l.join(", "))
+            }
+            //
             Expression::As(kid, typ, default) => format!(
                 "{} as {} default {}",
                 kid.to_debug(model),
@@ -476,6 +623,22 @@ impl ToDebug for Expression {
                 s.push_str(&format!(" else {} end", e.to_debug(model)));
                 s
             }
+            Expression::Match(e, cases, default) => {
+                let mut l: Vec<String> = cases
+                    .iter()
+                    .map(|(p, x)| format!("{} -> {}", p.to_debug(model), x.to_debug(model)))
+                    .collect();
+                if let Some(default) = default {
+                    l.push(format!("_ -> {}", default.to_debug(model)));
+                }
+                format!("match {} with {} end", e.to_debug(model), l.join(" | "))
+            }
+            Expression::Let(p, e, body) => format!(
+                "(let {} = {} in {})",
+                p.name(),
+                e.to_debug(model),
+                body.to_debug(model)
+            ),
             Expression::Quantifier(op, p, e) => {
                 let mut s = format!("{} ", op);
                 if let Some((first, others)) = p.split_first() {
@@ -487,6 +650,7 @@ impl ToDebug for Expression {
                 s.push_str(&format!(" | {} end", e.to_debug(model)));
                 s
             }
+            Expression::Trace(op, e) => format!("{}({})", op, e.to_debug(model)),
             //
             Expression::LTLunary(op, e) => format!("({} {})", op, e.to_debug(model)),
             Expression::LTLbinary(l, o, r) => {
@@ -495,6 +659,8 @@ impl ToDebug for Expression {
             //
             Expression::LTLVariable(id) => model.get(*id).unwrap().to_debug(model),
             //
+            Expression::First(typ) => format!("first({})", typ.to_lang(model)),
+            Expression::Last(typ) => format!("last({})", typ.to_lang(model)),
             Expression::Unresolved(name) => format!("?{}", name),
         }
     }
@@ -8,13 +8,18 @@ impl Expr {
             Expression::Int(_) => self.clone(),
             Expression::Real(_) => self.clone(),
             //
-            Expression::PrefixUnary(op, kid) => op.new(kid.flatten_ltl(model)).into(),
-            Expression::Binary(left, op, right) => op
-                .new(left.flatten_ltl(model), right.flatten_ltl(model))
-                .into(),
+            Expression::PrefixUnary(op, kid) => {
+                let kid = kid.flatten_ltl(model);
+                Expr::new(op.new(kid), self.position().clone())
+            }
+            Expression::Binary(left, op, right) => {
+                let left = left.flatten_ltl(model);
+                let right = right.flatten_ltl(model);
+                Expr::new(op.new(left, right), self.position().clone())
+            }
             Expression::Nary(op, list) => {
                 let list = list.iter().map(|x| x.flatten_ltl(model)).collect();
-                op.new(list).into()
+                Expr::new(op.new(list), self.position().clone())
             }
             //
             Expression::EnumerateElement(_) => self.clone(),
@@ -31,6 +36,25 @@ impl Expr {
                 let expression = Expression::Apply(Box::new(fun), params);
                 Expr::new(expression, self.position().clone())
             }
+            Expression::Index(array, index) => {
+                let array = array.flatten_ltl(model);
+                let index = index.flatten_ltl(model);
+                let expression = Expression::Index(Box::new(array), Box::new(index));
+                Expr::new(expression, self.position().clone())
+            }
+            Expression::Update(array, index, value) => {
+                let array = array.flatten_ltl(model);
+                let index = index.flatten_ltl(model);
+                let value = value.flatten_ltl(model);
+                let expression =
+                    Expression::Update(Box::new(array), Box::new(index), Box::new(value));
+                Expr::new(expression, self.position().clone())
+            }
+            Expression::ArrayValue(_) => self.clone(),
+            Expression::SetValue(list) => {
+                let list = list.iter().map(|e| e.flatten_ltl(model)).collect();
+                Expr::new(Expression::SetValue(list), self.position().clone())
+            }
             Expression::As(kid, typ, default) => {
                 let kid = kid.flatten_ltl(model);
                 let default = default.flatten_ltl(model);
@@ -70,11 +94,31 @@ impl Expr {
                     Expression::IfThenElse(Box::new(ce), Box::new(te), list, Box::new(ee));
                 Expr::new(expression, self.position().clone())
             }
+            Expression::Match(e, cases, default) => {
+                let e = e.flatten_ltl(model);
+                let cases = cases
+                    .iter()
+                    .map(|(p, x)| (p.clone(), x.flatten_ltl(model)))
+                    .collect();
+                let default = default.as_ref().map(|x| Box::new(x.flatten_ltl(model)));
+                let expression = Expression::Match(Box::new(e), cases, default);
+                Expr::new(expression, self.position().clone())
+            }
+            Expression::Let(p, e, body) => {
+                let e = e.flatten_ltl(model);
+                let body = body.flatten_ltl(model);
+                let expression = Expression::Let(p.clone(), Box::new(e), Box::new(body));
+                Expr::new(expression, self.position().clone())
+            }
             Expression::Quantifier(op, params, e) => {
                 let e = e.flatten_ltl(model);
                 let expression = op.new(params.clone(), e);
                 Expr::new(expression, self.position().clone())
             }
+            Expression::Trace(op, e) => {
+                let e = e.flatten_ltl(model);
+                Expr::new(op.new(e), self.position().clone())
+            }
             //
             Expression::LTLunary(op, kid) => {
                 let kid = kid.flatten_ltl(model);
@@ -91,6 +135,7 @@ impl Expr {
             }
             Expression::LTLVariable(_) => self.clone(),
             //
+            Expression::First(_) | Expression::Last(_) => self.clone(),
             Expression::Unresolved(_) => self.clone(),
         }
     }
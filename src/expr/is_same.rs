@@ -27,6 +27,20 @@ impl Expr {
             (Expression::Apply(f1, p1), Expression::Apply(f2, p2)) => {
                 f1.is_same(f2) && Expr::all_same(p1, p2)
             }
+            (Expression::Index(a1, i1), Expression::Index(a2, i2)) => {
+                a1.is_same(a2) && i1.is_same(i2)
+            }
+            (Expression::Update(a1, i1, v1), Expression::Update(a2, i2, v2)) => {
+                a1.is_same(a2) && i1.is_same(i2) && v1.is_same(v2)
+            }
+            (Expression::SetValue(l1), Expression::SetValue(l2)) => Expr::all_same(l1, l2),
+            (Expression::ArrayValue(l1), Expression::ArrayValue(l2)) => {
+                l1.len() == l2.len()
+                    && l1
+                        .iter()
+                        .zip(l2.iter())
+                        .all(|((i1, v1), (i2, v2))| i1.is_same(i2) && v1.is_same(v2))
+            }
             //
             (Expression::As(k1, t1, d1), Expression::As(k2, t2, d2)) => {
                 k1.is_same(k2) && t1 == t2 && d1.is_same(d2)
@@ -56,6 +70,25 @@ impl Expr {
                         .all(|((x1, y1), (x2, y2))| x1.is_same(x2) && y1.is_same(y2))
                     && e1.is_same(e2)
             }
+            (Expression::Let(p1, e1, b1), Expression::Let(p2, e2, b2)) => {
+                p1.is_same(p2) && e1.is_same(e2) && b1.is_same(b2)
+            }
+            (Expression::Match(e1, l1, d1), Expression::Match(e2, l2, d2)) => {
+                e1.is_same(e2)
+                    && l1.len() == l2.len()
+                    && l1
+                        .iter()
+                        .zip(l2.iter())
+                        .all(|((x1, y1), (x2, y2))| x1.is_same(x2) && y1.is_same(y2))
+                    && match (d1, d2) {
+                        (Some(d1), Some(d2)) => d1.is_same(d2),
+                        (None, None) => true,
+                        _ => false,
+                    }
+            }
+            (Expression::Trace(op1, e1), Expression::Trace(op2, e2)) => {
+                op1 == op2 && e1.is_same(e2)
+            }
             (Expression::Quantifier(op1, p1, e1), Expression::Quantifier(op2, p2, e2)) => {
                 op1 == op2
                     && p1.len() == p2.len()
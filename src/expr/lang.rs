@@ -309,6 +309,29 @@ impl Expr {
         let expression = Expression::State(kid, state_expr, None);
         Expr::new(expression, None)
     }
+    /// The match as an if-then-else chain (without default, the last case is the else)
+    pub fn match_to_ite(e: &Expr, cases: &[(Expr, Expr)], default: &Option<Box<Expr>>) -> Expr {
+        let (cases, other) = match default {
+            Some(default) => (cases, default.as_ref()),
+            None => {
+                let (last, cases) = cases.split_last().unwrap();
+                (cases, &last.1)
+            }
+        };
+        match cases.split_first() {
+            Some(((p, x), others)) => {
+                let list = others
+                    .iter()
+                    .map(|(p, x)| (e.clone().eq(p.clone()), x.clone()))
+                    .collect();
+                let c = Box::new(e.clone().eq(p.clone()));
+                let expression =
+                    Expression::IfThenElse(c, Box::new(x.clone()), list, Box::new(other.clone()));
+                Expr::new(expression, None)
+            }
+            None => other.clone(),
+        }
+    }
 }
 
 impl std::ops::Add for Expr {
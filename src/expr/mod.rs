@@ -33,3 +33,6 @@ pub mod substitute;
 
 pub mod flatten;
 // pub use flatten::*;
+
+pub mod references;
+// pub use references::*;
@@ -36,6 +36,48 @@ impl Expr {
                             -kid
                         }
                     },
+                    PrefixUnaryOperator::Abs => match kid.expression() {
+                        Expression::Int(value) => value.abs().into(),
+                        Expression::Real(value) => value.abs().into(),
+                        _ => Expr::new(op.new(kid), self.position().clone()),
+                    },
+                    PrefixUnaryOperator::ToReal => match kid.expression() {
+                        Expression::Int(value) => Fraction::from(*value).into(),
+                        _ => Expr::new(op.new(kid), self.position().clone()),
+                    },
+                    PrefixUnaryOperator::ToInt => match kid.expression() {
+                        Expression::Real(value) => {
+                            let floor = value.floor();
+                            let value = *floor.numer().unwrap() as i64;
+                            if floor.is_sign_negative() {
+                                (-value).into()
+                            } else {
+                                value.into()
+                            }
+                        }
+                        _ => Expr::new(op.new(kid), self.position().clone()),
+                    },
+                    PrefixUnaryOperator::Succ | PrefixUnaryOperator::Pred => {
+                        match kid.expression() {
+                            Expression::EnumerateElement(id) => {
+                                let elements = model.get(id.enumerate_id()).unwrap().elements();
+                                let index = if *op == PrefixUnaryOperator::Succ {
+                                    (id.index() + 1).min(elements.len() - 1)
+                                } else {
+                                    id.index().saturating_sub(1)
+                                };
+                                Expression::EnumerateElement(elements[index].id()).into()
+                            }
+                            _ => Expr::new(op.new(kid), self.position().clone()),
+                        }
+                    }
+                    PrefixUnaryOperator::Ord => match kid.expression() {
+                        Expression::EnumerateElement(id) => (id.index() as i64).into(),
+                        _ => Expr::new(op.new(kid), self.position().clone()),
+                    },
+                    PrefixUnaryOperator::BitNot | PrefixUnaryOperator::Card => {
+                        Expr::new(op.new(kid), self.position().clone())
+                    }
                 }
             }
             Expression::Binary(left, op, right) => {
@@ -126,6 +168,9 @@ impl Expr {
                     }
                     BinaryOperator::Lt => {
                         match (left.expression(), right.expression()) {
+                            (Expression::EnumerateElement(l), Expression::EnumerateElement(r)) => {
+                                return (l.index() < r.index()).into();
+                            }
                             (Expression::Int(l), Expression::Int(r)) => {
                                 if l < r {
                                     return true.into();
@@ -156,6 +201,9 @@ impl Expr {
                     }
                     BinaryOperator::Le => {
                         match (left.expression(), right.expression()) {
+                            (Expression::EnumerateElement(l), Expression::EnumerateElement(r)) => {
+                                return (l.index() <= r.index()).into();
+                            }
                             (Expression::Int(l), Expression::Int(r)) => {
                                 if l <= r {
                                     return true.into();
@@ -186,6 +234,9 @@ impl Expr {
                     }
                     BinaryOperator::Ge => {
                         match (left.expression(), right.expression()) {
+                            (Expression::EnumerateElement(l), Expression::EnumerateElement(r)) => {
+                                return (l.index() >= r.index()).into();
+                            }
                             (Expression::Int(l), Expression::Int(r)) => {
                                 if l >= r {
                                     return true.into();
@@ -216,6 +267,9 @@ impl Expr {
                     }
                     BinaryOperator::Gt => {
                         match (left.expression(), right.expression()) {
+                            (Expression::EnumerateElement(l), Expression::EnumerateElement(r)) => {
+                                return (l.index() > r.index()).into();
+                            }
                             (Expression::Int(l), Expression::Int(r)) => {
                                 if l > r {
                                     return true.into();
@@ -260,6 +314,44 @@ impl Expr {
                     }
                     BinaryOperator::Min => left.min(right),
                     BinaryOperator::Max => left.max(right),
+                    BinaryOperator::Div => match (left.expression(), right.expression()) {
+                        (Expression::Int(l), Expression::Int(r)) if *r != 0 => {
+                            (Fraction::from(*l) / Fraction::from(*r)).into()
+                        }
+                        (Expression::Int(l), Expression::Real(r)) if !r.is_zero() => {
+                            (Fraction::from(*l) / *r).into()
+                        }
+                        (Expression::Real(l), Expression::Int(r)) if *r != 0 => {
+                            (l / Fraction::from(*r)).into()
+                        }
+                        (Expression::Real(l), Expression::Real(r)) if !r.is_zero() => {
+                            (l / r).into()
+                        }
+                        _ => Expr::new(op.new(left, right), self.position().clone()),
+                    },
+                    BinaryOperator::IntDiv | BinaryOperator::Mod => {
+                        match (left.expression(), right.expression()) {
+                            (Expression::Int(l), Expression::Int(r)) if *r != 0 => {
+                                if *op == BinaryOperator::IntDiv {
+                                    l.div_euclid(*r).into()
+                                } else {
+                                    l.rem_euclid(*r).into()
+                                }
+                            }
+                            _ => Expr::new(op.new(left, right), self.position().clone()),
+                        }
+                    }
+                    BinaryOperator::BitAnd
+                    | BinaryOperator::BitOr
+                    | BinaryOperator::BitXor
+                    | BinaryOperator::Shl
+                    | BinaryOperator::Shr
+                    | BinaryOperator::In
+                    | BinaryOperator::Union
+                    | BinaryOperator::Inter
+                    | BinaryOperator::Diff => {
+                        Expr::new(op.new(left, right), self.position().clone())
+                    }
                 }
             }
             Expression::Nary(op, kids) => match op {
@@ -461,6 +553,25 @@ impl Expr {
                     Expression::Apply(Box::new(fun), params).into()
                 }
             }
+            Expression::Index(array, index) => {
+                let array = array.propagate(model);
+                let index = index.propagate(model);
+                let expression = Expression::Index(Box::new(array), Box::new(index));
+                Expr::new(expression, self.position().clone())
+            }
+            Expression::Update(array, index, value) => {
+                let array = array.propagate(model);
+                let index = index.propagate(model);
+                let value = value.propagate(model);
+                let expression =
+                    Expression::Update(Box::new(array), Box::new(index), Box::new(value));
+                Expr::new(expression, self.position().clone())
+            }
+            Expression::ArrayValue(_) => self.clone(),
+            Expression::SetValue(list) => {
+                let list = list.iter().map(|e| e.propagate(model)).collect();
+                Expr::new(Expression::SetValue(list), self.position().clone())
+            }
             Expression::As(kid, typ, default) => {
                 let kid = kid.propagate(model);
                 let default = default.propagate(model);
@@ -527,11 +638,19 @@ impl Expr {
                     Expression::IfThenElse(Box::new(ce), Box::new(te), v, Box::new(ee));
                 Expr::new(expression, None)
             }
+            Expression::Match(e, cases, default) => {
+                Expr::match_to_ite(e, cases, default).propagate(model)
+            }
+            Expression::Let(p, e, body) => body.substitute(&p.clone().into(), e).propagate(model),
             Expression::Quantifier(op, params, e) => {
                 let e = e.propagate(model);
                 let expression = op.new(params.clone(), e);
                 Expr::new(expression, self.position().clone())
             }
+            Expression::Trace(op, e) => {
+                let e = e.propagate(model);
+                Expr::new(op.new(e), self.position().clone())
+            }
             //
             Expression::LTLunary(op, kid) => {
                 let kid = kid.propagate(model);
@@ -546,6 +665,7 @@ impl Expr {
             }
             Expression::LTLVariable(_) => self.clone(),
             //
+            Expression::First(_) | Expression::Last(_) => self.clone(),
             Expression::Unresolved(_) => self.clone(),
         }
     }
@@ -0,0 +1,198 @@
+use super::*;
+use crate::model::*;
+
+impl Expr {
+    /**
+     * get the direct sub-expressions
+     */
+    pub fn kids(&self) -> Vec<&Expr> {
+        match self.expression() {
+            Expression::Bool(_) => vec![],
+            Expression::Int(_) => vec![],
+            Expression::Real(_) => vec![],
+            //
+            Expression::PrefixUnary(_, kid) => vec![kid],
+            Expression::Binary(left, _, right) => vec![left, right],
+            Expression::Nary(_, kids) => kids.iter().collect(),
+            //
+            Expression::EnumerateElement(_) => vec![],
+            Expression::Declaration(_) => vec![],
+            Expression::Definition(_) => vec![],
+            Expression::FunDec(_) => vec![],
+            Expression::FunDef(_) => vec![],
+            Expression::Parameter(_) => vec![],
+            Expression::LtlDefinition(_) => vec![],
+            //
+            Expression::Apply(fun, params) => {
+                let mut v = vec![fun.as_ref()];
+                v.extend(params.iter());
+                v
+            }
+            Expression::Index(array, index) => vec![array, index],
+            Expression::Update(array, index, value) => vec![array, index, value],
+            Expression::ArrayValue(list) => {
+                let mut v = vec![];
+                for (i, e) in list.iter() {
+                    v.push(i);
+                    v.push(e);
+                }
+                v
+            }
+            Expression::SetValue(list) => list.iter().collect(),
+            Expression::As(kid, _, default) => vec![kid, default],
+            //
+            Expression::Following(kid) => vec![kid],
+            Expression::State(kid, _, default) => match default {
+                Some(default) => vec![kid, default],
+                None => vec![kid],
+            },
+            Expression::Scope(l, e) => {
+                let mut v: Vec<&Expr> = l.iter().collect();
+                v.push(e);
+                v
+            }
+            //
+            Expression::IfThenElse(c, t, l, e) => {
+                let mut v = vec![c.as_ref(), t.as_ref()];
+                for (x, y) in l.iter() {
+                    v.push(x);
+                    v.push(y);
+                }
+                v.push(e);
+                v
+            }
+            Expression::Let(_, e, body) => vec![e.as_ref(), body.as_ref()],
+            Expression::Match(e, cases, default) => {
+                let mut v = vec![e.as_ref()];
+                for (p, x) in cases.iter() {
+                    v.push(p);
+                    v.push(x);
+                }
+                if let Some(default) = default {
+                    v.push(default);
+                }
+                v
+            }
+            Expression::Quantifier(_, _, e) => vec![e],
+            Expression::Trace(_, e) => vec![e],
+            //
+            Expression::LTLunary(_, kid) => vec![kid],
+            Expression::LTLbinary(left, _, right) => vec![left, right],
+            Expression::LTLVariable(_) => vec![],
+            Expression::First(_) | Expression::Last(_) => vec![],
+            Expression::Unresolved(_) => vec![],
+        }
+    }
+
+    /**
+     * get all the declarations used in the expression (without duplicate)
+     */
+    pub fn declarations(&self) -> Vec<DeclarationId> {
+        let mut res = vec![];
+        self.add_declarations(&mut res);
+        res
+    }
+
+    fn add_declarations(&self, res: &mut Vec<DeclarationId>) {
+        if let Expression::Declaration(id) = self.expression() {
+            if !res.contains(id) {
+                res.push(*id);
+            }
+        }
+        for kid in self.kids() {
+            kid.add_declarations(res);
+        }
+    }
+
+    /**
+     * get all the declarations used in a following expression (x')
+     */
+    pub fn following_declarations(&self) -> Vec<DeclarationId> {
+        let mut res = vec![];
+        self.add_following_declarations(&mut res);
+        res
+    }
+
+    fn add_following_declarations(&self, res: &mut Vec<DeclarationId>) {
+        match self.expression() {
+            Expression::Following(kid) => kid.add_declarations(res),
+            // the local name stands for its value (let z = x in z' = z + 1)
+            Expression::Let(p, e, body) => {
+                e.add_following_declarations(res);
+                body.substitute(&p.clone().into(), e)
+                    .add_following_declarations(res);
+            }
+            _ => {
+                for kid in self.kids() {
+                    kid.add_following_declarations(res);
+                }
+            }
+        }
+    }
+
+    /**
+     * get all the declarations used in the current state (not in x' nor in a scope list)
+     */
+    pub fn current_declarations(&self) -> Vec<DeclarationId> {
+        let mut res = vec![];
+        self.add_current_declarations(&mut res);
+        res
+    }
+
+    fn add_current_declarations(&self, res: &mut Vec<DeclarationId>) {
+        match self.expression() {
+            Expression::Declaration(id) => {
+                if !res.contains(id) {
+                    res.push(*id);
+                }
+            }
+            Expression::Following(_) => {}
+            Expression::Scope(_, e) => e.add_current_declarations(res),
+            _ => {
+                for kid in self.kids() {
+                    kid.add_current_declarations(res);
+                }
+            }
+        }
+    }
+
+    /**
+     * get all the definitions used in the expression (without duplicate)
+     */
+    pub fn definitions(&self) -> Vec<DefinitionId> {
+        let mut res = vec![];
+        self.add_definitions(&mut res);
+        res
+    }
+
+    fn add_definitions(&self, res: &mut Vec<DefinitionId>) {
+        if let Expression::Definition(id) = self.expression() {
+            if !res.contains(id) {
+                res.push(*id);
+            }
+        }
+        for kid in self.kids() {
+            kid.add_definitions(res);
+        }
+    }
+
+    /**
+     * get all the trace aggregates of the expression, inner first (without duplicate)
+     */
+    pub fn trace_aggregates(&self) -> Vec<Expr> {
+        let mut res = vec![];
+        self.add_trace_aggregates(&mut res);
+        res
+    }
+
+    pub fn add_trace_aggregates(&self, res: &mut Vec<Expr>) {
+        for kid in self.kids() {
+            kid.add_trace_aggregates(res);
+        }
+        if let Expression::Trace(_, _) = self.expression() {
+            if !res.iter().any(|x| x.is_same(self)) {
+                res.push(self.clone());
+            }
+        }
+    }
+}
@@ -2,6 +2,7 @@ use super::*;
 use crate::common::*;
 use crate::error::Error;
 use crate::model::*;
+use crate::typing::*;
 
 impl Expr {
     //---------- Resolve ----------
@@ -44,6 +45,25 @@ impl Expr {
                 }
                 Expression::Apply(Box::new(f), v)
             }
+            Expression::Index(array, index) => {
+                let array = array.resolve(model, entries)?;
+                let index = index.resolve(model, entries)?;
+                Expression::Index(Box::new(array), Box::new(index))
+            }
+            Expression::Update(array, index, value) => {
+                let array = array.resolve(model, entries)?;
+                let index = index.resolve(model, entries)?;
+                let value = value.resolve(model, entries)?;
+                Expression::Update(Box::new(array), Box::new(index), Box::new(value))
+            }
+            e @ Expression::ArrayValue(_) => e.clone(),
+            Expression::SetValue(list) => {
+                let mut l = vec![];
+                for e in list.iter() {
+                    l.push(e.resolve(model, entries)?);
+                }
+                Expression::SetValue(l)
+            }
             Expression::As(kid, typ, default) => {
                 let kid = kid.resolve(model, entries)?;
                 let default = default.resolve(model, entries)?;
@@ -85,6 +105,29 @@ impl Expr {
                 let ee = ee.resolve(model, entries)?;
                 Expression::IfThenElse(Box::new(ie), Box::new(te), v, Box::new(ee))
             }
+            Expression::Match(e, cases, default) => {
+                let e = e.resolve(model, entries)?;
+                let mut v = Vec::new();
+                for (p, x) in cases.iter() {
+                    let p = p.resolve(model, entries)?;
+                    let x = x.resolve(model, entries)?;
+                    v.push((p, x));
+                }
+                let default = match default {
+                    Some(default) => Some(Box::new(default.resolve(model, entries)?)),
+                    None => None,
+                };
+                Expression::Match(Box::new(e), v, default)
+            }
+            Expression::Let(p, e, body) => {
+                let e = e.resolve(model, entries)?;
+                // the type of the local name is the type of its value
+                let p = Parameter::new(p.name(), e.get_type(model), p.position().clone());
+                let mut entries = entries.clone();
+                entries.push((&p).into());
+                let body = body.resolve(model, &entries)?;
+                Expression::Let(p, Box::new(e), Box::new(body))
+            }
             Expression::Quantifier(op, params, e) => {
                 let mut entries = entries.clone();
                 for p in params.iter() {
@@ -95,6 +138,7 @@ impl Expr {
                 let e = e.resolve(model, &entries)?;
                 op.new(params, e)
             }
+            Expression::Trace(op, e) => op.new(e.resolve(model, entries)?),
             //
             Expression::LTLunary(op, e) => {
                 let e = e.resolve(model, entries)?;
@@ -107,6 +151,37 @@ impl Expr {
             }
             e @ Expression::LTLVariable(_) => e.clone(),
             //
+            Expression::First(typ) | Expression::Last(typ) => {
+                let first = matches!(self.expression(), Expression::First(_));
+                if let Type::Interval(id) = typ {
+                    let interval = model.get(*id).unwrap();
+                    let bound = if first {
+                        interval.min_expr()
+                    } else {
+                        interval.max_expr()
+                    };
+                    return bound.resolve(model, entries);
+                }
+                match typ.get_type(model) {
+                    Type::Enumerate(id) if model.get(id).unwrap().is_ordered() => {
+                        let elements = model.get(id).unwrap().elements();
+                        let element = if first {
+                            elements.first()
+                        } else {
+                            elements.last()
+                        };
+                        Expression::EnumerateElement(element.unwrap().id())
+                    }
+                    Type::IntInterval(min, max) => Expression::Int(if first { min } else { max }),
+                    t => {
+                        return Err(Error::Type {
+                            expr: self.clone(),
+                            typ: t,
+                            expected: vec![],
+                        })
+                    }
+                }
+            }
             Expression::Unresolved(name) => match &get_entry(name, entries) {
                 Some(entry) => entry.into(),
                 None => {
@@ -49,6 +49,25 @@ impl Expr {
                 }
                 Expression::Apply(Box::new(e), l)
             }
+            Expression::Index(array, index) => {
+                let array = array.resolve_type(types)?;
+                let index = index.resolve_type(types)?;
+                Expression::Index(Box::new(array), Box::new(index))
+            }
+            Expression::Update(array, index, value) => {
+                let array = array.resolve_type(types)?;
+                let index = index.resolve_type(types)?;
+                let value = value.resolve_type(types)?;
+                Expression::Update(Box::new(array), Box::new(index), Box::new(value))
+            }
+            e @ Expression::ArrayValue(_) => e.clone(),
+            Expression::SetValue(list) => {
+                let mut l = vec![];
+                for e in list.iter() {
+                    l.push(e.resolve_type(types)?);
+                }
+                Expression::SetValue(l)
+            }
             Expression::As(kid, typ, default) => {
                 let kid = kid.resolve_type(types)?;
                 let typ = typ.resolve(types)?;
@@ -87,6 +106,23 @@ impl Expr {
                 let e = e.resolve_type(types)?;
                 Expression::IfThenElse(Box::new(c), Box::new(t), l, Box::new(e))
             }
+            Expression::Match(e, cases, default) => {
+                let e = e.resolve_type(types)?;
+                let mut l = vec![];
+                for (p, x) in cases.iter() {
+                    l.push((p.resolve_type(types)?, x.resolve_type(types)?));
+                }
+                let default = match default {
+                    Some(default) => Some(Box::new(default.resolve_type(types)?)),
+                    None => None,
+                };
+                Expression::Match(Box::new(e), l, default)
+            }
+            Expression::Let(p, e, body) => {
+                let e = e.resolve_type(types)?;
+                let body = body.resolve_type(types)?;
+                Expression::Let(p.clone(), Box::new(e), Box::new(body))
+            }
             Expression::Quantifier(op, params, e) => {
                 let mut params = params.clone();
                 for p in params.iter_mut() {
@@ -95,6 +131,7 @@ impl Expr {
                 let e = e.resolve_type(types)?;
                 op.new(params, e)
             }
+            Expression::Trace(op, e) => op.new(e.resolve_type(types)?),
             Expression::LTLunary(op, e) => {
                 let e = e.resolve_type(types)?;
                 op.new(e)
@@ -105,6 +142,8 @@ impl Expr {
                 op.new(left, right)
             }
             e @ Expression::LTLVariable(_) => e.clone(),
+            Expression::First(typ) => Expression::First(typ.resolve(types)?),
+            Expression::Last(typ) => Expression::Last(typ.resolve(types)?),
             e @ Expression::Unresolved(_) => e.clone(),
         };
         Ok(Expr::new(expression, self.position().clone()))
@@ -31,6 +31,22 @@ impl Expr {
                     let p = p.iter().map(|e| e.substitute(old, new)).collect();
                     Expression::Apply(Box::new(f), p).into()
                 }
+                Expression::Index(array, index) => {
+                    let array = array.substitute(old, new);
+                    let index = index.substitute(old, new);
+                    Expression::Index(Box::new(array), Box::new(index)).into()
+                }
+                Expression::Update(array, index, value) => {
+                    let array = array.substitute(old, new);
+                    let index = index.substitute(old, new);
+                    let value = value.substitute(old, new);
+                    Expression::Update(Box::new(array), Box::new(index), Box::new(value)).into()
+                }
+                Expression::ArrayValue(_) => self.clone(),
+                Expression::SetValue(list) => {
+                    let list = list.iter().map(|e| e.substitute(old, new)).collect();
+                    Expression::SetValue(list).into()
+                }
                 Expression::As(kid, typ, default) => {
                     let kid = kid.substitute(old, new);
                     let default = default.substitute(old, new);
@@ -75,14 +91,39 @@ impl Expr {
                         Expression::IfThenElse(Box::new(ce), Box::new(te), list, Box::new(ee));
                     Expr::new(expression, None)
                 }
-                Expression::Quantifier(op, p, e) => {
-                    let p = p.clone();
+                Expression::Match(e, cases, default) => {
                     let e = e.substitute(old, new);
-                    op.new(p, e).into()
+                    let cases = cases
+                        .iter()
+                        .map(|(p, x)| (p.clone(), x.substitute(old, new)))
+                        .collect();
+                    let default = default.as_ref().map(|x| Box::new(x.substitute(old, new)));
+                    let expression = Expression::Match(Box::new(e), cases, default);
+                    Expr::new(expression, None)
+                }
+                Expression::Let(p, e, body) => {
+                    let e = e.substitute(old, new);
+                    // the local name hides the substituted parameter
+                    let body = if old.is_same(&p.clone().into()) {
+                        *body.clone()
+                    } else {
+                        body.substitute(old, new)
+                    };
+                    Expression::Let(p.clone(), Box::new(e), Box::new(body)).into()
+                }
+                Expression::Quantifier(op, p, e) => {
+                    let e = if p.iter().any(|p| old.is_same(&p.clone().into())) {
+                        *e.clone()
+                    } else {
+                        e.substitute(old, new)
+                    };
+                    op.new(p.clone(), e).into()
                 }
+                Expression::Trace(op, e) => op.new(e.substitute(old, new)).into(),
                 //
                 Expression::LTLVariable(_) => self.clone(),
                 //
+                Expression::First(_) | Expression::Last(_) => self.clone(),
                 Expression::Unresolved(_) => self.clone(),
             }
         }
@@ -53,6 +53,22 @@ impl Expr {
                 }
                 Ok(())
             }
+            Expression::Index(array, index) => {
+                array.check_time(model)?;
+                index.check_time(model)
+            }
+            Expression::Update(array, index, value) => {
+                array.check_time(model)?;
+                index.check_time(model)?;
+                value.check_time(model)
+            }
+            Expression::ArrayValue(_) => Ok(()),
+            Expression::SetValue(list) => {
+                for e in list.iter() {
+                    e.check_time(model)?;
+                }
+                Ok(())
+            }
             Expression::As(kid, _, default) => {
                 kid.check_time(model)?;
                 default.check_time(model)
@@ -103,7 +119,37 @@ impl Expr {
                 ee.check_time(model)?;
                 Ok(())
             }
+            Expression::Let(_, e, body) => {
+                e.check_time(model)?;
+                body.check_time(model)
+            }
+            Expression::Match(e, cases, default) => {
+                e.check_time(model)?;
+                for (_, x) in cases.iter() {
+                    x.check_time(model)?;
+                }
+                if let Some(default) = default {
+                    default.check_time(model)?;
+                }
+                Ok(())
+            }
             Expression::Quantifier(_, _, e) => e.check_time(model),
+            Expression::Trace(_, kid) => {
+                if let Some(expr) = kid.get_following().or(kid.get_ltl()) {
+                    let message = "Following and LTL not allowed in trace aggregate".into();
+                    let name = self.to_lang(model);
+                    let position = self.position().clone();
+                    let expr = expr.clone();
+                    Err(Error::Time {
+                        message,
+                        name,
+                        position,
+                        expr,
+                    })
+                } else {
+                    kid.check_time(model)
+                }
+            }
             //
             Expression::LTLunary(_, kid) => {
                 if let Some(expr) = kid.get_following() {
@@ -138,6 +184,7 @@ impl Expr {
                 }
             } //
             Expression::LTLVariable(_) => Ok(()),
+            Expression::First(_) | Expression::Last(_) => Ok(()),
             Expression::Unresolved(_) => Ok(()),
         }
     }
@@ -173,6 +220,13 @@ impl Expr {
             Expression::Apply(fun, params) => fun
                 .get_following()
                 .or(params.iter().find_map(|p| p.get_following())),
+            Expression::Index(array, index) => array.get_following().or(index.get_following()),
+            Expression::Update(array, index, value) => array
+                .get_following()
+                .or(index.get_following())
+                .or(value.get_following()),
+            Expression::ArrayValue(_) => None,
+            Expression::SetValue(list) => list.iter().find_map(|e| e.get_following()),
             Expression::As(kid, _, default) => kid.get_following().or(default.get_following()),
             //
             Expression::Following(_) => Some(self),
@@ -189,11 +243,18 @@ impl Expr {
                     .iter()
                     .find_map(|(c, e)| c.get_following().or(e.get_following())))
                 .or(ee.get_following()),
+            Expression::Match(e, cases, default) => e
+                .get_following()
+                .or(cases.iter().find_map(|(_, x)| x.get_following()))
+                .or(default.as_ref().and_then(|x| x.get_following())),
+            Expression::Let(_, e, body) => e.get_following().or(body.get_following()),
             Expression::Quantifier(_, _, e) => e.get_following(),
+            Expression::Trace(_, e) => e.get_following(),
             //
             Expression::LTLunary(_, kid) => kid.get_following(),
             Expression::LTLbinary(left, _, right) => left.get_following().or(right.get_following()),
             Expression::LTLVariable(_) => None,
+            Expression::First(_) | Expression::Last(_) => None,
             Expression::Unresolved(_) => None,
         }
     }
@@ -226,6 +287,12 @@ impl Expr {
             Expression::Apply(fun, params) => {
                 fun.get_ltl().or(params.iter().find_map(|p| p.get_ltl()))
             }
+            Expression::Index(array, index) => array.get_ltl().or(index.get_ltl()),
+            Expression::Update(array, index, value) => {
+                array.get_ltl().or(index.get_ltl()).or(value.get_ltl())
+            }
+            Expression::ArrayValue(_) => None,
+            Expression::SetValue(list) => list.iter().find_map(|e| e.get_ltl()),
             Expression::As(kid, _, default) => kid.get_ltl().or(default.get_ltl()),
             Expression::Following(e) => e.get_ltl(),
             Expression::State(e, _, default) => e.get_ltl().or(match default {
@@ -238,10 +305,17 @@ impl Expr {
                 .or(te.get_ltl())
                 .or(list.iter().find_map(|(c, e)| c.get_ltl().or(e.get_ltl())))
                 .or(ee.get_ltl()),
+            Expression::Match(e, cases, default) => e
+                .get_ltl()
+                .or(cases.iter().find_map(|(_, x)| x.get_ltl()))
+                .or(default.as_ref().and_then(|x| x.get_ltl())),
+            Expression::Let(_, e, body) => e.get_ltl().or(body.get_ltl()),
             Expression::Quantifier(_, _, e) => e.get_ltl(),
+            Expression::Trace(_, e) => e.get_ltl(),
             Expression::LTLunary(_, _) => Some(self),
             Expression::LTLbinary(_, _, _) => Some(self),
             Expression::LTLVariable(_) => None,
+            Expression::First(_) | Expression::Last(_) => None,
             Expression::Unresolved(_) => None,
         }
     }
@@ -25,9 +25,48 @@ impl Expression {
                         Type::Int => typ,
                         Type::Real => typ,
                         Type::IntInterval(min, max) => Type::IntInterval(-max, -min),
+                        Type::Unsigned(_) => typ,
+                        Type::Signed(_) => typ,
                         _ => Type::Undefined,
                     }
                 }
+                PrefixUnaryOperator::BitNot => {
+                    let typ = expr.get_type(model);
+                    if typ.is_bit_vector() {
+                        typ
+                    } else {
+                        Type::Undefined
+                    }
+                }
+                PrefixUnaryOperator::Card => match expr.get_type(model) {
+                    Type::Set(element) => Type::IntInterval(0, element.all(model).len() as i64),
+                    _ => Type::Undefined,
+                },
+                PrefixUnaryOperator::Abs => match expr.get_type(model) {
+                    Type::Int => Type::Int,
+                    Type::Real => Type::Real,
+                    Type::IntInterval(min, max) => {
+                        let low = if min >= 0 {
+                            min
+                        } else if max <= 0 {
+                            -max
+                        } else {
+                            0
+                        };
+                        Type::IntInterval(low, min.abs().max(max.abs()))
+                    }
+                    _ => Type::Undefined,
+                },
+                PrefixUnaryOperator::ToReal => Type::Real,
+                PrefixUnaryOperator::ToInt => Type::Int,
+                PrefixUnaryOperator::Succ | PrefixUnaryOperator::Pred => expr.get_type(model),
+                PrefixUnaryOperator::Ord => match expr.get_type(model) {
+                    Type::Enumerate(id) => {
+                        let n = model.get(id).unwrap().elements().len() as i64;
+                        Type::IntInterval(0, n - 1)
+                    }
+                    _ => Type::Undefined,
+                },
             },
             Expression::Binary(left, op, right) => match op {
                 BinaryOperator::Eq => Type::Bool,
@@ -37,6 +76,21 @@ impl Expression {
                 BinaryOperator::Ge => Type::Bool,
                 BinaryOperator::Gt => Type::Bool,
                 BinaryOperator::Implies => Type::Bool,
+                BinaryOperator::In => Type::Bool,
+                BinaryOperator::Union | BinaryOperator::Inter | BinaryOperator::Diff => left
+                    .get_type(model)
+                    .common_type(model, &right.get_type(model)),
+                BinaryOperator::BitAnd
+                | BinaryOperator::BitOr
+                | BinaryOperator::BitXor
+                | BinaryOperator::Shl
+                | BinaryOperator::Shr => {
+                    Expr::bit_vector_type(model, [left.as_ref(), right.as_ref()])
+                        .unwrap_or(Type::Undefined)
+                }
+                _ if Expr::bit_vector_type(model, [left.as_ref(), right.as_ref()]).is_some() => {
+                    Expr::bit_vector_type(model, [left.as_ref(), right.as_ref()]).unwrap()
+                }
                 BinaryOperator::Min => match (left.get_type(model), right.get_type(model)) {
                     (Type::Int, Type::Int) => Type::Int,
                     (Type::Real, Type::Real) => Type::Real,
@@ -44,9 +98,7 @@ impl Expression {
                         Type::IntInterval(min1.min(min2), max1.min(max2))
                     }
                     (Type::IntInterval(min, max), Type::Int) => Type::IntInterval(min, max),
-                    (Type::IntInterval(min, max), Type::Real) => Type::IntInterval(min, max),
                     (Type::Int, Type::IntInterval(min, max)) => Type::IntInterval(min, max),
-                    (Type::Real, Type::IntInterval(min, max)) => Type::IntInterval(min, max),
                     _ => Type::Undefined,
                 },
                 BinaryOperator::Max => match (left.get_type(model), right.get_type(model)) {
@@ -56,15 +108,35 @@ impl Expression {
                         Type::IntInterval(min1.max(min2), max1.max(max2))
                     }
                     (Type::IntInterval(min, max), Type::Int) => Type::IntInterval(min, max),
-                    (Type::IntInterval(min, max), Type::Real) => Type::IntInterval(min, max),
                     (Type::Int, Type::IntInterval(min, max)) => Type::IntInterval(min, max),
-                    (Type::Real, Type::IntInterval(min, max)) => Type::IntInterval(min, max),
                     _ => Type::Undefined,
                 },
+                // the integers are converted to reals
+                BinaryOperator::Div => Type::Real,
+                BinaryOperator::IntDiv => match (left.get_type(model), right.get_type(model)) {
+                    // euclidean division: floor for a positive divisor
+                    (Type::IntInterval(min1, max1), Type::IntInterval(min2, max2)) if min2 > 0 => {
+                        Type::IntInterval(
+                            min1.div_euclid(min2).min(min1.div_euclid(max2)),
+                            max1.div_euclid(min2).max(max1.div_euclid(max2)),
+                        )
+                    }
+                    _ => Type::Int,
+                },
+                BinaryOperator::Mod => match right.get_type(model) {
+                    // euclidean remainder: 0 <= r < |divisor|
+                    Type::IntInterval(min, max) if min != 0 || max != 0 => {
+                        Type::IntInterval(0, min.abs().max(max.abs()) - 1)
+                    }
+                    _ => Type::Int,
+                },
             },
             Expression::Nary(op, kids) => match op {
                 NaryOperator::And => Type::Bool,
                 NaryOperator::Or => Type::Bool,
+                _ if Expr::bit_vector_type(model, kids).is_some() => {
+                    Expr::bit_vector_type(model, kids).unwrap()
+                }
                 NaryOperator::Mul => {
                     if let Some((first, others)) = kids.split_first() {
                         let mut t = first.get_type(model);
@@ -140,6 +212,33 @@ impl Expression {
                     Type::Undefined
                 }
             }
+            Expression::Index(array, _) => match array.get_type(model) {
+                Type::Array(_, element) => *element,
+                _ => Type::Undefined,
+            },
+            Expression::Update(array, _, _) => array.get_type(model),
+            Expression::SetValue(list) => match list.split_first() {
+                Some((first, others)) => {
+                    let mut element = first.get_type(model);
+                    for e in others.iter() {
+                        element = element.common_type(model, &e.get_type(model));
+                    }
+                    Type::Set(Box::new(element))
+                }
+                None => Type::Set(Box::new(Type::Undefined)),
+            },
+            Expression::ArrayValue(list) => match list.split_first() {
+                Some(((i, v), others)) => {
+                    let mut index = i.get_type(model);
+                    let mut element = v.get_type(model);
+                    for (i, v) in others.iter() {
+                        index = index.common_type(model, &i.get_type(model));
+                        element = element.common_type(model, &v.get_type(model));
+                    }
+                    Type::Array(Box::new(index), Box::new(element))
+                }
+                None => Type::Undefined,
+            },
             Expression::As(_, typ, _) => typ.get_type(model),
             //
             Expression::Following(kid) => kid.get_type(model),
@@ -154,6 +253,18 @@ impl Expression {
                 res = res.common_type(model, &ee.get_type(model));
                 res
             }
+            Expression::Let(_, _, body) => body.get_type(model),
+            Expression::Match(_, cases, default) => {
+                let mut values = cases.iter().map(|(_, x)| x).chain(default.as_deref());
+                let mut res = match values.next() {
+                    Some(x) => x.get_type(model),
+                    None => Type::Undefined,
+                };
+                for x in values {
+                    res = res.common_type(model, &x.get_type(model));
+                }
+                res
+            }
             Expression::Quantifier(op, _, e) => match op {
                 QtOperator::Forall => Type::Bool,
                 QtOperator::Exists => Type::Bool,
@@ -174,11 +285,24 @@ impl Expression {
                 QtOperator::Min => e.get_type(model),
                 QtOperator::Max => e.get_type(model),
             },
+            Expression::Trace(op, e) => match op {
+                TraceOperator::Sum => match e.get_type(model) {
+                    t @ Type::Real => t,
+                    t @ Type::Int => t,
+                    Type::Interval(_) => Type::Int,
+                    Type::IntInterval(_, _) => Type::Int,
+                    _ => Type::Undefined,
+                },
+                TraceOperator::Min => e.get_type(model),
+                TraceOperator::Max => e.get_type(model),
+                TraceOperator::Count => Type::Int,
+            },
             //
             Expression::LTLunary(_, _) => Type::Bool,
             Expression::LTLbinary(_, _, _) => Type::Bool,
             Expression::LTLVariable(_) => Type::Bool,
             //
+            Expression::First(typ) | Expression::Last(typ) => typ.get_type(model),
             Expression::Unresolved(_) => Type::Undefined,
         }
     }
@@ -200,6 +324,18 @@ impl Expr {
                 match op {
                     PrefixUnaryOperator::Not => kid.check_is_bool(model)?,
                     PrefixUnaryOperator::Neg => kid.check_is_number(model)?,
+                    PrefixUnaryOperator::BitNot => kid.check_is_bit_vector(model)?,
+                    PrefixUnaryOperator::Card => {
+                        kid.check_is_set(model)?;
+                    }
+                    PrefixUnaryOperator::Abs => kid.check_is_integer_or_real(model)?,
+                    PrefixUnaryOperator::ToReal => kid.check_is_integer(model)?,
+                    PrefixUnaryOperator::ToInt => kid.check_is_real(model)?,
+                    PrefixUnaryOperator::Succ
+                    | PrefixUnaryOperator::Pred
+                    | PrefixUnaryOperator::Ord => {
+                        kid.check_is_ordered(model)?;
+                    }
                 }
                 Ok(())
             }
@@ -220,14 +356,61 @@ impl Expr {
                 ]
                 .contains(op)
                 {
-                    left.check_is_number(model)?;
-                    right.check_is_number(model)?;
+                    if let Type::Enumerate(_) = l_type {
+                        left.check_is_ordered(model)?;
+                    } else {
+                        left.check_is_number(model)?;
+                        right.check_is_number(model)?;
+                    }
                     right.check_compatible(model, l_type)
                 }
                 // Bool
                 else if [BinaryOperator::Implies].contains(op) {
                     left.check_is_bool(model)?;
                     right.check_is_bool(model)
+                }
+                // Set
+                else if [BinaryOperator::In].contains(op) {
+                    let element = right.check_is_set(model)?;
+                    left.check_compatible(model, element)
+                } else if [
+                    BinaryOperator::Union,
+                    BinaryOperator::Inter,
+                    BinaryOperator::Diff,
+                ]
+                .contains(op)
+                {
+                    left.check_is_set(model)?;
+                    right.check_is_set(model)?;
+                    right.check_compatible(model, l_type)
+                }
+                // Number
+                else if [BinaryOperator::Min, BinaryOperator::Max].contains(op) {
+                    Expr::check_all_integer_or_real(model, &vec![*left.clone(), *right.clone()])
+                }
+                // the only operator mixing integers and reals
+                else if [BinaryOperator::Div].contains(op) {
+                    left.check_is_integer_or_real(model)?;
+                    right.check_is_integer_or_real(model)
+                } else if [BinaryOperator::IntDiv, BinaryOperator::Mod].contains(op) {
+                    if Expr::bit_vector_type(model, [left.as_ref(), right.as_ref()]).is_some() {
+                        Expr::check_bit_vector_operands(model, [left.as_ref(), right.as_ref()])
+                    } else {
+                        left.check_is_integer(model)?;
+                        right.check_is_integer(model)
+                    }
+                }
+                // Bit-vector
+                else if [
+                    BinaryOperator::BitAnd,
+                    BinaryOperator::BitOr,
+                    BinaryOperator::BitXor,
+                    BinaryOperator::Shl,
+                    BinaryOperator::Shr,
+                ]
+                .contains(op)
+                {
+                    Expr::check_bit_vector_operands(model, [left.as_ref(), right.as_ref()])
                 } else {
                     panic!("undefined")
                 }
@@ -239,6 +422,8 @@ impl Expr {
                         e.check_is_bool(model)?;
                     }
                     Ok(())
+                } else if Expr::bit_vector_type(model, kids).is_some() {
+                    Expr::check_bit_vector_operands(model, kids)
                 } else if [NaryOperator::Mul, NaryOperator::Add, NaryOperator::Sub].contains(op) {
                     Expr::check_all_integer_or_real(model, kids)
                 } else {
@@ -274,6 +459,40 @@ impl Expr {
                     })
                 }
             }
+            Expression::Index(array, index) => {
+                array.check_type(model)?;
+                index.check_type(model)?;
+                let (index_type, _) = array.check_is_array(model)?;
+                index.check_compatible(model, index_type)
+            }
+            Expression::Update(array, index, value) => {
+                array.check_type(model)?;
+                index.check_type(model)?;
+                value.check_type(model)?;
+                let (index_type, element_type) = array.check_is_array(model)?;
+                index.check_compatible(model, index_type)?;
+                value.check_compatible(model, element_type)
+            }
+            Expression::ArrayValue(_) => Ok(()),
+            Expression::SetValue(list) => {
+                for e in list.iter() {
+                    e.check_type(model)?;
+                }
+                if let Some((first, others)) = list.split_first() {
+                    let element = first.get_type(model);
+                    if !element.is_index() {
+                        return Err(Error::Type {
+                            expr: first.clone(),
+                            typ: element,
+                            expected: vec![],
+                        });
+                    }
+                    for e in others.iter() {
+                        e.check_compatible(model, element.clone())?;
+                    }
+                }
+                Ok(())
+            }
             Expression::As(kid, typ, default) => {
                 let t = typ.get_type(model);
                 match t {
@@ -319,6 +538,29 @@ impl Expr {
                 }
                 ee.check_subtype(model, &t)
             }
+            Expression::Let(_, e, body) => {
+                e.check_type(model)?;
+                body.check_type(model)
+            }
+            Expression::Match(e, cases, default) => {
+                e.check_type(model)?;
+                for (_, x) in cases.iter() {
+                    x.check_type(model)?;
+                }
+                if let Some(default) = default {
+                    default.check_type(model)?;
+                }
+                self.check_match(model)?;
+                //
+                let t = self.get_type(model);
+                for (_, x) in cases.iter() {
+                    x.check_subtype(model, &t)?;
+                }
+                if let Some(default) = default {
+                    default.check_subtype(model, &t)?;
+                }
+                Ok(())
+            }
             Expression::Quantifier(op, _, e) => {
                 e.check_type(model)?;
                 match op {
@@ -330,6 +572,13 @@ impl Expr {
                     QtOperator::Max => e.check_is_number(model),
                 }
             }
+            Expression::Trace(op, e) => {
+                e.check_type(model)?;
+                match op {
+                    TraceOperator::Count => e.check_is_bool(model),
+                    _ => e.check_is_number(model),
+                }
+            }
             //
             Expression::LTLunary(_, kid) => {
                 kid.check_type(model)?;
@@ -343,10 +592,73 @@ impl Expr {
             }
             Expression::LTLVariable(_) => Ok(()),
             //
+            Expression::First(_) | Expression::Last(_) => Ok(()),
             Expression::Unresolved(_) => Ok(()),
         }
     }
 
+    /// The cases are elements of the enumerate, without redundancy, and cover all the elements
+    fn check_match(&self, model: &Model) -> Result<(), Error> {
+        if let Expression::Match(e, cases, default) = self.expression() {
+            let typ = e.get_type(model);
+            let id = match typ {
+                Type::Enumerate(id) => id,
+                _ => {
+                    return Err(Error::Type {
+                        expr: *e.clone(),
+                        typ,
+                        expected: vec![],
+                    })
+                }
+            };
+            let mut covered = vec![];
+            for (p, _) in cases.iter() {
+                match p.expression() {
+                    Expression::EnumerateElement(element) if element.enumerate_id() == id => {
+                        if covered.contains(element) {
+                            return Err(Error::Match {
+                                message: "redundant case".into(),
+                                name: p.to_lang(model),
+                                position: p.position().clone(),
+                            });
+                        }
+                        covered.push(*element);
+                    }
+                    _ => {
+                        return Err(Error::Type {
+                            expr: p.clone(),
+                            typ: p.get_type(model),
+                            expected: vec![typ],
+                        })
+                    }
+                }
+            }
+            let missing: Vec<String> = model
+                .get(id)
+                .unwrap()
+                .elements()
+                .iter()
+                .filter(|x| !covered.contains(&x.id()))
+                .map(|x| x.name().to_string())
+                .collect();
+            match default {
+                Some(default) if missing.is_empty() => Err(Error::Match {
+                    message: "redundant case".into(),
+                    name: "_".into(),
+                    position: default.position().clone(),
+                }),
+                None if !missing.is_empty() => Err(Error::Match {
+                    message: "missing case".into(),
+                    name: missing.join(", "),
+                    position: self.position().clone(),
+                }),
+                _ => Ok(()),
+            }
+        } else {
+            Ok(())
+        }
+    }
+
     pub fn check_subtype(&self, model: &Model, supertype: &Type) -> Result<(), Error> {
         let my_type = self.get_type(model);
         if my_type.is_subtype_of(&supertype) {
@@ -361,6 +673,10 @@ impl Expr {
     }
 
     pub fn check_compatible(&self, model: &Model, other: Type) -> Result<(), Error> {
+        // an integer literal must be in the range of the bit-vector (it is never wrapped)
+        if let (Expression::Int(_), true) = (self.expression(), other.is_bit_vector()) {
+            return self.check_subtype(model, &other);
+        }
         let my_type = self.get_type(model);
         if my_type.is_compatible_with(&other) {
             Ok(())
@@ -381,7 +697,10 @@ impl Expr {
         let my_type = self.get_type(model);
         let int_type = Type::Int;
         let real_type = Type::Real;
-        if my_type.is_subtype_of(&int_type) || my_type.is_subtype_of(&real_type) {
+        if my_type.is_subtype_of(&int_type)
+            || my_type.is_subtype_of(&real_type)
+            || my_type.is_bit_vector()
+        {
             Ok(())
         } else {
             Err(Error::Type {
@@ -415,8 +734,101 @@ impl Expr {
         }
     }
 
+    pub fn check_is_integer_or_real(&self, model: &Model) -> Result<(), Error> {
+        match self.get_type(model) {
+            Type::Int => Ok(()),
+            Type::IntInterval(_, _) => Ok(()),
+            Type::Real => Ok(()),
+            t => Err(Error::Type {
+                expr: self.clone(),
+                typ: t,
+                expected: vec![Type::Int, Type::Real],
+            }),
+        }
+    }
+
+    /// The expression is an element of an ordered enumerate
+    pub fn check_is_ordered(&self, model: &Model) -> Result<EnumerateId, Error> {
+        match self.get_type(model) {
+            Type::Enumerate(id) if model.get(id).unwrap().is_ordered() => Ok(id),
+            t => Err(Error::Type {
+                expr: self.clone(),
+                typ: t,
+                expected: vec![],
+            }),
+        }
+    }
+
+    pub fn check_is_array(&self, model: &Model) -> Result<(Type, Type), Error> {
+        match self.get_type(model) {
+            Type::Array(index, element) => Ok((*index, *element)),
+            t => Err(Error::Type {
+                expr: self.clone(),
+                typ: t,
+                expected: vec![],
+            }),
+        }
+    }
+
+    pub fn check_is_bit_vector(&self, model: &Model) -> Result<(), Error> {
+        let my_type = self.get_type(model);
+        if my_type.is_bit_vector() {
+            Ok(())
+        } else {
+            Err(Error::Type {
+                expr: self.clone(),
+                typ: my_type,
+                expected: vec![],
+            })
+        }
+    }
+
+    pub fn check_is_set(&self, model: &Model) -> Result<Type, Error> {
+        match self.get_type(model) {
+            Type::Set(element) => Ok(*element),
+            t => Err(Error::Type {
+                expr: self.clone(),
+                typ: t,
+                expected: vec![],
+            }),
+        }
+    }
+
+    /// The bit-vector type of the expressions (if one of them is a bit-vector)
+    pub fn bit_vector_type<'a>(
+        model: &Model,
+        exprs: impl IntoIterator<Item = &'a Expr>,
+    ) -> Option<Type> {
+        exprs
+            .into_iter()
+            .map(|e| e.get_type(model))
+            .find(|t| t.is_bit_vector())
+    }
+
+    /// One of the operands is a bit-vector and the others are compatible with its type
+    pub fn check_bit_vector_operands<'a>(
+        model: &Model,
+        exprs: impl IntoIterator<Item = &'a Expr> + Clone,
+    ) -> Result<(), Error> {
+        match Expr::bit_vector_type(model, exprs.clone()) {
+            Some(typ) => {
+                for e in exprs {
+                    e.check_type(model)?;
+                    e.check_compatible(model, typ.clone())?;
+                }
+                Ok(())
+            }
+            None => match exprs.into_iter().next() {
+                Some(e) => e.check_is_bit_vector(model),
+                None => Ok(()),
+            },
+        }
+    }
+
+    /// All integers or all reals: 'to_real' is required to mix them
     pub fn check_all_integer_or_real(model: &Model, exprs: &Vec<Expr>) -> Result<(), Error> {
         if let Some((first, others)) = exprs.split_first() {
+            first.check_type(model)?;
             let is_integer = match first.get_type(model) {
                 Type::Int => true,
                 Type::IntInterval(_, _) => true,
@@ -430,6 +842,7 @@ impl Expr {
                 }
             };
             for e in others.iter() {
+                e.check_type(model)?;
                 if is_integer {
                     e.check_is_integer(model)?;
                 } else {
@@ -440,3 +853,140 @@ impl Expr {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::tests::load_source;
+    use crate::ToLang;
+
+    /// Load a model where x is defined by the match expression
+    fn load_match(expr: &str) -> Result<(), Error> {
+        let source = format!(
+            "enum S = {{A, B, C}}
+            enum T = {{D, E}}
+            var s: S
+            var t: T
+            var x: Int
+            init I {{ x = {} }}
+            search initial solve",
+            expr
+        );
+        load_source(&source).map(|_| ())
+    }
+
+    fn match_error(expr: &str) -> (String, String) {
+        match load_match(expr) {
+            Err(Error::Match { message, name, .. }) => (message, name),
+            Err(e) => panic!("unexpected error: {}", e.category()),
+            Ok(_) => panic!("no error"),
+        }
+    }
+
+    #[test]
+    fn check_match_complete() {
+        assert!(load_match("match s with A -> 0 | B -> 1 | C -> 2 end").is_ok());
+        assert!(load_match("match s with A -> 0 | _ -> 1 end").is_ok());
+    }
+
+    #[test]
+    fn check_match_missing_case() {
+        let (message, name) = match_error("match s with A -> 0 end");
+        assert_eq!(message, "missing case");
+        assert_eq!(name, "B, C");
+    }
+
+    #[test]
+    fn check_match_redundant_case() {
+        let (message, name) = match_error("match s with A -> 0 | B -> 1 | A -> 2 | _ -> 3 end");
+        assert_eq!(message, "redundant case");
+        assert_eq!(name, "A");
+    }
+
+    #[test]
+    fn check_match_redundant_default() {
+        let (message, name) = match_error("match s with A -> 0 | B -> 1 | C -> 2 | _ -> 3 end");
+        assert_eq!(message, "redundant case");
+        assert_eq!(name, "_");
+    }
+
+    #[test]
+    fn check_match_type() {
+        assert!(matches!(
+            load_match("match s with A -> 0 | D -> 1 | _ -> 2 end"),
+            Err(Error::Type { .. })
+        ));
+        assert!(matches!(
+            load_match("match x with A -> 0 | _ -> 2 end"),
+            Err(Error::Type { .. })
+        ));
+        assert!(matches!(
+            load_match("match s with A -> 0 | _ -> true end"),
+            Err(Error::Type { .. })
+        ));
+        assert!(matches!(
+            load_match("match s with A -> D | _ -> E end"),
+            Err(Error::Type { .. })
+        ));
+    }
+
+    /// Load a model with the real t and the integer x constrained by 'expr'
+    fn load_arith(expr: &str) -> Result<(), Error> {
+        let source = format!(
+            "var t: Real
+            var x: Int
+            init I {{ {} }}
+            search initial solve",
+            expr
+        );
+        load_source(&source).map(|_| ())
+    }
+
+    #[test]
+    fn check_real_division() {
+        // only '/' converts the integers to reals
+        for expr in [
+            "t = x / 2",
+            "t = 1 / t",
+            "t = to_real(x) + 0.5",
+            "t < to_real(x)",
+            "x = to_int(t) * 2",
+            "t = t min 0.5",
+        ] {
+            assert!(load_arith(expr).is_ok(), "{} is rejected", expr);
+        }
+    }
+
+    #[test]
+    fn check_integer_real_mix() {
+        for expr in [
+            "t = x",
+            "x = t",
+            "t = t + 1",
+            "t = 2 * t",
+            "t = t - x",
+            "t < x",
+            "t = t min x",
+            "x = x / 2",
+            "x = t div 2",
+            "x = x mod t",
+        ] {
+            assert!(
+                matches!(load_arith(expr), Err(Error::Type { .. })),
+                "{} is accepted",
+                expr
+            );
+        }
+    }
+
+    #[test]
+    fn parse_mul_div() {
+        let source = "var x: Int
+            init I { x = x * 2 * x div 3 * 4 mod 5 }
+            search initial solve";
+        let model = load_source(source).ok().unwrap();
+        let init = model.initials()[0].expr().to_lang(&model);
+        // the products stay n-ary, div and mod apply to the product on their left
+        assert_eq!(init, "(x = ((((x * 2 * x) div 3) * 4) mod 5))");
+    }
+}
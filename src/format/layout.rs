@@ -0,0 +1,586 @@
+use super::*;
+
+/// Maximal line width before breaking
+pub const WIDTH: usize = 100;
+/// Indentation of blocks and broken groups
+pub const INDENT: usize = 4;
+
+/// Keywords starting a top level item
+const ITEMS: [&str; 13] = [
+    "include", "enum", "interval", "cst", "var", "let", "ltl", "init", "inv", "trans", "trig",
+    "prop", "search",
+];
+/// Items with a `{ expr }` body
+const BLOCKS: [&str; 4] = ["init", "inv", "trans", "trig"];
+/// Declarations aligned on their ':'
+const DECLARATIONS: [&str; 2] = ["cst", "var"];
+/// Logical operators, from the weakest
+const CHAINS: [&str; 3] = ["implies", "or", "and"];
+
+/// Words that are not the end of an operand
+const OPERATORS: [&str; 43] = [
+    "and", "or", "not", "implies", "if", "then", "elif", "else", "min", "max", "at", "default",
+    "as", "until", "X", "G", "F", "U", "R", "minimize", "maximize", "with", "of", "land", "lor",
+    "lxor", "lnot", "shl", "shr", "in", "union", "inter", "diff", "card", "div", "mod", "abs",
+    "to_real", "to_int", "match", "succ", "pred", "ord",
+];
+
+//------------------------- Spaces -------------------------
+
+fn is_operand_end(token: &Token) -> bool {
+    match token.kind {
+        TokenKind::Number | TokenKind::Str => true,
+        TokenKind::Word => !OPERATORS.contains(&token.text.as_str()),
+        TokenKind::Symbol => [")", "]", "}", "'"].iter().any(|s| token.is(s)),
+        _ => false,
+    }
+}
+
+/// Set the canonical space before each token.
+pub fn set_spaces(tokens: &mut [Token]) {
+    let mut scope = false; // inside |...|
+    let mut opening_bar = false;
+    let mut closing_bar = false;
+    let mut unary = false;
+
+    for i in 0..tokens.len() {
+        let next = &tokens[i];
+        let space = match i.checked_sub(1).map(|j| &tokens[j]) {
+            None => false,
+            Some(prev) => {
+                if next.is_comment() || prev.kind == TokenKind::BlockComment {
+                    true
+                } else if prev.kind == TokenKind::LineComment {
+                    false
+                } else if unary {
+                    // '- 1' is not '-1'
+                    next.kind == TokenKind::Number
+                } else if opening_bar
+                    || ["(", "[", ".."].iter().any(|s| prev.is(s))
+                    || [")", "]", ",", ":", "'", ".."].iter().any(|s| next.is(s))
+                {
+                    false
+                } else if next.is("[") {
+                    // 'a[i]' but 'with [i]'
+                    prev.kind == TokenKind::Word && OPERATORS.contains(&prev.text.as_str())
+                } else if next.is("(") {
+                    let call = prev.kind == TokenKind::Word
+                        && (!OPERATORS.contains(&prev.text.as_str())
+                            || [
+                                "X", "G", "F", "card", "abs", "to_real", "to_int", "succ", "pred",
+                                "ord",
+                            ]
+                            .contains(&prev.text.as_str()));
+                    !(call || closing_bar)
+                } else {
+                    !(next.is("|") && scope)
+                }
+            }
+        };
+
+        let operand = i > 0 && (closing_bar || is_operand_end(&tokens[i - 1]));
+        opening_bar = false;
+        closing_bar = false;
+        unary = next.is("-") && !operand;
+        if next.is("|") {
+            if scope {
+                scope = false;
+                closing_bar = true;
+            } else if !operand {
+                scope = true;
+                opening_bar = true;
+            }
+        }
+        tokens[i].space = space;
+    }
+}
+
+/// Tokens on one line
+fn render(tokens: &[&Token]) -> String {
+    let mut res = "".to_string();
+    for (i, token) in tokens.iter().enumerate() {
+        if i > 0 && token.space {
+            res.push(' ');
+        }
+        res += &token.text;
+    }
+    res
+}
+
+//------------------------- Node -------------------------
+
+enum Node<'a> {
+    Leaf(&'a Token),
+    Group(&'a Token, Vec<Node<'a>>, Option<&'a Token>),
+}
+
+fn closing(token: &Token) -> Option<&'static str> {
+    if token.kind != TokenKind::Symbol && token.kind != TokenKind::Word {
+        return None;
+    }
+    match token.text.as_str() {
+        "(" => Some(")"),
+        "[" => Some("]"),
+        "{" => Some("}"),
+        "if" => Some("end"),
+        "match" => Some("end"),
+        _ => None,
+    }
+}
+
+fn nodes<'a>(
+    tokens: &'a [Token],
+    i: &mut usize,
+    close: Option<&str>,
+) -> (Vec<Node<'a>>, Option<&'a Token>) {
+    let mut res = vec![];
+    while *i < tokens.len() {
+        let token = &tokens[*i];
+        *i += 1;
+        if close.map(|c| token.is(c)).unwrap_or(false) {
+            return (res, Some(token));
+        }
+        match closing(token) {
+            Some(c) => {
+                let (kids, end) = nodes(tokens, i, Some(c));
+                res.push(Node::Group(token, kids, end));
+            }
+            None => res.push(Node::Leaf(token)),
+        }
+    }
+    (res, None)
+}
+
+fn tokens<'a>(nodes: &[Node<'a>], res: &mut Vec<&'a Token>) {
+    for node in nodes.iter() {
+        match node {
+            Node::Leaf(t) => res.push(t),
+            Node::Group(open, kids, close) => {
+                res.push(open);
+                tokens(kids, res);
+                if let Some(close) = close {
+                    res.push(close);
+                }
+            }
+        }
+    }
+}
+
+fn all_tokens<'a>(nodes: &[Node<'a>]) -> Vec<&'a Token> {
+    let mut res = vec![];
+    tokens(nodes, &mut res);
+    res
+}
+
+fn has_comment(nodes: &[Node]) -> bool {
+    all_tokens(nodes).iter().any(|t| t.is_comment())
+}
+
+fn is_leaf(node: &Node, text: &str) -> bool {
+    matches!(node, Node::Leaf(t) if t.is(text))
+}
+
+//------------------------- Chain -------------------------
+
+/// A sequence split at its top level logical operators (the weakest one).
+struct Chain<'n, 'a> {
+    segments: Vec<&'n [Node<'a>]>,
+    operators: Vec<&'a Token>,
+    blanks: Vec<bool>,
+}
+
+fn chain<'n, 'a>(nodes: &'n [Node<'a>]) -> Option<Chain<'n, 'a>> {
+    for op in CHAINS.iter() {
+        let positions: Vec<usize> = (1..nodes.len())
+            .filter(|i| is_leaf(&nodes[*i], op))
+            .collect();
+        if positions.is_empty() {
+            continue;
+        }
+        let mut segments = vec![];
+        let mut operators = vec![];
+        let mut blanks = vec![false];
+        let mut start = 0;
+        for i in positions {
+            segments.push(&nodes[start..i]);
+            if let Node::Leaf(t) = &nodes[i] {
+                operators.push(*t);
+                let next = nodes
+                    .get(i + 1)
+                    .map(|n| all_tokens(std::slice::from_ref(n)));
+                let next_blank = next
+                    .and_then(|l| l.first().map(|t| t.newlines > 1))
+                    .unwrap_or(false);
+                blanks.push(t.newlines > 1 || next_blank);
+            }
+            start = i + 1;
+        }
+        segments.push(&nodes[start..]);
+        return Some(Chain {
+            segments,
+            operators,
+            blanks,
+        });
+    }
+    None
+}
+
+//------------------------- Layout -------------------------
+
+#[derive(Default)]
+pub struct Layout {
+    lines: Vec<String>,
+}
+
+impl Layout {
+    fn push(&mut self, indent: usize, text: &str) {
+        self.lines.push(format!("{}{}", " ".repeat(indent), text));
+    }
+
+    fn append(&mut self, text: &str) {
+        if let Some(line) = self.lines.last_mut() {
+            line.push(' ');
+            *line += text;
+        }
+    }
+
+    fn blank(&mut self) {
+        if self.lines.last().map(|l| !l.is_empty()).unwrap_or(false) {
+            self.lines.push("".to_string());
+        }
+    }
+
+    fn fits(indent: usize, text: &str) -> bool {
+        indent + text.chars().count() <= WIDTH
+    }
+
+    /// Tokens on one line if possible (no line comment)
+    fn flat(nodes: &[Node]) -> Option<String> {
+        let tokens = all_tokens(nodes);
+        if tokens
+            .iter()
+            .any(|t| t.kind == TokenKind::LineComment || t.text.contains('\n'))
+        {
+            None
+        } else {
+            Some(render(&tokens))
+        }
+    }
+
+    //---------- Sequence ----------
+
+    /// The last line keeps 'reserve' characters for the text appended after it.
+    fn sequence(&mut self, nodes: &[Node], indent: usize, reserve: usize) {
+        if nodes.is_empty() {
+            return;
+        }
+        if has_comment(nodes) {
+            self.preserve(&all_tokens(nodes), indent);
+            return;
+        }
+        let chain = chain(nodes);
+        let blank = chain
+            .as_ref()
+            .map(|c| c.blanks.iter().any(|b| *b))
+            .unwrap_or(false);
+        if !blank {
+            if let Some(text) = Self::flat(nodes) {
+                if Self::fits(indent + reserve, &text) {
+                    self.push(indent, &text);
+                    return;
+                }
+            }
+        }
+        if let Some(chain) = chain {
+            self.chain(&chain, indent, reserve);
+            return;
+        }
+        // break the last group
+        let group = nodes
+            .iter()
+            .rposition(|n| matches!(n, Node::Group(_, kids, _) if !kids.is_empty()));
+        match group {
+            Some(i) => self.group(&nodes[..i], &nodes[i], &nodes[i + 1..], indent),
+            None => self.push(indent, &render(&all_tokens(nodes))),
+        }
+    }
+
+    /// One operand per line
+    fn chain(&mut self, chain: &Chain, indent: usize, reserve: usize) {
+        for (i, segment) in chain.segments.iter().enumerate() {
+            if chain.blanks[i] {
+                self.blank();
+            }
+            match chain.operators.get(i) {
+                Some(op) => {
+                    self.sequence(segment, indent, op.text.chars().count() + 1);
+                    self.append(&op.text);
+                }
+                None => self.sequence(segment, indent, reserve),
+            }
+        }
+    }
+
+    //---------- Group ----------
+
+    fn group(&mut self, prefix: &[Node], group: &Node, rest: &[Node], indent: usize) {
+        let (open, kids, close) = match group {
+            Node::Group(open, kids, close) => (*open, kids, *close),
+            Node::Leaf(_) => return,
+        };
+        let mut head = all_tokens(prefix);
+        head.push(open);
+
+        if open.is("if") {
+            self.conditional(&head, kids, indent);
+        } else {
+            self.push(indent, &render(&head));
+            self.contents(open, kids, indent + INDENT);
+        }
+
+        let mut tail: Vec<&Token> = close.into_iter().collect();
+        tail.extend(all_tokens(rest));
+        if !tail.is_empty() {
+            self.push(indent, &render(&tail));
+        }
+    }
+
+    /// if c then e (elif c then e)* else e
+    /// A condition too long for the line is broken between 'if' and 'then'.
+    fn conditional(&mut self, head: &[&Token], kids: &[Node], indent: usize) {
+        let mut line = render(head);
+        let mut start = 0;
+        for i in 0..=kids.len() {
+            let keyword = match kids.get(i) {
+                Some(Node::Leaf(t)) if t.is("then") || t.is("elif") || t.is("else") => Some(*t),
+                Some(_) => continue,
+                None => None,
+            };
+            let part = &kids[start..i];
+            if line.is_empty() {
+                // body
+                self.sequence(part, indent + INDENT, 0);
+            } else {
+                // condition
+                let cond = render(&all_tokens(part));
+                if Self::fits(indent, &format!("{} {} then", line, cond)) {
+                    line.push(' ');
+                    line += &cond;
+                } else {
+                    self.push(indent, &line);
+                    self.sequence(part, indent + INDENT, 0);
+                    line = "".to_string();
+                }
+            }
+            match keyword {
+                Some(t) if t.is("then") => {
+                    if !line.is_empty() {
+                        line.push(' ');
+                    }
+                    line += &t.text;
+                    self.push(indent, &line);
+                    line = "".to_string();
+                }
+                Some(t) if t.is("else") => self.push(indent, &t.text),
+                Some(t) => line = t.text.clone(),
+                None => {}
+            }
+            start = i + 1;
+        }
+    }
+
+    /// Elements of a list one per line
+    fn contents(&mut self, open: &Token, kids: &[Node], indent: usize) {
+        let mut bars = 0;
+        let commas: Vec<usize> = (0..kids.len())
+            .filter(|i| {
+                if is_leaf(&kids[*i], "|") {
+                    bars += 1;
+                }
+                bars % 2 == 0 && is_leaf(&kids[*i], ",")
+            })
+            .collect();
+        if commas.is_empty() || open.is("if") {
+            // the group is broken: so are its operands
+            match chain(kids).filter(|_| !has_comment(kids)) {
+                Some(chain) => self.chain(&chain, indent, 0),
+                None => self.sequence(kids, indent, 0),
+            }
+            return;
+        }
+        let mut start = 0;
+        for i in commas {
+            self.sequence(&kids[start..i], indent, 1);
+            self.append(",");
+            start = i + 1;
+        }
+        self.sequence(&kids[start..], indent, 0);
+    }
+
+    //---------- Preserve ----------
+
+    /// Keep the line breaks of the source (used with comments),
+    /// only the indentation and the spaces are normalized.
+    fn preserve(&mut self, tokens: &[&Token], indent: usize) {
+        let mut depth: usize = 0;
+        let mut line: Vec<&Token> = vec![];
+        let mut line_depth = 0;
+        for token in tokens.iter() {
+            if !line.is_empty() && token.newlines > 0 {
+                self.push(indent + line_depth * INDENT, &render(&line));
+                line.clear();
+                if token.newlines > 1 {
+                    self.blank();
+                }
+            }
+            let close = [")", "]", "}", "end"].iter().any(|s| token.is(s));
+            if line.is_empty() {
+                let dedent = close || token.is("elif") || token.is("else");
+                line_depth = if dedent {
+                    depth.saturating_sub(1)
+                } else {
+                    depth
+                };
+            }
+            line.push(token);
+            if closing(token).is_some() {
+                depth += 1;
+            } else if close {
+                depth = depth.saturating_sub(1);
+            }
+        }
+        if !line.is_empty() {
+            self.push(indent + line_depth * INDENT, &render(&line));
+        }
+    }
+
+    //---------- Item ----------
+
+    fn item(&mut self, tokens: &[Token]) {
+        let mut i = 0;
+        let (nodes, _) = nodes(tokens, &mut i, None);
+        let keyword = tokens.first().map(|t| t.text.as_str()).unwrap_or("");
+
+        let body = nodes
+            .iter()
+            .position(|n| matches!(n, Node::Group(open, _, _) if open.is("{")));
+        if BLOCKS.contains(&keyword) {
+            if let Some(b) = body.filter(|b| !has_comment(&nodes[..*b])) {
+                if let Node::Group(open, kids, close) = &nodes[b] {
+                    let mut head = all_tokens(&nodes[..b]);
+                    head.push(open);
+                    self.push(0, &render(&head));
+                    self.sequence(kids, INDENT, 0);
+                    let mut tail: Vec<&Token> = close.iter().copied().collect();
+                    tail.extend(all_tokens(&nodes[b + 1..]));
+                    self.push(0, &render(&tail));
+                    return;
+                }
+            }
+        }
+
+        if has_comment(&nodes) {
+            self.preserve(&all_tokens(&nodes), 0);
+            return;
+        }
+        if let Some(text) = Self::flat(&nodes) {
+            if Self::fits(0, &text) {
+                self.push(0, &text);
+                return;
+            }
+        }
+        // name = long expression
+        if keyword != "enum" {
+            if let Some(eq) = nodes.iter().position(|n| is_leaf(n, "=")) {
+                self.push(0, &render(&all_tokens(&nodes[..=eq])));
+                self.sequence(&nodes[eq + 1..], INDENT, 0);
+                return;
+            }
+        }
+        self.sequence(&nodes, 0, 0);
+    }
+
+    //---------- Align ----------
+
+    /// Align the types of consecutive one line declarations.
+    fn align(&mut self) {
+        let keyword = |line: &str| {
+            DECLARATIONS
+                .iter()
+                .find(|k| {
+                    line.starts_with(&format!("{} ", k))
+                        && line.find(':').map(|i| !line[..i].contains('(')) == Some(true)
+                })
+                .copied()
+        };
+        let mut start = 0;
+        while start < self.lines.len() {
+            let kind = keyword(&self.lines[start]);
+            let mut end = start + 1;
+            if kind.is_some() {
+                while end < self.lines.len() && keyword(&self.lines[end]) == kind {
+                    end += 1;
+                }
+                let width = self.lines[start..end]
+                    .iter()
+                    .map(|l| l.find(':').unwrap())
+                    .max()
+                    .unwrap();
+                for line in self.lines[start..end].iter_mut() {
+                    let (name, typ) = line.split_at(line.find(':').unwrap());
+                    let typ = typ[1..].trim_start();
+                    *line = format!("{}:{} {}", name, " ".repeat(width - name.len()), typ);
+                }
+            }
+            start = end;
+        }
+    }
+
+    //---------- File ----------
+
+    /// Layout of a whole file: items separated by (at most one) blank line.
+    pub fn file(tokens: &[Token]) -> Self {
+        let mut layout = Self::default();
+        let mut item: Vec<Token> = vec![];
+        let mut depth: usize = 0;
+
+        for token in tokens.iter() {
+            let top = depth == 0;
+            let start =
+                top && token.kind == TokenKind::Word && ITEMS.contains(&token.text.as_str());
+            let trailing = token.is_comment() && token.newlines == 0 && !item.is_empty();
+            let alone = top && token.is_comment() && !trailing;
+            if !item.is_empty() && (start || alone) {
+                layout.item(&item);
+                item.clear();
+            }
+            if (start || alone) && token.newlines > 1 {
+                layout.blank();
+            }
+            if alone {
+                layout.push(0, &token.text);
+            } else {
+                item.push(token.clone());
+            }
+            if closing(token).is_some() && token.kind == TokenKind::Symbol {
+                depth += 1;
+            } else if [")", "]", "}"].iter().any(|s| token.is(s)) {
+                depth = depth.saturating_sub(1);
+            }
+        }
+        if !item.is_empty() {
+            layout.item(&item);
+        }
+        layout.align();
+        layout
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut res = "".to_string();
+        for line in self.lines.iter() {
+            res += line.trim_end();
+            res.push('\n');
+        }
+        res
+    }
+}
@@ -0,0 +1,101 @@
+pub mod token;
+pub use token::*;
+
+pub mod layout;
+pub use layout::*;
+
+use crate::error::Error;
+use crate::model::Model;
+use crate::parser::parse_source;
+
+/// Format a source: indentation, spaces, line breaking of long expressions
+/// and alignment of declarations. The comments and the includes are kept.
+#[allow(clippy::result_large_err)]
+pub fn format_source(file: &str, source: &str) -> Result<String, Error> {
+    parse_source(&mut Model::empty(), file, source)?;
+    let mut tokens = tokenize(file, source)?;
+    set_spaces(&mut tokens);
+    let res = Layout::file(&tokens).to_text();
+
+    // Safety: the formatted source must have the same tokens
+    let same = |x: &Token, y: &Token| x.kind == y.kind && x.text == y.text;
+    let formatted = tokenize(file, &res)?;
+    if formatted.len() != tokens.len()
+        || !formatted.iter().zip(tokens.iter()).all(|(x, y)| same(x, y))
+    {
+        return Err(Error::Parse {
+            message: "Formatting would change the tokens of the file".to_string(),
+            token: None,
+            position: None,
+            expected: vec![],
+        });
+    }
+    Ok(res)
+}
+
+/// Format a file in place (or only check it).
+/// Return true if the file was (or would be) changed.
+#[allow(clippy::result_large_err)]
+pub fn format_file(file: &str, check: bool) -> Result<bool, Error> {
+    let source = std::fs::read_to_string(file).map_err(|e| Error::File {
+        filename: file.to_string(),
+        message: format!("{:?}", e),
+    })?;
+    let formatted = format_source(file, &source)?;
+    let changed = formatted != source;
+    if changed && !check {
+        std::fs::write(file, formatted).map_err(|e| Error::File {
+            filename: file.to_string(),
+            message: format!("{:?}", e),
+        })?;
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "enum Color={Black,White}
+interval I=0..10
+// a comment
+cst c:I
+var x,y:Int
+var col:Color
+let d:Int=x+c*2
+
+init init_state{x=0 and y=0 and col=Black}
+
+trans t{if x>0 and y>0 or col=White then x'=x - 1 and y'=y else x'=x+1 and y'=y+1 end and col'!=col}
+trans long_condition {
+if x > 1000000 or y > 1000000 or x + y > 2000000 or x - y > 3000000 or y - x > 3000000 or c = 0 then x' = 0 and y' = 0 else x' = x and y' = y end and col' = col }
+
+prop=F(x=d and G(y>=0)) /* the property */
+
+search[0..10] truncated solve
+";
+
+    #[test]
+    fn format_source_idempotent() {
+        let formatted = format_source("test.tat", SOURCE).ok().unwrap();
+        assert_ne!(formatted, SOURCE);
+        let again = format_source("test.tat", &formatted).ok().unwrap();
+        assert_eq!(again, formatted);
+    }
+
+    #[test]
+    fn format_source_width() {
+        let formatted = format_source("test.tat", SOURCE).ok().unwrap();
+        for line in formatted.lines() {
+            assert!(line.chars().count() <= WIDTH, "too long: {}", line);
+        }
+        assert!(formatted.contains("    if\n"));
+        assert!(formatted.contains("// a comment\n"));
+        assert!(formatted.contains("/* the property */"));
+    }
+
+    #[test]
+    fn format_source_parse_error() {
+        assert!(format_source("test.tat", "var x: Int\ninit I { x = }").is_err());
+    }
+}
@@ -0,0 +1,125 @@
+use crate::error::Error;
+use crate::parser::Position;
+use line_col::LineColLookup;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Word,
+    Number,
+    Str,
+    Symbol,
+    LineComment,
+    BlockComment,
+}
+
+#[derive(Clone, Debug)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+    /// number of line breaks before the token
+    pub newlines: usize,
+    /// space before the token (when not at the beginning of a line)
+    pub space: bool,
+}
+
+impl Token {
+    pub fn is(&self, text: &str) -> bool {
+        matches!(self.kind, TokenKind::Word | TokenKind::Symbol) && self.text == text
+    }
+
+    pub fn is_comment(&self) -> bool {
+        matches!(self.kind, TokenKind::LineComment | TokenKind::BlockComment)
+    }
+}
+
+/// Longest symbols first
+const SYMBOLS: [&str; 23] = [
+    "!=", "<=", ">=", "..", "->", "(", ")", "{", "}", "[", "]", ",", ":", "=", "<", ">", "+", "-",
+    "*", "/", "|", "'", "_",
+];
+
+/// Split a source in tokens, keeping the comments (same lexical rules as the grammar).
+#[allow(clippy::result_large_err)]
+pub fn tokenize(file: &str, source: &str) -> Result<Vec<Token>, Error> {
+    let mut tokens = vec![];
+    let mut newlines = 0;
+    let mut offset = 0;
+
+    while offset < source.len() {
+        let rest = &source[offset..];
+        let c = rest.chars().next().unwrap();
+
+        if c.is_whitespace() {
+            if c == '\n' {
+                newlines += 1;
+            }
+            offset += c.len_utf8();
+            continue;
+        }
+
+        let (kind, len) = if rest.starts_with("//") {
+            let len = rest.find(['\n', '\r']).unwrap_or(rest.len());
+            (TokenKind::LineComment, len)
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            match comment.find("*/") {
+                Some(i) => (TokenKind::BlockComment, i + 4),
+                None => return Err(error(file, source, offset, "Unterminated comment")),
+            }
+        } else if c == '"' {
+            match rest[1..].find('"') {
+                Some(i) => (TokenKind::Str, i + 2),
+                None => return Err(error(file, source, offset, "Unterminated string")),
+            }
+        } else if c.is_ascii_digit() || (c == '-' && starts_with_digit(&rest[1..])) {
+            (TokenKind::Number, number_length(rest))
+        } else if c.is_ascii_alphabetic() {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (TokenKind::Word, len)
+        } else {
+            match SYMBOLS.iter().find(|s| rest.starts_with(*s)) {
+                Some(s) => (TokenKind::Symbol, s.len()),
+                None => return Err(error(file, source, offset, "Invalid Token")),
+            }
+        };
+
+        tokens.push(Token {
+            kind,
+            text: rest[..len].trim_end().to_string(),
+            newlines,
+            space: false,
+        });
+        newlines = 0;
+        offset += len;
+    }
+    Ok(tokens)
+}
+
+fn starts_with_digit(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// -?[0-9]+ or [0-9]+.[0-9]+
+fn number_length(s: &str) -> usize {
+    let sign = if s.starts_with('-') { 1 } else { 0 };
+    let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let int = sign + digits(&s[sign..]);
+    let rest = &s[int..];
+    if sign == 0 && rest.starts_with('.') && starts_with_digit(&rest[1..]) {
+        int + 1 + digits(&rest[1..])
+    } else {
+        int
+    }
+}
+
+fn error(file: &str, source: &str, offset: usize, message: &str) -> Error {
+    let lookup = LineColLookup::new(source);
+    let token = source[offset..].chars().next().map(|c| c.to_string());
+    Error::Parse {
+        message: message.to_string(),
+        token,
+        position: Some(Position::new(file, &lookup, offset)),
+        expected: vec![],
+    }
+}
@@ -4,6 +4,7 @@ extern crate lalrpop_util;
 pub mod common;
 pub mod error;
 pub mod expr;
+pub mod format;
 pub mod model;
 pub mod parser;
 pub mod search;
@@ -14,9 +15,18 @@ pub use common::*;
 use crate::search::*;
 use crate::typing::*;
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use std::collections::HashMap;
 
-#[derive(Parser, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Output {
+    /// human readable output
+    Text,
+    /// JSON output (see docs/json.md)
+    Json,
+}
+
+#[derive(Parser, Debug, Clone)]
 #[command(name = "tatam", about = "Transition And Theory Analysis Machine")]
 #[command(author, version, about, long_about = None)]
 pub struct Args {
@@ -32,12 +42,77 @@ pub struct Args {
     /// Incremental solver
     #[arg(short, long, default_value_t = false)]
     pub incremental: bool,
+    /// Portfolio solver (race incremental, sequence and parallel solvers)
+    #[arg(short, long, default_value_t = false)]
+    pub portfolio: bool,
     // /// install vscode extensions in the destination folder
     // #[arg(long = "vs")]
     // pub vscode_extension: Option<String>,
+    /// output format
+    #[arg(short, long, value_enum, default_value_t = Output::Text)]
+    pub output: Output,
+    /// constant value (name=value), can be repeated
+    #[arg(short = 'D', long = "define", value_name = "NAME=VALUE")]
+    pub defines: Vec<String>,
+    /// solve for each value of an integer constant (name=min..max)
+    #[arg(long, value_name = "NAME=MIN..MAX")]
+    pub sweep: Option<String>,
+    /// check that each atomic subformula of the property affects the result
+    #[arg(long, default_value_t = false)]
+    pub vacuity: bool,
+    /// disable a lint warning (can be repeated)
+    #[arg(short = 'A', long = "allow", value_enum, value_name = "LINT")]
+    pub allow: Vec<error::Lint>,
+    /// export the trace as VCD in this file
+    #[arg(long)]
+    pub vcd: Option<String>,
+    /// export the trace as CSV in this file
+    #[arg(long)]
+    pub csv: Option<String>,
+    /// export the trace as Graphviz (DOT) in this file
+    #[arg(long)]
+    pub dot: Option<String>,
+    /// export the model structure as Graphviz (DOT) in this file
+    #[arg(long)]
+    pub dot_model: Option<String>,
     /// SMT Log Folder
     #[arg(short, long)]
     log_folder: Option<String>,
+    /// Set to stop the solving (shared by the clones)
+    #[arg(skip)]
+    cancel: solve::Cancel,
+}
+
+impl Args {
+    /// A new cancellation flag for these arguments and their clones
+    pub fn new_cancel(&mut self) {
+        self.cancel = solve::Cancel::default();
+    }
+
+    /// Stop the solving with these arguments and their clones
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    pub fn cancellation(&self) -> &solve::Cancel {
+        &self.cancel
+    }
+}
+
+/// `tatam fmt`
+#[derive(Parser, Debug, Clone)]
+#[command(name = "tatam fmt", about = "Format tatam files")]
+pub struct FmtArgs {
+    /// files to format
+    #[arg(required = true)]
+    pub files: Vec<String>,
+    /// only check that the files are formatted (exit code 1 otherwise)
+    #[arg(long, default_value_t = false)]
+    pub check: bool,
 }
 
 pub fn ok_entry<S: Into<String>>(title: S) -> d_stuff::Entry {
@@ -59,9 +134,20 @@ pub fn load_file(
     model: &mut model::Model,
     filename: &str,
     verbose: u8,
+) -> Result<(), error::Error> {
+    load_file_with_sources(pretty, model, filename, &HashMap::new(), verbose)
+}
+
+/// Same as load_file, the content of the files found in 'sources' is used instead of the one on disk.
+pub fn load_file_with_sources(
+    pretty: &mut d_stuff::Pretty,
+    model: &mut model::Model,
+    filename: &str,
+    sources: &HashMap<String, String>,
+    verbose: u8,
 ) -> Result<(), error::Error> {
     // Parsing
-    match parser::parse_file(model, filename) {
+    match parser::parse_file_with_sources(model, filename, sources) {
         Ok(_) => {
             if verbose >= 2 {
                 pretty.add(ok_entry("Parse"));
@@ -70,16 +156,6 @@ pub fn load_file(
         }
         Err(e) => return Err(e),
     }
-    // Check Interval
-    match model.check_intervals() {
-        Ok(_) => {
-            if verbose >= 2 {
-                pretty.add(ok_entry("Interval"));
-                pretty.print();
-            }
-        }
-        Err(e) => return Err(e),
-    }
     // Duplicate
     match model.check_unicity() {
         Ok(_) => {
@@ -114,6 +190,28 @@ pub fn load_file(
         Err(e) => return Err(e),
     }
 
+    // Check Cycle
+    match model.check_cycle() {
+        Ok(_) => {
+            if verbose >= 2 {
+                pretty.add(ok_entry("Check Cycle"));
+                pretty.print();
+            }
+        }
+        Err(e) => return Err(e),
+    }
+
+    // Check Interval
+    match model.check_intervals() {
+        Ok(_) => {
+            if verbose >= 2 {
+                pretty.add(ok_entry("Interval"));
+                pretty.print();
+            }
+        }
+        Err(e) => return Err(e),
+    }
+
     // Check Type
     match model.check_type() {
         Ok(_) => {
@@ -173,16 +271,6 @@ pub fn load_file(
         }
     }
 
-    // // Check Cycle
-    // match problem.check_cycle() {
-    //     Ok(_) => {
-    //         if verbose >= 2 {
-    //             pretty.add(ok_entry("Cycle    "));
-    //             pretty.print();
-    //         }
-    //     }
-    //     Err(e) => return Err(e),
-    // }
     // // Check Interval
     // match problem.check_interval() {
     //     Ok(_) => {
@@ -229,3 +317,533 @@ pub fn load_file(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Load a model from its source
+    pub(crate) fn load_source(source: &str) -> Result<model::Model, error::Error> {
+        let mut model = model::Model::empty();
+        let sources = HashMap::from([("test.tat".to_string(), source.to_string())]);
+        load_file_with_sources(
+            &mut d_stuff::Pretty::new(),
+            &mut model,
+            "test.tat",
+            &sources,
+            0,
+        )?;
+        Ok(model)
+    }
+
+    /// Default arguments on the test source, without output
+    pub(crate) fn test_args() -> Args {
+        let mut args = Args::parse_from(["tatam", "--file", "test.tat"]);
+        args.verbose = 0;
+        args
+    }
+
+    /// Solve a model from its source, None if z3 is not in the PATH
+    pub(crate) fn solve_source(source: &str) -> Option<(model::Model, solve::Response)> {
+        if std::process::Command::new("z3")
+            .arg("-version")
+            .output()
+            .is_err()
+        {
+            eprintln!("z3 is not in the PATH: the model is not solved");
+            return None;
+        }
+        let mut model = load_source(source).ok().unwrap();
+        let response = solve::resolve(&mut model, &mut d_stuff::Pretty::new(), &test_args());
+        Some((model, response))
+    }
+
+    /// Values of a constant, a variable or a definition on the states of the solution
+    pub(crate) fn trace(
+        model: &model::Model,
+        response: &solve::Response,
+        name: &str,
+    ) -> Vec<String> {
+        use crate::model::*;
+        let solution = match response.solution() {
+            Some(solution) => solution,
+            None => panic!("no solution: {}", response.kind()),
+        };
+        let values = |v: &Vec<Option<expr::Expr>>| {
+            v.iter()
+                .map(|e| e.as_ref().map(|e| e.to_lang(model)).unwrap_or_default())
+                .collect()
+        };
+        if let Some(dec) = FromName::<Declaration>::from_name(model, name) {
+            if dec.is_constant() {
+                values(&vec![solution.cst_dec[&dec.id()].clone()])
+            } else {
+                values(&solution.var_dec[&dec.id()])
+            }
+        } else {
+            let def = model.definitions().iter().find(|d| d.name() == name);
+            values(&solution.var_def[&def.unwrap().id()])
+        }
+    }
+
+    #[test]
+    fn trace_aggregates() {
+        let source = "var x: 0..10
+            init I { x = 1 }
+            trans t { x' = x + 2 }
+            prop = sum_trace(x) = 16 and min_trace(x) = 1 and max_trace(x) = 7 and
+                count_trace(x > 2) = 3
+            search[3..3] truncated solve";
+        if let Some((model, response)) = solve_source(source) {
+            assert_eq!(trace(&model, &response, "x"), vec!["1", "3", "5", "7"]);
+        }
+        // the aggregates are of the whole trace
+        let source = "var x: 0..10
+            init I { x = 1 }
+            trans t { x' = x + 2 }
+            prop = sum_trace(x) = 9
+            search[3..3] truncated solve";
+        if let Some((_, response)) = solve_source(source) {
+            assert!(response.solution().is_none(), "{}", response.kind());
+        }
+        let source = "var x: 0..10
+            init I { x = 5 }
+            trans down { x > 0 and x' = x - 1 }
+            trans up { x < 10 and x' = x + 1 }
+            search[2..2] truncated minimize sum_trace(x)";
+        if let Some((model, response)) = solve_source(source) {
+            assert_eq!(trace(&model, &response, "x"), vec!["5", "4", "3"]);
+        }
+    }
+
+    #[test]
+    fn trace_aggregate_errors() {
+        let load = |prop: &str, trans: &str| {
+            let source = format!(
+                "var x: 0..10
+                init I {{ x = 1 }}
+                trans t {{ {} }}
+                prop = {}
+                search[3..3] truncated solve",
+                trans, prop
+            );
+            load_source(&source).map(|_| ())
+        };
+        assert!(load("sum_trace(x) > 3", "x' = x + 1").is_ok());
+        assert!(matches!(
+            load("count_trace(x) > 3", "x' = x + 1"),
+            Err(error::Error::Type { .. })
+        ));
+        assert!(matches!(
+            load("sum_trace(x > 1) > 3", "x' = x + 1"),
+            Err(error::Error::Type { .. })
+        ));
+        assert!(matches!(
+            load("sum_trace(x') > 3", "x' = x + 1"),
+            Err(error::Error::Time { .. })
+        ));
+        assert!(matches!(
+            load("x > 3", "x' = x + 1 and sum_trace(x) < 10"),
+            Err(error::Error::Time { .. })
+        ));
+    }
+
+    #[test]
+    fn arrays() {
+        let source = "enum Room = {Kitchen, Bedroom, Office}
+            var buf: Array[0..3] of 0..5
+            var light: Array[Room] of Bool
+            var b1, b2: 0..5
+            var office: Bool
+            init I {
+                buf[0] = 0 and buf[1] = 0 and buf[2] = 0 and buf[3] = 0 and
+                not light[Kitchen] and not light[Bedroom] and not light[Office]
+            }
+            inv read { b1 = buf[1] and b2 = buf[2] and office = light[Office] }
+            trans push {
+                buf' = buf with [2] = buf[2] + 1 and
+                light' = light with [Office] = not light[Office]
+            }
+            search[2..2] truncated solve";
+        if let Some((model, response)) = solve_source(source) {
+            assert_eq!(trace(&model, &response, "b1"), vec!["0", "0", "0"]);
+            assert_eq!(trace(&model, &response, "b2"), vec!["0", "1", "2"]);
+            assert_eq!(
+                trace(&model, &response, "office"),
+                vec!["false", "true", "false"]
+            );
+        }
+    }
+
+    #[test]
+    fn array_errors() {
+        let load = |expr: &str| {
+            let source = format!(
+                "enum Room = {{Kitchen, Bedroom, Office}}
+                var buf: Array[0..3] of 0..5
+                var light: Array[Room] of Bool
+                init I {{ {} }}
+                search initial solve",
+                expr
+            );
+            load_source(&source).map(|_| ())
+        };
+        assert!(load("buf[1] = 2 and light[Office]").is_ok());
+        for expr in [
+            "buf[Kitchen] = 2",
+            "light[0]",
+            "buf = 0",
+            "buf = light",
+            "buf = buf with [Office] = 1",
+            "buf = buf with [1] = true",
+        ] {
+            assert!(
+                matches!(load(expr), Err(error::Error::Type { .. })),
+                "{} is accepted",
+                expr
+            );
+        }
+    }
+
+    #[test]
+    fn bit_vectors() {
+        let source = "var counter: Unsigned[8]
+            var w, s, sl, sr: Signed[8]
+            var u, ul, ur, m, x, n: Unsigned[8]
+            var neg, big: Bool
+            init I { counter = 254 and w = 127 and s = -3 and u = 200 }
+            inv read {
+                sl = s shl 1 and sr = s shr 1 and ul = u shl 1 and ur = u shr 1 and
+                m = u land 15 and x = u lxor 255 and n = lnot u and
+                neg = (s < 0) and big = (u > 100)
+            }
+            trans tick { counter' = counter + 1 and w' = w + 1 and s' = s and u' = u }
+            search[2..2] truncated solve";
+        if let Some((model, response)) = solve_source(source) {
+            let trace = |name| trace(&model, &response, name);
+            // wraparound
+            assert_eq!(trace("counter"), vec!["254", "255", "0"]);
+            assert_eq!(trace("w"), vec!["127", "-128", "-127"]);
+            // shifts: arithmetic for Signed[n]
+            assert_eq!(trace("sl")[0], "-6");
+            assert_eq!(trace("sr")[0], "-2");
+            assert_eq!(trace("ul")[0], "144");
+            assert_eq!(trace("ur")[0], "100");
+            // bitwise
+            assert_eq!(trace("m")[0], "8");
+            assert_eq!(trace("x")[0], "55");
+            assert_eq!(trace("n")[0], "55");
+            // signed and unsigned comparisons
+            assert_eq!(trace("neg")[0], "true");
+            assert_eq!(trace("big")[0], "true");
+        }
+    }
+
+    #[test]
+    fn bit_vector_errors() {
+        let load = |expr: &str| {
+            let source = format!(
+                "var s: Signed[8]
+                var u: Unsigned[8]
+                var v: Unsigned[16]
+                var i: Int
+                init I {{ {} }}
+                search initial solve",
+                expr
+            );
+            load_source(&source).map(|_| ())
+        };
+        assert!(load("s = -1 and u = 255 and v = 300 and (u land 1) = 0").is_ok());
+        for expr in [
+            "s = u",
+            "u = v",
+            "u + v = 1",
+            "i land 1 = 0",
+            "lnot true",
+            "u = 300",
+            "u = -1",
+        ] {
+            assert!(
+                matches!(load(expr), Err(error::Error::Type { .. })),
+                "{} is accepted",
+                expr
+            );
+        }
+    }
+
+    #[test]
+    fn sets() {
+        let source = "enum Room = {Kitchen, Bedroom, Office}
+            var s, i: Set of 0..3
+            var occupied: Set of Room
+            var c, ci: Int
+            var one, zero, one_i, kitchen, office: Bool
+            init I { s = {0} and card(occupied) = 0 }
+            inv read {
+                i = s inter {0, 1} and c = card(s) and ci = card(i) and
+                one = (1 in s) and zero = (0 in s) and one_i = (1 in i) and
+                kitchen = (Kitchen in occupied) and office = (Office in occupied)
+            }
+            trans t { s' = (s diff {0}) union {1, 2} and occupied' = occupied union {Office} }
+            search[2..2] truncated solve";
+        if let Some((model, response)) = solve_source(source) {
+            let trace = |name| trace(&model, &response, name);
+            assert_eq!(trace("c"), vec!["1", "2", "2"]);
+            assert_eq!(trace("ci"), vec!["1", "1", "1"]);
+            assert_eq!(trace("one"), vec!["false", "true", "true"]);
+            assert_eq!(trace("zero"), vec!["true", "false", "false"]);
+            assert_eq!(trace("one_i"), vec!["false", "true", "true"]);
+            assert_eq!(trace("kitchen"), vec!["false", "false", "false"]);
+            assert_eq!(trace("office"), vec!["false", "true", "true"]);
+        }
+    }
+
+    #[test]
+    fn set_errors() {
+        let load = |expr: &str| {
+            let source = format!(
+                "enum Room = {{Kitchen, Bedroom, Office}}
+                var s: Set of 0..3
+                var occupied: Set of Room
+                init I {{ {} }}
+                search initial solve",
+                expr
+            );
+            load_source(&source).map(|_| ())
+        };
+        assert!(load("(1 in s) and (Office in occupied) and card(s) = 1").is_ok());
+        for expr in [
+            "1 in occupied",
+            "Kitchen in s",
+            "s = occupied",
+            "card(1) = 0",
+            "s = s union {Kitchen}",
+            "occupied = occupied inter Office",
+        ] {
+            assert!(
+                matches!(load(expr), Err(error::Error::Type { .. })),
+                "{} is accepted",
+                expr
+            );
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        let source = "var a, b, q1, r1, q2, r2, q3, r3, ab, mn, mx, ti: Int
+            var h: Real
+            var half, quarter: Bool
+            init I {
+                a = -7 and b = 2 and
+                q1 = a div b and r1 = a mod b and
+                q2 = a div -b and r2 = a mod -b and
+                q3 = -a div -b and r3 = -a mod -b and
+                ab = abs(a) and mn = a min b and mx = a max b and
+                h = to_real(a) / 2.0 and ti = to_int(h) and
+                half = (h + 3.5 = 0.0) and quarter = (a / 4 + 1.75 = 0.0)
+            }
+            search initial solve";
+        if let Some((model, response)) = solve_source(source) {
+            let trace = |name| trace(&model, &response, name);
+            // Euclidean div and mod: the remainder is not negative
+            assert_eq!(trace("q1"), vec!["-4"]);
+            assert_eq!(trace("r1"), vec!["1"]);
+            assert_eq!(trace("q2"), vec!["4"]);
+            assert_eq!(trace("r2"), vec!["1"]);
+            assert_eq!(trace("q3"), vec!["-3"]);
+            assert_eq!(trace("r3"), vec!["1"]);
+            assert_eq!(trace("ab"), vec!["7"]);
+            assert_eq!(trace("mn"), vec!["-7"]);
+            assert_eq!(trace("mx"), vec!["2"]);
+            // to_int rounds down
+            assert_eq!(trace("ti"), vec!["-4"]);
+            assert_eq!(trace("half"), vec!["true"]);
+            assert_eq!(trace("quarter"), vec!["true"]);
+        }
+    }
+
+    #[test]
+    fn arithmetic_errors() {
+        let load = |expr: &str| {
+            let source = format!(
+                "var a: Int
+                var h: Real
+                init I {{ {} }}
+                search initial solve",
+                expr
+            );
+            load_source(&source).map(|_| ())
+        };
+        assert!(load("h = a / 2 and a = to_int(h) and h + to_real(a) = 1.0").is_ok());
+        for expr in [
+            "h + a = 1.0",
+            "a = h",
+            "(a min h) = 1.0",
+            "a div h = 1",
+            "h mod 2.0 = 1.0",
+            "abs(true) = 1",
+            "to_int(a) = 1",
+            "to_real(h) = 1.0",
+        ] {
+            assert!(
+                matches!(load(expr), Err(error::Error::Type { .. })),
+                "{} is accepted",
+                expr
+            );
+        }
+    }
+
+    #[test]
+    fn match_expression() {
+        let source = "enum State = {Ready, Moving, Stopped}
+            var state: State
+            var speed: 0..10
+            init I { state = Ready and speed = 3 }
+            trans T {
+                state' = match state with Ready -> Moving | Moving -> Stopped | _ -> Ready end and
+                speed' = match state' with Ready -> 0 | Moving -> 10 | _ -> speed end
+            }
+            search[3..3] truncated solve";
+        if let Some((model, response)) = solve_source(source) {
+            assert_eq!(
+                trace(&model, &response, "state"),
+                vec!["Ready", "Moving", "Stopped", "Ready"]
+            );
+            assert_eq!(
+                trace(&model, &response, "speed"),
+                vec!["3", "10", "10", "0"]
+            );
+        }
+    }
+
+    #[test]
+    fn let_in() {
+        let source = "var clock: 0..30
+            var x: -5..5
+            var ok, pos: Bool
+            init I { clock = 0 and x = 2 }
+            inv read { ok = (let c = clock in c >= 4) and pos = (let p = x > 0 in p) }
+            trans move {
+                let t = clock + 2 in
+                clock' = t and (let d = x in d' = d - 1)
+            }
+            search[3..3] truncated solve";
+        let model = load_source(source).ok().unwrap();
+        // x is primed through the name d
+        let never_primed: Vec<String> = model
+            .lint(&[error::Lint::NeverPrimed])
+            .iter()
+            .flat_map(|w| w.items(&model))
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(never_primed, vec!["ok", "pos"]);
+        if let Some((model, response)) = solve_source(source) {
+            let trace = |name| trace(&model, &response, name);
+            assert_eq!(trace("clock"), vec!["0", "2", "4", "6"]);
+            assert_eq!(trace("x"), vec!["2", "1", "0", "-1"]);
+            assert_eq!(trace("ok"), vec!["false", "false", "true", "true"]);
+            assert_eq!(trace("pos"), vec!["true", "true", "false", "false"]);
+        }
+    }
+
+    #[test]
+    fn let_errors() {
+        let load = |expr: &str| {
+            let source = format!(
+                "var x: Int
+                var b: Bool
+                init I {{ {} }}
+                search initial solve",
+                expr
+            );
+            load_source(&source).map(|_| ())
+        };
+        assert!(load("let n = x + 1 in n > 0 and b").is_ok());
+        for expr in [
+            "let n = x + 1 in n",
+            "let ok = x > 0 in ok + 1 = 2",
+            "let n = b in n = x",
+        ] {
+            assert!(
+                matches!(load(expr), Err(error::Error::Type { .. })),
+                "{} is accepted",
+                expr
+            );
+        }
+    }
+
+    #[test]
+    fn ordered_enumerates() {
+        // the successor of the last element and the predecessor of the first one saturate
+        let source = "enum ordered Level = {Normal, Low, Critical}
+            var p, q, x, y, a, b, c, d: Level
+            var i, j: Int
+            init values { p = succ(last(Level)) and q = pred(first(Level)) }
+            init I {
+                x = last(Level) and y = first(Level) and
+                a = succ(x) and b = pred(y) and c = succ(y) and d = pred(x) and
+                i = ord(a) and j = ord(b)
+            }
+            search initial solve";
+        let model = load_source(source).ok().unwrap();
+        let values = model.initials()[0].expr().propagate(&model);
+        assert_eq!(values.to_lang(&model), "((p = Critical) and (q = Normal))");
+        if let Some((model, response)) = solve_source(source) {
+            let trace = |name| trace(&model, &response, name);
+            assert_eq!(trace("a"), vec!["Critical"]);
+            assert_eq!(trace("b"), vec!["Normal"]);
+            assert_eq!(trace("c"), vec!["Low"]);
+            assert_eq!(trace("d"), vec!["Low"]);
+            assert_eq!(trace("i"), vec!["2"]);
+            assert_eq!(trace("j"), vec!["0"]);
+        }
+    }
+
+    #[test]
+    fn symbolic_intervals() {
+        // Before is declared before the interval it depends on
+        let source = "interval Before = first(Slot)..last(Slot) - 1
+            interval Slot = 1..capacity
+            interval Low = 0..half
+            let half: Int = capacity div 2
+            cst capacity: 1..20
+            var x: Slot
+            var b: Before
+            var l: Low
+            var k: 0..capacity - 1
+            init I { capacity = 4 and x >= 4 and b >= 3 and l >= 2 and k = 0 }
+            trans T { k' = (k + 1) mod capacity and x' = x and b' = b and l' = l }
+            search[4..4] truncated solve";
+        if let Some((model, response)) = solve_source(source) {
+            let trace = |name| trace(&model, &response, name);
+            // the values are constrained by the bounds, not only by their range
+            assert_eq!(trace("x")[0], "4");
+            assert_eq!(trace("b")[0], "3");
+            assert_eq!(trace("l")[0], "2");
+            assert_eq!(trace("k"), vec!["0", "1", "2", "3", "0"]);
+        }
+    }
+
+    #[test]
+    fn symbolic_interval_errors() {
+        let load = |interval: &str| {
+            let source = format!(
+                "cst c: 1..20
+                var v: Int
+                interval I = {}
+                var x: I
+                search initial solve",
+                interval
+            );
+            load_source(&source).map(|_| ())
+        };
+        assert!(load("0..c - 1").is_ok());
+        assert!(matches!(load("0..true"), Err(error::Error::Type { .. })));
+        assert!(matches!(load("0..1.5"), Err(error::Error::Type { .. })));
+        assert!(matches!(load("0..v"), Err(error::Error::Time { .. })));
+        assert!(matches!(
+            load("c + 20..c"),
+            Err(error::Error::Interval { .. })
+        ));
+    }
+}
@@ -0,0 +1,248 @@
+use super::*;
+use crate::common::*;
+use crate::error::*;
+use crate::expr::*;
+use crate::parser::Position;
+use crate::typing::*;
+
+//------------------------- Definition Node -------------------------
+
+/// A definition of the dependency graph
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Node {
+    Interval(IntervalId),
+    Definition(DefinitionId),
+    FunDef(FunDefId),
+    LtlDefinition(LtlDefinitionId),
+}
+
+fn add_node(node: Node, res: &mut Vec<Node>) {
+    if !res.contains(&node) {
+        res.push(node);
+    }
+}
+
+/// The intervals used by a type
+fn type_dependencies(typ: &Type, res: &mut Vec<Node>) {
+    for id in typ.intervals() {
+        add_node(Node::Interval(id), res);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    Todo,
+    InProgress,
+    Done,
+}
+
+impl Model {
+    /// The definitions and the intervals used by an expression (with the types of its constants)
+    fn dependencies(&self, expr: &Expr, res: &mut Vec<Node>) {
+        match expr.expression() {
+            Expression::Definition(id) => add_node(Node::Definition(*id), res),
+            Expression::FunDef(id) => add_node(Node::FunDef(*id), res),
+            Expression::LtlDefinition(id) => add_node(Node::LtlDefinition(*id), res),
+            Expression::Declaration(id) => type_dependencies(self.get(*id).unwrap().typ(), res),
+            Expression::FunDec(id) => type_dependencies(self.get(*id).unwrap().return_type(), res),
+            Expression::First(typ) | Expression::Last(typ) => type_dependencies(typ, res),
+            _ => {}
+        }
+        for kid in expr.kids() {
+            self.dependencies(kid, res);
+        }
+    }
+
+    fn node_dependencies(&self, node: Node) -> Vec<Node> {
+        let mut res = vec![];
+        match node {
+            Node::Interval(id) => {
+                let x = self.get(id).unwrap();
+                self.dependencies(x.min_expr(), &mut res);
+                self.dependencies(x.max_expr(), &mut res);
+            }
+            Node::Definition(id) => {
+                let x = self.get(id).unwrap();
+                type_dependencies(x.typ(), &mut res);
+                self.dependencies(x.expr(), &mut res);
+            }
+            Node::FunDef(id) => self.dependencies(self.get(id).unwrap().expr(), &mut res),
+            Node::LtlDefinition(id) => self.dependencies(self.get(id).unwrap().expr(), &mut res),
+        }
+        res
+    }
+
+    fn definition_nodes(&self) -> Vec<Node> {
+        let mut res = vec![];
+        res.extend(self.intervals().iter().map(|x| Node::Interval(x.id())));
+        res.extend(self.definitions().iter().map(|x| Node::Definition(x.id())));
+        res.extend(self.fun_defs().iter().map(|x| Node::FunDef(x.id())));
+        res.extend(
+            self.ltl_definitions()
+                .iter()
+                .map(|x| Node::LtlDefinition(x.id())),
+        );
+        res
+    }
+
+    fn node_naming(&self, node: Node) -> (String, Option<Position>) {
+        match node {
+            Node::Interval(id) => {
+                let x = self.get(id).unwrap();
+                (x.label(self), x.position().clone())
+            }
+            Node::Definition(id) => {
+                let x = self.get(id).unwrap();
+                (x.name().to_string(), x.position().clone())
+            }
+            Node::FunDef(id) => {
+                let x = self.get(id).unwrap();
+                (x.name().to_string(), x.position().clone())
+            }
+            Node::LtlDefinition(id) => {
+                let x = self.get(id).unwrap();
+                (x.name().to_string(), x.position().clone())
+            }
+        }
+    }
+
+    /// Depth first search, a cycle is found for each node already in the path.
+    fn visit_cycle(
+        &self,
+        node: Node,
+        nodes: &[Node],
+        visits: &mut Vec<Visit>,
+        path: &mut Vec<Node>,
+        errors: &mut Vec<Error>,
+    ) {
+        let index = nodes.iter().position(|n| *n == node).unwrap();
+        match visits[index] {
+            Visit::Done => {}
+            Visit::InProgress => {
+                let start = path.iter().position(|n| *n == node).unwrap();
+                let mut cycle: Vec<(String, Option<Position>)> =
+                    path[start..].iter().map(|n| self.node_naming(*n)).collect();
+                cycle.push(self.node_naming(node));
+                errors.push(Error::Cycle { path: cycle });
+            }
+            Visit::Todo => {
+                visits[index] = Visit::InProgress;
+                path.push(node);
+                for n in self.node_dependencies(node) {
+                    self.visit_cycle(n, nodes, visits, path, errors);
+                }
+                path.pop();
+                visits[index] = Visit::Done;
+            }
+        }
+    }
+
+    fn visit_order(&self, node: Node, visited: &mut Vec<Node>, res: &mut Vec<IntervalId>) {
+        if !visited.contains(&node) {
+            visited.push(node);
+            for n in self.node_dependencies(node) {
+                self.visit_order(n, visited, res);
+            }
+            if let Node::Interval(id) = node {
+                res.push(id);
+            }
+        }
+    }
+
+    /// The intervals, each one after the intervals used by its bounds (without cycle)
+    pub fn interval_order(&self) -> Vec<IntervalId> {
+        let mut res = vec![];
+        let mut visited = vec![];
+        for x in self.intervals().iter() {
+            self.visit_order(Node::Interval(x.id()), &mut visited, &mut res);
+        }
+        res
+    }
+
+    //---------- Check Cycle ----------
+
+    /// Check that the definitions (let, functions and ltl) and the intervals do not depend on themselves.
+    pub fn check_cycle(&self) -> Result<(), Error> {
+        let nodes = self.definition_nodes();
+        let mut visits = vec![Visit::Todo; nodes.len()];
+        let mut errors = vec![];
+        for node in nodes.iter() {
+            self.visit_cycle(*node, &nodes, &mut visits, &mut vec![], &mut errors);
+        }
+        Error::from_list(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use crate::error::Error;
+    use crate::tests::load_source;
+
+    /// The names of the cycle found when loading the source
+    fn cycle(source: &str) -> Vec<String> {
+        match load_source(source) {
+            Err(Error::Cycle { path }) => path.into_iter().map(|(name, _)| name).collect(),
+            Err(e) => panic!("unexpected error: {}", e.category()),
+            Ok(_) => panic!("no cycle"),
+        }
+    }
+
+    #[test]
+    fn check_cycle_definitions() {
+        let path = cycle(
+            "var x: Int
+            let a: Int = b + x
+            let b: Int = a - 1
+            search initial solve",
+        );
+        assert!(path.contains(&"a".to_string()));
+        assert!(path.contains(&"b".to_string()));
+    }
+
+    #[test]
+    fn check_cycle_interval() {
+        let path = cycle(
+            "interval I = 0..last(J)
+            interval J = 0..last(I)
+            var x: I
+            search initial solve",
+        );
+        assert!(path.contains(&"I".to_string()));
+        assert!(path.contains(&"J".to_string()));
+    }
+
+    #[test]
+    fn check_cycle_none() {
+        let model = load_source(
+            "interval I = 0..last(J)
+            interval J = 0..5
+            var x: I
+            let a: Int = x + 1
+            let b: Int = a * 2
+            search initial solve",
+        );
+        assert!(model.is_ok());
+    }
+
+    #[test]
+    fn interval_order() {
+        let model = load_source(
+            "interval I = 0..c
+            cst c: J
+            interval J = 1..n
+            cst n: K
+            interval K = 1..5
+            var x: I
+            search initial solve",
+        )
+        .ok()
+        .unwrap();
+        let names: Vec<&str> = model
+            .interval_order()
+            .into_iter()
+            .map(|id| model.get(id).unwrap().name())
+            .collect();
+        assert_eq!(names, vec!["K", "J", "I"]);
+    }
+}
@@ -51,6 +51,10 @@ impl Declaration {
     pub fn is_constant(&self) -> bool {
         self.constant
     }
+
+    pub fn typ(&self) -> &Type {
+        &self.typ
+    }
 }
 
 //------------------------- Postion -------------------------
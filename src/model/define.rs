@@ -0,0 +1,139 @@
+use std::str::FromStr;
+
+use super::*;
+use crate::error::*;
+use crate::expr::*;
+use crate::*;
+use fraction::Fraction;
+
+/// Split a `name=value` definition.
+pub fn split_definition(definition: &str) -> Option<(String, String)> {
+    let (name, value) = definition.split_once('=')?;
+    let name = name.trim();
+    let value = value.trim();
+    if name.is_empty() || value.is_empty() {
+        None
+    } else {
+        Some((name.to_string(), value.to_string()))
+    }
+}
+
+impl Model {
+    #[allow(clippy::result_large_err)]
+    fn define_value(&self, name: &str, value: &str, typ: &Type) -> Result<Expr, Error> {
+        let invalid = || Error::Parse {
+            message: format!("Invalid value for constant '{}'", name),
+            token: Some(value.to_string()),
+            position: None,
+            expected: vec![typ.to_lang(self)],
+        };
+        match typ {
+            // only the possible bounds: the solver constrains the value by the actual ones
+            Type::Interval(id) => {
+                let interval = self.get(*id).unwrap();
+                let typ = Type::IntInterval(interval.min(), interval.max());
+                self.define_value(name, value, &typ)
+            }
+            Type::Bool => match value {
+                "true" => Ok(true.into()),
+                "false" => Ok(false.into()),
+                _ => Err(invalid()),
+            },
+            Type::Int => match i64::from_str(value) {
+                Ok(i) => Ok(i.into()),
+                Err(_) => Err(invalid()),
+            },
+            Type::IntInterval(min, max) => match i64::from_str(value) {
+                Ok(i) if *min <= i && i <= *max => Ok(i.into()),
+                _ => Err(invalid()),
+            },
+            Type::Unsigned(width) | Type::Signed(width) => {
+                let (min, max) = Type::bit_vector_range(*width, typ.bit_vector().unwrap().1);
+                match i64::from_str(value) {
+                    Ok(i) if min <= i as i128 && i as i128 <= max => Ok(i.into()),
+                    _ => Err(invalid()),
+                }
+            }
+            Type::Real => match Fraction::from_str(value) {
+                Ok(f) => Ok(Expr::new_real(f, None)),
+                Err(_) => Err(invalid()),
+            },
+            Type::Enumerate(e) => {
+                let enumerate = self.get(*e).unwrap();
+                match enumerate.from_name(value) {
+                    Some(element) => Ok(element.into()),
+                    None => Err(invalid()),
+                }
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Add the initial constraint `name = value` on a constant.
+    #[allow(clippy::result_large_err)]
+    pub fn define_constant(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        let dec: Option<&Declaration> = self.from_name(name);
+        let dec = match dec {
+            Some(dec) if dec.is_constant() => dec,
+            _ => {
+                return Err(Error::Resolve {
+                    category: "constant".to_string(),
+                    name: name.to_string(),
+                    position: None,
+                })
+            }
+        };
+        let id = dec.id();
+        let typ = dec.typ().clone();
+        let value = self.define_value(name, value, &typ)?;
+        let expr = Expr::from(id).eq(value);
+        self.add_initial(Initial::new(format!("define_{}", name), expr, None));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::load_source;
+
+    fn define(typ: &str, value: &str) -> bool {
+        let mut model = load_source(&format!(
+            "cst n: 2..4
+            interval I = 0..n
+            cst c: {}
+            search initial solve",
+            typ
+        ))
+        .ok()
+        .unwrap();
+        model.define_constant("c", value).is_ok()
+    }
+
+    #[test]
+    fn define_bit_vector() {
+        assert!(define("Unsigned[8]", "0"));
+        assert!(define("Unsigned[8]", "255"));
+        assert!(!define("Unsigned[8]", "256"));
+        assert!(!define("Unsigned[8]", "-1"));
+        assert!(define("Signed[8]", "-128"));
+        assert!(define("Signed[8]", "127"));
+        assert!(!define("Signed[8]", "128"));
+        assert!(!define("Signed[8]", "true"));
+    }
+
+    #[test]
+    fn define_symbolic_interval() {
+        // the possible bounds of I are 0..4
+        assert!(define("I", "0"));
+        assert!(define("I", "4"));
+        assert!(!define("I", "5"));
+        assert!(!define("I", "-1"));
+    }
+
+    #[test]
+    fn define_invalid() {
+        assert!(!define("Int", "1.5"));
+        assert!(!define("Bool", "1"));
+        assert!(!define("2..4", "1"));
+    }
+}
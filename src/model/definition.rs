@@ -49,6 +49,10 @@ impl Definition {
         }
     }
 
+    pub fn typ(&self) -> &Type {
+        &self.typ
+    }
+
     pub fn expr(&self) -> &Expr {
         &self.expr
     }
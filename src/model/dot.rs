@@ -0,0 +1,245 @@
+use super::*;
+use crate::common::*;
+use crate::expr::*;
+use crate::typing::*;
+
+//------------------------- Enumerate State Machine -------------------------
+
+/// Transition effect on an enumerate variable
+enum Target {
+    Same,
+    Elements(Vec<EnumerateElementId>),
+}
+
+fn conjuncts<'a>(expr: &'a Expr, res: &mut Vec<&'a Expr>, scopes: &mut Vec<&'a Vec<Expr>>) {
+    match expr.expression() {
+        Expression::Nary(NaryOperator::And, kids) => {
+            for kid in kids.iter() {
+                conjuncts(kid, res, scopes);
+            }
+        }
+        Expression::Scope(l, e) => {
+            scopes.push(l);
+            conjuncts(e, res, scopes);
+        }
+        _ => res.push(expr),
+    }
+}
+
+fn is_declaration(expr: &Expr, id: DeclarationId) -> bool {
+    match expr.expression() {
+        Expression::Declaration(x) => *x == id,
+        _ => false,
+    }
+}
+
+fn is_following(expr: &Expr, id: DeclarationId) -> bool {
+    match expr.expression() {
+        Expression::Following(kid) => is_declaration(kid, id),
+        _ => false,
+    }
+}
+
+fn element(expr: &Expr) -> Option<EnumerateElementId> {
+    match expr.expression() {
+        Expression::EnumerateElement(x) => Some(*x),
+        _ => None,
+    }
+}
+
+/// x = A, A = x, x != A, A != x (with f the test of the variable side)
+fn compare<F: Fn(&Expr) -> bool>(expr: &Expr, f: F) -> Option<(bool, EnumerateElementId)> {
+    if let Expression::Binary(left, op, right) = expr.expression() {
+        let equal = match op {
+            BinaryOperator::Eq => true,
+            BinaryOperator::Ne => false,
+            _ => return None,
+        };
+        if f(left) {
+            if let Some(e) = element(right) {
+                return Some((equal, e));
+            }
+        }
+        if f(right) {
+            if let Some(e) = element(left) {
+                return Some((equal, e));
+            }
+        }
+    }
+    None
+}
+
+fn restrict(values: &mut Vec<EnumerateElementId>, equal: bool, element: EnumerateElementId) {
+    values.retain(|x| (*x == element) == equal);
+}
+
+impl Model {
+    /// Only the conjuncts on enumerate variables are used
+    /// (the other constraints are abstracted away).
+    fn is_enumerate_conjunct(&self, expr: &Expr) -> bool {
+        expr.definitions().is_empty()
+            && expr.declarations().iter().all(|id| {
+                let dec = self.get(*id).unwrap();
+                dec.get_type(self).is_enumerate()
+            })
+    }
+
+    /// Source and target values of an enumerate variable for a transition,
+    /// None if the transition does not constrain the variable.
+    fn enumerate_transition(
+        &self,
+        dec: DeclarationId,
+        elements: &[EnumerateElementId],
+        transition: &Transition,
+    ) -> Option<(Vec<EnumerateElementId>, Target)> {
+        let mut list = vec![];
+        let mut scopes = vec![];
+        conjuncts(transition.expr(), &mut list, &mut scopes);
+
+        let mut sources = elements.to_vec();
+        let mut targets = elements.to_vec();
+        let mut same = false;
+        let mut constrained = false;
+
+        for c in list.into_iter().filter(|c| self.is_enumerate_conjunct(c)) {
+            if let Some((equal, e)) = compare(c, |x| is_declaration(x, dec)) {
+                restrict(&mut sources, equal, e);
+                constrained = true;
+            } else if let Some((equal, e)) = compare(c, |x| is_following(x, dec)) {
+                restrict(&mut targets, equal, e);
+                constrained = true;
+            } else if let Expression::Binary(l, BinaryOperator::Eq, r) = c.expression() {
+                if (is_following(l, dec) && is_declaration(r, dec))
+                    || (is_declaration(l, dec) && is_following(r, dec))
+                {
+                    same = true;
+                }
+            }
+        }
+        // Scope without the variable: unchanged
+        if !scopes.is_empty()
+            && scopes
+                .iter()
+                .all(|l| l.iter().all(|x| !is_declaration(x, dec)))
+        {
+            same = true;
+        }
+        if !constrained {
+            return None;
+        }
+        if same {
+            Some((sources, Target::Same))
+        } else {
+            Some((sources, Target::Elements(targets)))
+        }
+    }
+
+    fn enumerate_to_dot(&self, dec: &Declaration, enumerate: &Enumerate) -> Option<String> {
+        let elements: Vec<EnumerateElementId> =
+            enumerate.elements().iter().map(|e| e.id()).collect();
+        let mut edges = "".to_string();
+        for transition in self.transitions().iter() {
+            if let Some((sources, target)) =
+                self.enumerate_transition(dec.id(), &elements, transition)
+            {
+                for source in sources.iter() {
+                    let targets = match &target {
+                        Target::Same => vec![*source],
+                        Target::Elements(l) => l.clone(),
+                    };
+                    for t in targets.iter() {
+                        edges += &format!(
+                            "        \"{}_{}\" -> \"{}_{}\" [label=\"{}\"];\n",
+                            dec.name(),
+                            self.get(*source).unwrap().name(),
+                            dec.name(),
+                            self.get(*t).unwrap().name(),
+                            transition.name()
+                        );
+                    }
+                }
+            }
+        }
+        if edges.is_empty() {
+            return None;
+        }
+
+        let mut res = format!("    subgraph \"cluster_{}\" {{\n", dec.name());
+        res += &format!("        label=\"{}: {}\";\n", dec.name(), enumerate.name());
+        for id in elements.iter() {
+            let element = self.get(*id).unwrap();
+            res += &format!(
+                "        \"{}_{}\" [label=\"{}\", shape=ellipse];\n",
+                dec.name(),
+                element.name(),
+                element.name()
+            );
+        }
+        res += &edges;
+        res += "    }\n";
+        Some(res)
+    }
+
+    //------------------------- To Dot -------------------------
+
+    /// Graphviz view of the model: the variables read (dashed) and written
+    /// by each transition, and the state machine of each enumerate variable.
+    pub fn to_dot(&self) -> String {
+        let mut res = "digraph model {\n".to_string();
+        res += "    node [fontname=\"monospace\"];\n";
+
+        // Enumerate state machines
+        for id in self.var_declaration_ids() {
+            let dec = self.get(id).unwrap();
+            if let Type::Enumerate(e) = dec.get_type(self) {
+                let enumerate = self.get(e).unwrap();
+                if let Some(machine) = self.enumerate_to_dot(dec, enumerate) {
+                    res += &machine;
+                }
+            }
+        }
+
+        // Variables / Transitions
+        res += "    subgraph cluster_transitions {\n";
+        res += "        label=\"transitions\";\n";
+        for id in self.var_declaration_ids() {
+            let dec = self.get(id).unwrap();
+            res += &format!(
+                "        \"var_{}\" [label=\"{}: {}\", shape=ellipse];\n",
+                dec.name(),
+                dec.name(),
+                dec.get_type(self).to_lang(self)
+            );
+        }
+        for transition in self.transitions().iter() {
+            res += &format!(
+                "        \"trans_{}\" [label=\"{}\", shape=box];\n",
+                transition.name(),
+                transition.name()
+            );
+            let expr = transition.expr();
+            for id in expr.current_declarations() {
+                let dec = self.get(id).unwrap();
+                if !dec.is_constant() {
+                    res += &format!(
+                        "        \"var_{}\" -> \"trans_{}\" [style=dashed];\n",
+                        dec.name(),
+                        transition.name()
+                    );
+                }
+            }
+            for id in expr.following_declarations() {
+                let dec = self.get(id).unwrap();
+                res += &format!(
+                    "        \"trans_{}\" -> \"var_{}\";\n",
+                    transition.name(),
+                    dec.name()
+                );
+            }
+        }
+        res += "    }\n";
+
+        res += "}\n";
+        res
+    }
+}
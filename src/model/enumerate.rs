@@ -41,18 +41,20 @@ impl EnumerateElementId {
 #[derive(Clone)]
 pub struct Enumerate {
     id: EnumerateId,
+    ordered: bool,
     name: String,
     elements: Vec<EnumerateElement>,
     position: Option<Position>,
 }
 
 impl Enumerate {
-    pub fn new<S: Into<String>>(name: S, position: Option<Position>) -> Self {
+    pub fn new<S: Into<String>>(ordered: bool, name: S, position: Option<Position>) -> Self {
         let id = EnumerateId::empty();
         let name = name.into();
         let elements = Vec::new();
         Self {
             id,
+            ordered,
             name,
             elements,
             position,
@@ -71,6 +73,11 @@ impl Enumerate {
         &self.elements
     }
 
+    /// The elements are ordered by their declaration
+    pub fn is_ordered(&self) -> bool {
+        self.ordered
+    }
+
     //---------- Naming ----------
 
     pub fn namings(&self) -> Vec<Naming> {
@@ -122,7 +129,8 @@ impl std::fmt::Display for Enumerate {
 
 impl ToLang for Enumerate {
     fn to_lang(&self, _: &super::Model) -> String {
-        let mut res = format!("enum {} = {{", self.name());
+        let ordered = if self.ordered { "ordered " } else { "" };
+        let mut res = format!("enum {}{} = {{", ordered, self.name());
         if let Some((first, others)) = self.elements.split_first() {
             res.push_str(&format!("{}", first));
             for e in others {
@@ -137,7 +145,8 @@ impl ToLang for Enumerate {
 impl ToDebug for Enumerate {
     fn to_debug(&self, model: &super::Model) -> String {
         let mut res = format!("// {:?}\n", self.id);
-        res.push_str(&format!("enum {} = {{", self.name));
+        let ordered = if self.ordered { "ordered " } else { "" };
+        res.push_str(&format!("enum {}{} = {{", ordered, self.name));
         if let Some((first, others)) = self.elements.split_first() {
             res.push_str(&format!("{}", first.to_debug(model)));
             for e in others {
@@ -1,6 +1,10 @@
+use super::*;
+use crate::error::*;
+use crate::expr::*;
 use crate::parser::Position;
-use crate::{error::*, ToDebug};
-use crate::{Id, Named, ToLang, WithPosition};
+use crate::typing::*;
+use crate::*;
+use std::collections::HashMap;
 
 //------------------------- Interval Id -------------------------
 
@@ -22,41 +26,203 @@ impl Id for IntervalId {
 pub struct Interval {
     id: IntervalId,
     name: String,
+    anonymous: bool,
+    min_expr: Expr,
+    max_expr: Expr,
     min: i64,
     max: i64,
+    symbolic: bool,
     position: Option<Position>,
 }
 
 impl Interval {
-    pub fn new<S: Into<String>>(name: S, position: Option<Position>, min: i64, max: i64) -> Self {
+    pub fn new<S: Into<String>>(
+        name: S,
+        position: Option<Position>,
+        min_expr: Expr,
+        max_expr: Expr,
+    ) -> Self {
         let id = IntervalId::empty();
         let name = name.into();
+        let min = Self::value(&min_expr).unwrap_or_default();
+        let max = Self::value(&max_expr).unwrap_or_default();
         Self {
             id,
             name,
+            anonymous: false,
+            min_expr,
+            max_expr,
             min,
             max,
+            symbolic: false,
             position,
         }
     }
 
+    /// An interval type written in place (ex: 'var x: 1..n')
+    pub fn new_anonymous(position: Option<Position>, min_expr: Expr, max_expr: Expr) -> Self {
+        let mut interval = Self::new("", position, min_expr, max_expr);
+        interval.anonymous = true;
+        interval
+    }
+
+    fn value(expr: &Expr) -> Option<i64> {
+        match expr.expression() {
+            Expression::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn is_anonymous(&self) -> bool {
+        self.anonymous
+    }
+
+    pub fn min_expr(&self) -> &Expr {
+        &self.min_expr
+    }
+    pub fn max_expr(&self) -> &Expr {
+        &self.max_expr
+    }
+
+    /// The lower bound (the smallest possible one if it is symbolic)
     pub fn min(&self) -> i64 {
         self.min
     }
+    /// The upper bound (the greatest possible one if it is symbolic)
     pub fn max(&self) -> i64 {
         self.max
     }
 
-    pub fn check_interval(&self) -> Result<(), Error> {
-        if self.min > self.max {
+    /// The bounds are not both constant values
+    pub fn is_symbolic(&self) -> bool {
+        self.symbolic
+    }
+
+    /// The propagated bounds
+    pub fn bounds(&self, model: &Model) -> (Expr, Expr) {
+        (
+            self.min_expr.propagate(model),
+            self.max_expr.propagate(model),
+        )
+    }
+
+    /// The constraint of the values in the interval, only needed if the bounds are symbolic
+    pub fn domain(&self, model: &Model, value: Expr) -> Option<Expr> {
+        if self.symbolic {
+            let (min, max) = self.bounds(model);
+            Some(Expr::and(vec![value.clone().ge(min), value.le(max)]))
+        } else {
+            None
+        }
+    }
+
+    //---------- Resolve Type ----------
+
+    pub fn resolve_type(&mut self, types: &HashMap<String, Type>) -> Result<(), Error> {
+        self.min_expr = self.min_expr.resolve_type(types)?;
+        self.max_expr = self.max_expr.resolve_type(types)?;
+        Ok(())
+    }
+
+    //---------- Resolve Expr ----------
+
+    pub fn resolve_expr(&self, model: &Model, entries: &Vec<Entry>) -> Result<Self, Error> {
+        let mut interval = self.clone();
+        interval.min_expr = self.min_expr.resolve(model, entries)?;
+        interval.max_expr = self.max_expr.resolve(model, entries)?;
+        Ok(interval)
+    }
+
+    //---------- Check Interval ----------
+
+    /// Compute the values of the bounds (or their range if they are symbolic)
+    pub fn check_interval(&self, model: &Model) -> Result<Self, Error> {
+        let mut interval = self.clone();
+        let min_expr = self.check_bound(model, &self.min_expr)?;
+        let max_expr = self.check_bound(model, &self.max_expr)?;
+        interval.min = match min_expr.get_type(model) {
+            Type::IntInterval(min, _) => min,
+            _ => return Err(self.unbounded(model, &self.min_expr)),
+        };
+        interval.max = match max_expr.get_type(model) {
+            Type::IntInterval(_, max) => max,
+            _ => return Err(self.unbounded(model, &self.max_expr)),
+        };
+        interval.symbolic = Self::value(&min_expr).is_none() || Self::value(&max_expr).is_none();
+        if interval.min > interval.max {
             Err(Error::Interval {
-                name: self.name.clone(),
+                name: self.label(model),
                 position: self.position.clone(),
             })
         } else {
-            Ok(())
+            Ok(interval)
         }
     }
+
+    fn check_bound(&self, model: &Model, bound: &Expr) -> Result<Expr, Error> {
+        bound.check_type(model)?;
+        bound.check_is_integer(model)?;
+        let bound = bound.propagate(model);
+        self.check_constant(model, &bound)?;
+        Ok(bound)
+    }
+
+    /// Only the constants are allowed in the bounds
+    fn check_constant(&self, model: &Model, expr: &Expr) -> Result<(), Error> {
+        match expr.expression() {
+            Expression::Declaration(id) if model.get(*id).unwrap().is_constant() => {}
+            Expression::FunDec(id) if model.get(*id).unwrap().is_constant() => {}
+            Expression::Declaration(_)
+            | Expression::FunDec(_)
+            | Expression::Parameter(_)
+            | Expression::Following(_)
+            | Expression::State(_, _, _)
+            | Expression::Trace(_, _)
+            | Expression::LTLunary(_, _)
+            | Expression::LTLbinary(_, _, _)
+            | Expression::LTLVariable(_)
+            | Expression::LtlDefinition(_) => {
+                return Err(Error::Time {
+                    message: "Only constants allowed in 'Interval'".into(),
+                    name: self.label(model),
+                    position: self.position.clone(),
+                    expr: expr.clone(),
+                })
+            }
+            _ => {}
+        }
+        for kid in expr.kids() {
+            self.check_constant(model, kid)?;
+        }
+        Ok(())
+    }
+
+    fn unbounded(&self, model: &Model, bound: &Expr) -> Error {
+        Error::Bounded {
+            name: format!("{} in {}", bound.to_lang(model), self.label(model)),
+            position: self.position.clone(),
+        }
+    }
+
+    //---------- To Lang ----------
+
+    /// The name, or the bounds of an anonymous interval
+    pub fn label(&self, model: &Model) -> String {
+        if self.anonymous {
+            self.bounds_to_lang(model)
+        } else {
+            self.name.clone()
+        }
+    }
+
+    /// The bounds 'min..max'
+    pub fn bounds_to_lang(&self, model: &Model) -> String {
+        format!(
+            "{}..{}",
+            self.min_expr.to_lang(model),
+            self.max_expr.to_lang(model)
+        )
+    }
 }
 
 impl Named<IntervalId> for Interval {
@@ -86,20 +252,19 @@ impl std::fmt::Display for Interval {
 }
 
 impl ToLang for Interval {
-    fn to_lang(&self, _: &super::Model) -> String {
-        format!("interval {} = {}..{}", self.name(), self.min, self.max)
+    fn to_lang(&self, model: &super::Model) -> String {
+        if self.anonymous {
+            self.bounds_to_lang(model)
+        } else {
+            format!("interval {} = {}", self.name(), self.bounds_to_lang(model))
+        }
     }
 }
 
 impl ToDebug for Interval {
-    fn to_debug(&self, _: &super::Model) -> String {
-        let mut res = format!("// {:?}\n", self.id);
-        res.push_str(&format!(
-            "interval {} = {}..{}",
-            self.name(),
-            self.min,
-            self.max
-        ));
+    fn to_debug(&self, model: &super::Model) -> String {
+        let mut res = format!("// {:?} ({}..{})\n", self.id, self.min, self.max);
+        res.push_str(&self.to_lang(model));
         res
     }
 }
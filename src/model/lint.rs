@@ -0,0 +1,335 @@
+use super::*;
+use crate::common::*;
+use crate::error::*;
+use crate::expr::*;
+use crate::parser::Position;
+
+/// Scope variables never changed (x') in the scope
+fn unassigned_scopes(expr: &Expr, res: &mut Vec<(DeclarationId, Option<Position>)>) {
+    if let Expression::Scope(l, e) = expr.expression() {
+        let changed = e.following_declarations();
+        for x in l.iter() {
+            if let Expression::Declaration(id) = x.expression() {
+                if !changed.contains(id) {
+                    res.push((*id, x.position().clone()));
+                }
+            }
+        }
+    }
+    for kid in expr.kids() {
+        unassigned_scopes(kid, res);
+    }
+}
+
+impl Model {
+    /// All the expressions of the model
+    pub fn exprs(&self) -> Vec<&Expr> {
+        let mut res = vec![];
+        for x in self.intervals().iter() {
+            res.push(x.min_expr());
+            res.push(x.max_expr());
+        }
+        res.extend(self.definitions().iter().map(|x| x.expr()));
+        res.extend(self.fun_defs().iter().map(|x| x.expr()));
+        res.extend(self.initials().iter().map(|x| x.expr()));
+        res.extend(self.invariants().iter().map(|x| x.expr()));
+        res.extend(self.transitions().iter().map(|x| x.expr()));
+        res.extend(self.triggers().iter().map(|x| x.expr()));
+        res.extend(self.ltl_definitions().iter().map(|x| x.expr()));
+        if let Some(phi) = self.property() {
+            res.push(phi);
+        }
+        for optimization in self.search().search_type().optimizations() {
+            res.push(&optimization.objective);
+            res.extend(optimization.bound.iter());
+        }
+        if let Some(pareto) = self.search().search_type().pareto() {
+            res.extend(pareto.objectives.iter());
+        }
+        if let Some(enumeration) = self.search().search_type().enumeration() {
+            res.extend(enumeration.projection.iter());
+        }
+        res
+    }
+
+    /// Declarations of the expression, and of the definitions it uses (transitively)
+    fn declarations_through_definitions(&self, expr: &Expr) -> Vec<DeclarationId> {
+        let mut res = expr.declarations();
+        let mut definitions = expr.definitions();
+        let mut i = 0;
+        while i < definitions.len() {
+            let def = self.get(definitions[i]).unwrap();
+            res.extend(def.expr().declarations());
+            for id in def.expr().definitions() {
+                if !definitions.contains(&id) {
+                    definitions.push(id);
+                }
+            }
+            i += 1;
+        }
+        res
+    }
+
+    //---------- Lints ----------
+
+    fn lint_unused(&self, res: &mut Vec<Warning>) {
+        let exprs = self.exprs();
+        let mut used_decs = vec![];
+        let mut used_defs = vec![];
+        for expr in exprs.iter() {
+            used_decs.extend(expr.declarations());
+            used_defs.extend(expr.definitions());
+        }
+        let decs: Vec<DeclarationId> = self
+            .declarations()
+            .iter()
+            .map(|x| x.id())
+            .filter(|id| !used_decs.contains(id))
+            .collect();
+        if !decs.is_empty() {
+            res.push(Warning::UnusedDec(decs));
+        }
+        let defs: Vec<DefinitionId> = self
+            .definition_ids()
+            .into_iter()
+            .filter(|id| !used_defs.contains(id))
+            .collect();
+        if !defs.is_empty() {
+            res.push(Warning::UnusedDef(defs));
+        }
+    }
+
+    fn lint_never_primed(&self, res: &mut Vec<Warning>) {
+        // without transition or trigger, the variables cannot change anyway
+        if self.transitions().is_empty() && self.triggers().is_empty() {
+            return;
+        }
+        let mut changed = vec![];
+        for x in self.transitions().iter() {
+            changed.extend(x.expr().following_declarations());
+        }
+        for x in self.triggers().iter() {
+            changed.extend(x.expr().following_declarations());
+        }
+        let v: Vec<DeclarationId> = self
+            .var_declaration_ids()
+            .into_iter()
+            .filter(|id| !changed.contains(id))
+            .collect();
+        if !v.is_empty() {
+            res.push(Warning::NeverPrimed(v));
+        }
+    }
+
+    fn lint_unconstrained(&self, res: &mut Vec<Warning>) {
+        let mut constrained = vec![];
+        for x in self.initials().iter() {
+            constrained.extend(self.declarations_through_definitions(x.expr()));
+        }
+        let v: Vec<DeclarationId> = self
+            .cst_declaration_ids()
+            .into_iter()
+            .filter(|id| !constrained.contains(id))
+            .collect();
+        if !v.is_empty() {
+            res.push(Warning::Unconstrained(v));
+        }
+    }
+
+    fn lint_unsat_transition(&self, res: &mut Vec<Warning>) {
+        let v: Vec<TransitionId> = self
+            .transitions()
+            .iter()
+            .filter(|x| {
+                matches!(
+                    x.expr().propagate(self).expression(),
+                    Expression::Bool(false)
+                )
+            })
+            .map(|x| x.id())
+            .collect();
+        if !v.is_empty() {
+            res.push(Warning::UnsatTransition(v));
+        }
+    }
+
+    fn lint_unassigned_scope(&self, res: &mut Vec<Warning>) {
+        let mut v = vec![];
+        for x in self.transitions().iter() {
+            unassigned_scopes(x.expr(), &mut v);
+        }
+        if !v.is_empty() {
+            res.push(Warning::UnassignedScope(v));
+        }
+    }
+
+    /// Static checks of a loaded model (the model is not changed).
+    pub fn lint(&self, lints: &[Lint]) -> Vec<Warning> {
+        let mut res = vec![];
+        for lint in lints.iter() {
+            match lint {
+                Lint::Unused => self.lint_unused(&mut res),
+                Lint::NeverPrimed => self.lint_never_primed(&mut res),
+                Lint::Unconstrained => self.lint_unconstrained(&mut res),
+                Lint::UnsatTransition => self.lint_unsat_transition(&mut res),
+                Lint::UnassignedScope => self.lint_unassigned_scope(&mut res),
+                Lint::All => {}
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Lint;
+    use crate::tests::load_source;
+    use crate::typing::Type;
+
+    /// Names of the items reported by the lint
+    fn lint(source: &str, lint: Lint) -> Vec<String> {
+        let model = load_source(source).ok().unwrap();
+        model
+            .lint(&[lint])
+            .iter()
+            .flat_map(|w| w.items(&model))
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    #[test]
+    fn lint_unused() {
+        let source = "cst c: Int
+            var x, y: Int
+            let d: Int = x + 1
+            init I { x = c }
+            trans inc { x' = x }
+            search initial solve";
+        assert_eq!(lint(source, Lint::Unused), vec!["y", "d"]);
+    }
+
+    #[test]
+    fn lint_unused_owners() {
+        // each constant is only used by one kind of expression
+        let source = "cst a: 1..5
+            cst b, c, d, e, f, g, h, i, j: Int
+            var x: Int
+            interval I = 0..a
+            var y: I
+            let def: Int = b
+            ltl l = F(x = c)
+            init ini { x = def and y = 0 }
+            inv v { x >= d }
+            trans t { x' = x + e and y' = y }
+            trig tr { x' = f }
+            prop = l or G(x < g)
+            search initial minimize h then maximize x until i";
+        assert_eq!(lint(source, Lint::Unused), vec!["j"]);
+        let source = "cst a, b: Int
+            var x: Int
+            init ini { x = 0 }
+            search initial solve 2 on a";
+        assert_eq!(lint(source, Lint::Unused), vec!["b"]);
+    }
+
+    #[test]
+    fn lint_unused_fun_def() {
+        let mut model = load_source(
+            "cst c: Int
+            var x: Int
+            init I { x = 0 }
+            search initial solve",
+        )
+        .ok()
+        .unwrap();
+        let c = model.cst_declaration_ids()[0];
+        let fun = FunDef::new("f", vec![], Type::Int, Expr::from(c), None);
+        model.add_fun_def(fun);
+        assert!(model
+            .lint(&[Lint::Unused])
+            .iter()
+            .all(|w| w.items(&model).is_empty()));
+    }
+
+    #[test]
+    fn lint_never_primed() {
+        let source = "var x, y: Int
+            init I { x = 0 and y = 0 }
+            trans inc { x' = x + 1 and y' >= 0 }
+            trans stay { x' = x }
+            search initial solve";
+        assert!(lint(source, Lint::NeverPrimed).is_empty());
+        let source = "var x, y, z: Int
+            init I { x = 0 and y = 0 and z = 0 }
+            trans inc { x' = x + 1 }
+            trig keep { z' = z }
+            search initial solve";
+        assert_eq!(lint(source, Lint::NeverPrimed), vec!["y"]);
+        // without transition nor trigger, the variables cannot change anyway
+        let source = "var x: Int
+            init I { x = 0 }
+            search initial solve";
+        assert!(lint(source, Lint::NeverPrimed).is_empty());
+    }
+
+    #[test]
+    fn lint_never_primed_let() {
+        let source = "var x, y: Int
+            init I { x = 0 and y = 0 }
+            trans inc { let n = x + 1 in x' = n and y' = y }
+            search initial solve";
+        assert!(lint(source, Lint::NeverPrimed).is_empty());
+    }
+
+    #[test]
+    fn lint_unconstrained() {
+        let source = "cst c, d: Int
+            var x: Int
+            init I { c > 0 and x = d }
+            search initial solve";
+        assert!(lint(source, Lint::Unconstrained).is_empty());
+        let source = "cst c, d: Int
+            var x: Int
+            init I { c > 0 }
+            trans inc { x' = x + d }
+            search initial solve";
+        assert_eq!(lint(source, Lint::Unconstrained), vec!["d"]);
+    }
+
+    #[test]
+    fn lint_unconstrained_definition() {
+        let source = "cst c, d, e: Int
+            let small: Bool = c < 10
+            let ok: Bool = small and d > 0
+            init I { ok and (let n = e + 1 in n > 0) }
+            search initial solve";
+        assert!(lint(source, Lint::Unconstrained).is_empty());
+        let source = "cst c, d: Int
+            let small: Bool = c < 10
+            init I { small }
+            inv V { d > 0 }
+            search initial solve";
+        assert_eq!(lint(source, Lint::Unconstrained), vec!["d"]);
+    }
+
+    #[test]
+    fn lint_unsat_transition() {
+        let source = "var x: Int
+            init I { x = 0 }
+            trans inc { x' = x + 1 }
+            trans stay { x' = x and 1 > 2 }
+            search initial solve";
+        assert_eq!(lint(source, Lint::UnsatTransition), vec!["stay"]);
+    }
+
+    #[test]
+    fn lint_unassigned_scope() {
+        let source = "var x, y: Int
+            init I { x = 0 and y = 0 }
+            trans inc { |x, y|(x' = x + 1) }
+            trans stay { |x|(x' = x + 1) }
+            search initial solve";
+        assert_eq!(lint(source, Lint::UnassignedScope), vec!["y"]);
+    }
+}
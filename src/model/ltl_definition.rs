@@ -46,6 +46,10 @@ impl LtlDefinition {
         &self.expr
     }
 
+    pub fn set_expr(&mut self, expr: Expr) {
+        self.expr = expr;
+    }
+
     //---------- Resolve Expr ----------
 
     pub fn resolve_expr(&self, model: &Model, entries: &Vec<Entry>) -> Result<Self, Error> {
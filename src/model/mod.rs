@@ -40,6 +40,13 @@ pub use ltl_definition::*;
 pub mod ltl_variable;
 pub use ltl_variable::*;
 
+pub mod cycle;
+pub mod dot;
+pub mod lint;
+
+pub mod define;
+pub use define::*;
+
 //
 pub mod entry;
 pub use entry::*;
@@ -231,6 +231,10 @@ impl Model {
         id
     }
 
+    pub fn set_ltl_definition_expr(&mut self, id: LtlDefinitionId, expr: Expr) {
+        self.ltl_definitions[id.index()].set_expr(expr);
+    }
+
     //---------- Property ----------
 
     pub fn property(&self) -> &Option<Expr> {
@@ -241,6 +245,23 @@ impl Model {
         self.property = Some(expr)
     }
 
+    /// The initial defining the flattened property: prop = phi
+    pub fn property_initial(&self) -> Option<InitialId> {
+        let prop = match self.property.as_ref().map(|phi| phi.expression()) {
+            Some(Expression::Declaration(id)) => *id,
+            _ => return None,
+        };
+        self.initials
+            .iter()
+            .find(|x| match x.expr().expression() {
+                Expression::Binary(left, BinaryOperator::Eq, _) => {
+                    matches!(left.expression(), Expression::Declaration(id) if *id == prop)
+                }
+                _ => false,
+            })
+            .map(|x| x.id())
+    }
+
     //---------- Search ----------
 
     pub fn search(&self) -> &Search {
@@ -269,6 +290,33 @@ impl Model {
         id
     }
 
+    //---------- Trace Aggregate ----------
+
+    /// The trace aggregates (sum_trace, ...) of all the expressions, inner first
+    pub fn trace_aggregates(&self) -> Vec<Expr> {
+        let mut exprs: Vec<&Expr> = vec![];
+        exprs.extend(self.definitions.iter().map(|x| x.expr()));
+        exprs.extend(self.initials.iter().map(|x| x.expr()));
+        exprs.extend(self.invariants.iter().map(|x| x.expr()));
+        exprs.extend(self.transitions.iter().map(|x| x.expr()));
+        exprs.extend(self.triggers.iter().map(|x| x.expr()));
+        exprs.extend(self.ltl_definitions.iter().map(|x| x.expr()));
+        exprs.extend(self.ltl_variables.iter().map(|x| x.expr()));
+        exprs.extend(self.property.iter());
+        for optimization in self.search.search_type().optimizations() {
+            exprs.push(&optimization.objective);
+            exprs.extend(optimization.bound.iter());
+        }
+        if let Some(pareto) = self.search.search_type().pareto() {
+            exprs.extend(pareto.objectives.iter());
+        }
+        let mut res = vec![];
+        for expr in exprs {
+            expr.add_trace_aggregates(&mut res);
+        }
+        res
+    }
+
     pub fn get_ltl_expr(&self, expr: Expr) -> Expr {
         if let Some(v) = self.ltl_variables.iter().find(|v| v.expr().is_same(&expr)) {
             return v.id().into();
@@ -279,6 +327,7 @@ impl Model {
     pub fn flatten_ltl(&mut self) {
         if let Some(phi) = self.property.clone() {
             // ----- Non Loop -----
+            let phi_position = phi.position().clone();
             let phi = phi.flatten_ltl(self);
             // Dec
             let dec_id = DeclarationId(self.declarations.len());
@@ -288,7 +337,7 @@ impl Model {
             // Init
             let init_id = InitialId(self.initials.len());
             let expr = Expr::eq(dec_id.into(), phi);
-            let mut init = Initial::new("prop", expr, None);
+            let mut init = Initial::new("prop", expr, phi_position);
             init.set_id(init_id);
             self.initials.push(init);
             //
@@ -341,9 +390,11 @@ impl Model {
 
     //---------- Check Interval ----------
 
-    pub fn check_intervals(&self) -> Result<(), Error> {
-        for i in self.intervals.iter() {
-            i.check_interval()?;
+    /// Evaluate the bounds of the intervals (after the intervals they use, the cycles are checked before)
+    pub fn check_intervals(&mut self) -> Result<(), Error> {
+        for id in self.interval_order() {
+            let interval = self.intervals[id.index()].check_interval(self)?;
+            self.intervals[id.index()] = interval;
         }
         Ok(())
     }
@@ -353,7 +404,12 @@ impl Model {
     pub fn namings(&self) -> Vec<Naming> {
         let mut v = Vec::new();
         v.extend(self.enumerates.iter().flat_map(|x| x.namings()));
-        v.extend(self.intervals.iter().map(|x| x.naming()));
+        v.extend(
+            self.intervals
+                .iter()
+                .filter(|x| !x.is_anonymous())
+                .map(|x| x.naming()),
+        );
         v.extend(self.declarations.iter().map(|x| x.naming()));
         v.extend(self.definitions.iter().map(|x| x.naming()));
         v.extend(self.fun_decs.iter().map(|x| x.naming()));
@@ -389,7 +445,7 @@ impl Model {
             map.insert(x.name().to_string(), Type::Enumerate(x.id()));
         }
         // Interval
-        for x in self.intervals.iter() {
+        for x in self.intervals.iter().filter(|x| !x.is_anonymous()) {
             map.insert(x.name().to_string(), Type::Interval(x.id()));
         }
         //
@@ -400,40 +456,63 @@ impl Model {
 
     pub fn resolve_type(&mut self) -> Result<(), Error> {
         let types = self.types();
+        let mut errors = vec![];
+        // Interval
+        for x in self.intervals.iter_mut() {
+            if let Err(e) = x.resolve_type(&types) {
+                errors.push(e);
+            }
+        }
         // Declaration
         for x in self.declarations.iter_mut() {
-            x.resolve_type(&types)?;
+            if let Err(e) = x.resolve_type(&types) {
+                errors.push(e);
+            }
         }
         // Definitions
         for x in self.definitions.iter_mut() {
-            x.resolve_type(&types)?;
+            if let Err(e) = x.resolve_type(&types) {
+                errors.push(e);
+            }
         }
         // FunDec
         for x in self.fun_decs.iter_mut() {
-            x.resolve_type(&types)?;
+            if let Err(e) = x.resolve_type(&types) {
+                errors.push(e);
+            }
         }
         // FunDef
         for x in self.fun_defs.iter_mut() {
-            x.resolve_type(&types)?;
+            if let Err(e) = x.resolve_type(&types) {
+                errors.push(e);
+            }
         }
         // Init
         for x in self.initials.iter_mut() {
-            x.resolve_type(&types)?;
+            if let Err(e) = x.resolve_type(&types) {
+                errors.push(e);
+            }
         }
         // Inv
         for x in self.invariants.iter_mut() {
-            x.resolve_type(&types)?;
+            if let Err(e) = x.resolve_type(&types) {
+                errors.push(e);
+            }
         }
         // Trans
         for x in self.transitions.iter_mut() {
-            x.resolve_type(&types)?;
+            if let Err(e) = x.resolve_type(&types) {
+                errors.push(e);
+            }
         }
         // Trans
         for x in self.triggers.iter_mut() {
-            x.resolve_type(&types)?;
+            if let Err(e) = x.resolve_type(&types) {
+                errors.push(e);
+            }
         }
         //
-        Ok(())
+        Error::from_list(errors)
     }
 
     //---------- Entries ----------
@@ -471,135 +550,226 @@ impl Model {
     //---------- Resolve Expr ----------
 
     pub fn resolve_expr(&mut self) -> Result<(), Error> {
+        // first(I) and last(I) are replaced by the bounds of I, that must not depend on I
+        self.check_cycle()?;
         let entries = self.entries();
+        let mut errors = vec![];
+        // Interval
+        for index in 0..self.intervals.len() {
+            match self.intervals[index].resolve_expr(self, &entries) {
+                Ok(x) => self.intervals[index] = x,
+                Err(e) => errors.push(e),
+            }
+        }
         // Definition
         let mut definitions = Vec::new();
         for x in self.definitions.iter() {
-            let y = x.resolve_expr(self, &entries)?;
-            definitions.push(y);
+            match x.resolve_expr(self, &entries) {
+                Ok(y) => definitions.push(y),
+                Err(e) => {
+                    errors.push(e);
+                    definitions.push(x.clone());
+                }
+            }
         }
         self.definitions = definitions;
         // FunDef
         let mut fun_defs = Vec::new();
         for x in self.fun_defs.iter() {
-            let y = x.resolve_expr(self, &entries)?;
-            fun_defs.push(y);
+            match x.resolve_expr(self, &entries) {
+                Ok(y) => fun_defs.push(y),
+                Err(e) => {
+                    errors.push(e);
+                    fun_defs.push(x.clone());
+                }
+            }
         }
         self.fun_defs = fun_defs;
         // Initial
         let mut initials = Vec::new();
         for x in self.initials.iter() {
-            let y = x.resolve_expr(self, &entries)?;
-            initials.push(y);
+            match x.resolve_expr(self, &entries) {
+                Ok(y) => initials.push(y),
+                Err(e) => {
+                    errors.push(e);
+                    initials.push(x.clone());
+                }
+            }
         }
         self.initials = initials;
         // Invariant
         let mut invariants = Vec::new();
         for x in self.invariants.iter() {
-            let y = x.resolve_expr(self, &entries)?;
-            invariants.push(y);
+            match x.resolve_expr(self, &entries) {
+                Ok(y) => invariants.push(y),
+                Err(e) => {
+                    errors.push(e);
+                    invariants.push(x.clone());
+                }
+            }
         }
         self.invariants = invariants;
         // Transition
         let mut transitions = Vec::new();
         for x in self.transitions.iter() {
-            let y = x.resolve_expr(self, &entries)?;
-            transitions.push(y);
+            match x.resolve_expr(self, &entries) {
+                Ok(y) => transitions.push(y),
+                Err(e) => {
+                    errors.push(e);
+                    transitions.push(x.clone());
+                }
+            }
         }
         self.transitions = transitions;
         // Trigger
         let mut triggers = Vec::new();
         for x in self.triggers.iter() {
-            let y = x.resolve_expr(self, &entries)?;
-            triggers.push(y);
+            match x.resolve_expr(self, &entries) {
+                Ok(y) => triggers.push(y),
+                Err(e) => {
+                    errors.push(e);
+                    triggers.push(x.clone());
+                }
+            }
         }
         self.triggers = triggers;
         // LTL Definitions
         let mut ltl_definitions = Vec::new();
         for x in self.ltl_definitions.iter() {
-            let y = x.resolve_expr(self, &entries)?;
-            ltl_definitions.push(y);
+            match x.resolve_expr(self, &entries) {
+                Ok(y) => ltl_definitions.push(y),
+                Err(e) => {
+                    errors.push(e);
+                    ltl_definitions.push(x.clone());
+                }
+            }
         }
         self.ltl_definitions = ltl_definitions;
         // Property
         if let Some(phi) = &self.property {
-            let phi = phi.resolve(self, &entries)?;
-            self.property = Some(phi)
+            match phi.resolve(self, &entries) {
+                Ok(phi) => self.property = Some(phi),
+                Err(e) => errors.push(e),
+            }
         }
         // Search
-        let search = self.search.resolve_expr(self, &entries)?;
-        self.search = search;
+        match self.search.resolve_expr(self, &entries) {
+            Ok(search) => self.search = search,
+            Err(e) => errors.push(e),
+        }
         //
-        Ok(())
+        Error::from_list(errors)
     }
 
     //---------- Typing ----------
 
     pub fn check_type(&self) -> Result<(), Error> {
+        let mut errors = vec![];
         // Definition
         for x in self.definitions.iter() {
-            x.check_type(self)?;
+            if let Err(e) = x.check_type(self) {
+                errors.push(e);
+            }
         }
         // FunDef
         for x in self.fun_defs.iter() {
-            x.check_type(self)?;
+            if let Err(e) = x.check_type(self) {
+                errors.push(e);
+            }
         }
         // Initial
         for x in self.initials.iter() {
-            x.check_type(self)?;
+            if let Err(e) = x.check_type(self) {
+                errors.push(e);
+            }
         }
         // Invariant
         for x in self.invariants.iter() {
-            x.check_type(self)?;
+            if let Err(e) = x.check_type(self) {
+                errors.push(e);
+            }
         }
         // Transition
         for x in self.transitions.iter() {
-            x.check_type(self)?;
+            if let Err(e) = x.check_type(self) {
+                errors.push(e);
+            }
         }
         // Trigger
         for x in self.triggers.iter() {
-            x.check_type(self)?;
+            if let Err(e) = x.check_type(self) {
+                errors.push(e);
+            }
         }
         // LTL Definitions
         for x in self.ltl_definitions.iter() {
-            x.check_type(self)?;
+            if let Err(e) = x.check_type(self) {
+                errors.push(e);
+            }
         }
         // Property
         if let Some(phi) = &self.property {
-            phi.check_type(self)?;
+            if let Err(e) = phi.check_type(self) {
+                errors.push(e);
+            }
         }
         // Search
-        self.search.check_type(self)?;
+        if let Err(e) = self.search.check_type(self) {
+            errors.push(e);
+        }
         //
-        Ok(())
+        Error::from_list(errors)
     }
 
     //---------- Time ----------
 
+    /// The trace aggregates are values of the whole trace: only allowed in the property and the objectives
+    fn check_no_trace_aggregate<T: Id, N: Named<T> + WithPosition>(
+        entry: &N,
+        category: &str,
+        expr: &Expr,
+    ) -> Result<(), Error> {
+        match expr.trace_aggregates().first() {
+            Some(trace) => Err(Error::Time {
+                message: format!("Trace aggregate not allowed in '{}'", category),
+                name: entry.name().to_string(),
+                position: entry.position().clone(),
+                expr: trace.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
     pub fn check_time(&self) -> Result<(), Error> {
         // Definition
         for x in self.definitions.iter() {
             x.check_time()?;
+            Self::check_no_trace_aggregate(x, "definition", x.expr())?;
         }
         // FunDef
         for x in self.fun_defs.iter() {
             x.check_time()?;
+            Self::check_no_trace_aggregate(x, "definition", x.expr())?;
         }
         // Initial
         for x in self.initials.iter() {
             x.check_time()?;
+            Self::check_no_trace_aggregate(x, "initial", x.expr())?;
         }
         // Invariant
         for x in self.invariants.iter() {
             x.check_time()?;
+            Self::check_no_trace_aggregate(x, "invariant", x.expr())?;
         }
         // Transition
         for x in self.transitions.iter() {
             x.check_time(self)?;
+            Self::check_no_trace_aggregate(x, "transition", x.expr())?;
         }
         // Trigger
         for x in self.triggers.iter() {
             x.check_time(self)?;
+            Self::check_no_trace_aggregate(x, "trigger", x.expr())?;
         }
         // LTL Definitions
         for x in self.ltl_definitions.iter() {
@@ -710,14 +880,15 @@ impl Model {
         pretty.add(entry)
     }
 
-    pub fn check_var_fun_bounded_type(&self, _pretty: &mut d_stuff::Pretty) {
-        todo!()
-        // for fun in self.fun_decs.iter() {
-        //     if !fun.is_constant() {
-        //         fun.check_bounded_parameters(self)?;
-        //     }
-        // }
-        // Ok(())
+    pub fn check_var_fun_bounded_type(&self, pretty: &mut d_stuff::Pretty) {
+        let mut v = vec![];
+        for fun in self.fun_decs.iter() {
+            if !fun.is_constant() && !fun.get_type(self).is_bounded() {
+                v.push(fun.id())
+            }
+        }
+        let entry = Warning::UnboundedFun(v).to_entry(self);
+        pretty.add(entry)
     }
 
     //==================== ====================
@@ -831,7 +1002,7 @@ impl std::fmt::Display for Model {
             write!(f, "{}\n", x.to_lang(self))?;
         }
         // ----- Interval -----
-        for x in self.intervals.iter() {
+        for x in self.intervals.iter().filter(|x| !x.is_anonymous()) {
             write!(f, "{}\n", x.to_lang(self))?;
         }
         // ----- Declaration -----
@@ -27,6 +27,10 @@ impl Parameter {
         &self.name
     }
 
+    pub fn typ(&self) -> &Type {
+        &self.typ
+    }
+
     pub fn is_same(&self, other: &Parameter) -> bool {
         self.name == other.name && self.typ == other.typ
     }
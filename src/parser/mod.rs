@@ -6,9 +6,13 @@ pub use parser::*;
 
 lalrpop_mod!(grammar, "/parser/grammar.rs");
 
+/// The keywords of the grammar (generated from grammar.lalrpop)
+pub const KEYWORDS: &[&str] = include!(concat!(env!("OUT_DIR"), "/keywords.rs"));
+
 use crate::error::Error;
 use crate::model::Model;
 use line_col::LineColLookup;
+use std::collections::HashMap;
 
 #[derive(Debug)]
 pub struct Identifier {
@@ -25,18 +29,34 @@ impl Identifier {
 }
 
 pub fn parse_file(model: &mut Model, file: &str) -> Result<(), Error> {
+    parse_file_with_sources(model, file, &HashMap::new())
+}
+
+/// Parse a file and its includes.
+/// The content of the files found in 'sources' is used instead of the one on disk.
+pub fn parse_file_with_sources(
+    model: &mut Model,
+    file: &str,
+    sources: &HashMap<String, String>,
+) -> Result<(), Error> {
     let mut parser = Parser::new(model);
     parser.add(file);
+    let mut errors = vec![];
 
     loop {
         match parser.next() {
-            None => return Ok(()),
-            Some(file) => match std::fs::read_to_string(&file) {
+            None => return Error::from_list(errors),
+            Some(file) => match sources
+                .get(&file)
+                .map(|input| Ok(input.clone()))
+                .unwrap_or_else(|| std::fs::read_to_string(&file))
+            {
                 Ok(input) => {
                     let lookup = LineColLookup::new(&input);
-                    match grammar::ModelParser::new().parse(&lookup, &mut parser, &input) {
-                        Ok(_) => {}
-                        Err(e) => return Err(Error::new_parse(&file, &lookup, e)),
+                    let result = grammar::ModelParser::new().parse(&lookup, &mut parser, &input);
+                    errors.extend(parser.take_errors());
+                    if let Err(e) = result {
+                        errors.push(Error::new_parse(&file, &lookup, e));
                     }
                 }
                 Err(e) => {
@@ -44,9 +64,24 @@ pub fn parse_file(model: &mut Model, file: &str) -> Result<(), Error> {
                         filename: file,
                         message: format!("{:?}", e),
                     };
-                    return Err(e);
+                    errors.push(e);
+                    return Error::from_list(errors);
                 }
             },
         }
     }
 }
+
+/// Parse a single source (its includes are not read).
+pub fn parse_source(model: &mut Model, file: &str, input: &str) -> Result<(), Error> {
+    let mut parser = Parser::new(model);
+    parser.add(file);
+    parser.next();
+    let lookup = LineColLookup::new(input);
+    let result = grammar::ModelParser::new().parse(&lookup, &mut parser, input);
+    let mut errors = parser.take_errors();
+    if let Err(e) = result {
+        errors.push(Error::new_parse(file, &lookup, e));
+    }
+    Error::from_list(errors)
+}
@@ -1,9 +1,15 @@
+use super::Position;
+use crate::error::Error;
 use crate::model::Model;
+use lalrpop_util::lexer::Token;
+use lalrpop_util::ParseError;
+use line_col::LineColLookup;
 
 pub struct Parser<'a> {
     current: Option<String>,
     todo: Vec<String>,
     done: Vec<String>,
+    errors: Vec<Error>,
     pub model: &'a mut Model,
 }
 
@@ -13,6 +19,7 @@ impl<'a> Parser<'a> {
             current: None,
             todo: vec![],
             done: vec![],
+            errors: vec![],
             model,
         }
     }
@@ -33,6 +40,55 @@ impl<'a> Parser<'a> {
         &self.done
     }
 
+    //------------------------- Errors -------------------------
+
+    /// Add a syntax error the parser has recovered from.
+    pub fn add_error(&mut self, lookup: &LineColLookup, error: ParseError<usize, Token, &str>) {
+        let error = Error::new_parse(self.file(), lookup, error);
+        self.errors.push(error);
+    }
+
+    /// The width of a bit-vector type: an error is added if it is out of range.
+    pub fn bit_vector_width(
+        &mut self,
+        lookup: &LineColLookup,
+        offset: usize,
+        width: i64,
+        max: i64,
+    ) -> usize {
+        if (1..=max).contains(&width) {
+            width as usize
+        } else {
+            let error = Error::Resolve {
+                category: format!("bit-vector width (between 1 and {})", max),
+                name: width.to_string(),
+                position: Some(Position::new(self.file(), lookup, offset)),
+            };
+            self.errors.push(error);
+            1
+        }
+    }
+
+    /// The number of solutions to enumerate: an error is added if it is below 1.
+    pub fn solution_number(&mut self, lookup: &LineColLookup, offset: usize, number: i64) -> usize {
+        if number >= 1 {
+            number as usize
+        } else {
+            let error = Error::Resolve {
+                category: "number of solutions (at least 1)".to_string(),
+                name: number.to_string(),
+                position: Some(Position::new(self.file(), lookup, offset)),
+            };
+            self.errors.push(error);
+            1
+        }
+    }
+
+    /// Take the syntax errors of the current file.
+    pub fn take_errors(&mut self) -> Vec<Error> {
+        std::mem::take(&mut self.errors)
+    }
+
     //------------------------- -------------------------
 
     pub fn files(&self) -> Vec<String> {
@@ -68,3 +124,44 @@ impl<'a> Parser<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::tests::load_source;
+
+    fn load_solve(number: &str) -> Result<(), Error> {
+        let source = format!(
+            "var x: 0..3
+            init I {{ x = 0 }}
+            search initial solve {}",
+            number
+        );
+        load_source(&source).map(|_| ())
+    }
+
+    #[test]
+    fn keywords_documented() {
+        let syntax = include_str!("../../docs/syntax.md");
+        let words: Vec<&str> = syntax.split_whitespace().collect();
+        for keyword in crate::parser::KEYWORDS {
+            assert!(words.contains(keyword), "{} is not in the syntax", keyword);
+        }
+    }
+
+    #[test]
+    fn solution_number() {
+        assert!(load_solve("1").is_ok());
+        assert!(load_solve("all").is_ok());
+        for number in ["0", "-2"] {
+            match load_solve(number) {
+                Err(Error::Resolve { name, position, .. }) => {
+                    assert_eq!(name, number);
+                    let position = position.unwrap();
+                    assert_eq!((position.line, position.column), (3, 34));
+                }
+                _ => panic!("solve {} is accepted", number),
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::common::*;
+use crate::error::*;
+use crate::expr::*;
+use crate::model::*;
+
+#[derive(Clone, Debug)]
+pub struct Enumeration {
+    /// maximal number of traces (all if None)
+    pub number: Option<usize>,
+    /// the traces are distinct on these declarations (all if empty)
+    pub projection: Vec<Expr>,
+}
+
+impl Enumeration {
+    pub fn projection_ids(&self) -> Vec<DeclarationId> {
+        self.projection
+            .iter()
+            .filter_map(|e| match e.expression() {
+                Expression::Declaration(id) => Some(*id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    //---------- Resolve Expr ----------
+
+    pub fn resolve_expr(&self, model: &Model, entries: &Vec<Entry>) -> Result<Self, Error> {
+        let mut projection = vec![];
+        for e in self.projection.iter() {
+            projection.push(e.resolve(model, entries)?);
+        }
+        Ok(Self {
+            number: self.number,
+            projection,
+        })
+    }
+
+    //---------- Check Type ----------
+
+    pub fn check_type(&self, model: &Model) -> Result<(), Error> {
+        for e in self.projection.iter() {
+            if !matches!(e.expression(), Expression::Declaration(_)) {
+                return Err(Error::Resolve {
+                    category: "variable or constant".to_string(),
+                    name: e.to_lang(model),
+                    position: e.position().clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+//------------------------- ToLang -------------------------
+
+impl ToLang for Enumeration {
+    fn to_lang(&self, model: &Model) -> String {
+        let mut res = match self.number {
+            Some(n) => format!("solve {}", n),
+            None => "solve all".to_string(),
+        };
+        if !self.projection.is_empty() {
+            let l: Vec<String> = self.projection.iter().map(|e| e.to_lang(model)).collect();
+            res += &format!(" on {}", l.join(", "));
+        }
+        res
+    }
+}
+
+//------------------------- ToDebug -------------------------
+
+impl ToDebug for Enumeration {
+    fn to_debug(&self, model: &Model) -> String {
+        let mut res = match self.number {
+            Some(n) => format!("solve {}", n),
+            None => "solve all".to_string(),
+        };
+        if !self.projection.is_empty() {
+            let l: Vec<String> = self.projection.iter().map(|e| e.to_debug(model)).collect();
+            res += &format!(" on {}", l.join(", "));
+        }
+        res
+    }
+}
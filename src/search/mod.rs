@@ -7,6 +7,12 @@ pub use search_type::*;
 pub mod optimization;
 pub use optimization::*;
 
+pub mod pareto;
+pub use pareto::*;
+
+pub mod enumeration;
+pub use enumeration::*;
+
 pub mod path_type;
 pub use path_type::*;
 
@@ -7,7 +7,8 @@ use crate::model::*;
 pub struct Optimization {
     pub minimize: bool,
     pub objective: Expr,
-    pub bound: Expr,
+    /// without bound, the solver detects the unbounded objectives
+    pub bound: Option<Expr>,
 }
 
 impl Optimization {
@@ -15,7 +16,10 @@ impl Optimization {
 
     pub fn resolve_expr(&self, model: &Model, entries: &Vec<Entry>) -> Result<Self, Error> {
         let o = self.objective.resolve(model, entries)?;
-        let b = self.bound.resolve(model, entries)?;
+        let b = match &self.bound {
+            Some(bound) => Some(bound.resolve(model, entries)?),
+            None => None,
+        };
         Ok(Self {
             minimize: self.minimize,
             objective: o,
@@ -27,9 +31,11 @@ impl Optimization {
 
     pub fn check_type(&self, model: &Model) -> Result<(), Error> {
         self.objective.check_type(model)?;
-        self.bound.check_type(model)?;
         self.objective.check_is_number(model)?;
-        self.bound.check_is_number(model)?;
+        if let Some(bound) = &self.bound {
+            bound.check_type(model)?;
+            bound.check_is_number(model)?;
+        }
         // TODO check bound is Value
         Ok(())
     }
@@ -49,7 +55,7 @@ impl Optimization {
                 expr,
             });
         }
-        if let Some(expr) = self.bound.get_following() {
+        if let Some(expr) = self.bound.as_ref().and_then(|b| b.get_following()) {
             let message = "Following not allowed in 'search'".into();
             let name = "bound".to_string();
             let position = expr.position().clone();
@@ -68,7 +74,7 @@ impl Optimization {
 
     pub fn propagate_expr(&self, model: &Model) -> Self {
         let o = self.objective.propagate(model);
-        let b = self.bound.propagate(model);
+        let b = self.bound.as_ref().map(|b| b.propagate(model));
         Self {
             minimize: self.minimize,
             objective: o,
@@ -81,16 +87,19 @@ impl Optimization {
 
 impl ToLang for Optimization {
     fn to_lang(&self, model: &Model) -> String {
-        format!(
-            "{} {} until {}",
+        let mut res = format!(
+            "{} {}",
             if self.minimize {
                 "minimize"
             } else {
                 "maximize"
             },
-            self.objective.to_lang(model),
-            self.bound.to_lang(model)
-        )
+            self.objective.to_lang(model)
+        );
+        if let Some(bound) = &self.bound {
+            res += &format!(" until {}", bound.to_lang(model));
+        }
+        res
     }
 }
 
@@ -98,15 +107,18 @@ impl ToLang for Optimization {
 
 impl ToDebug for Optimization {
     fn to_debug(&self, model: &Model) -> String {
-        format!(
-            "{} {} until {}",
+        let mut res = format!(
+            "{} {}",
             if self.minimize {
                 "minimize"
             } else {
                 "maximize"
             },
-            self.objective.to_debug(model),
-            self.bound.to_debug(model)
-        )
+            self.objective.to_debug(model)
+        );
+        if let Some(bound) = &self.bound {
+            res += &format!(" until {}", bound.to_debug(model));
+        }
+        res
     }
 }
@@ -0,0 +1,99 @@
+use crate::common::*;
+use crate::error::*;
+use crate::expr::Expr;
+use crate::model::*;
+
+#[derive(Clone, Debug)]
+pub struct Pareto {
+    pub minimize: bool,
+    /// objectives without priority
+    pub objectives: Vec<Expr>,
+}
+
+impl Pareto {
+    //---------- Resolve Expr ----------
+
+    pub fn resolve_expr(&self, model: &Model, entries: &Vec<Entry>) -> Result<Self, Error> {
+        let mut objectives = vec![];
+        for e in self.objectives.iter() {
+            objectives.push(e.resolve(model, entries)?);
+        }
+        Ok(Self {
+            minimize: self.minimize,
+            objectives,
+        })
+    }
+
+    //---------- Check Type ----------
+
+    pub fn check_type(&self, model: &Model) -> Result<(), Error> {
+        for e in self.objectives.iter() {
+            e.check_type(model)?;
+            e.check_is_number(model)?;
+        }
+        Ok(())
+    }
+
+    //---------- Check Time ----------
+
+    pub fn check_time(&self) -> Result<(), Error> {
+        for e in self.objectives.iter() {
+            if let Some(expr) = e.get_following() {
+                let message = "Following not allowed in 'search'".into();
+                let name = "objective".to_string();
+                let position = expr.position().clone();
+                let expr = expr.clone();
+                return Err(Error::Time {
+                    message,
+                    name,
+                    position,
+                    expr,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    //---------- Propagate Expr ----------
+
+    pub fn propagate_expr(&self, model: &Model) -> Self {
+        Self {
+            minimize: self.minimize,
+            objectives: self.objectives.iter().map(|e| e.propagate(model)).collect(),
+        }
+    }
+}
+
+//------------------------- ToLang -------------------------
+
+impl ToLang for Pareto {
+    fn to_lang(&self, model: &Model) -> String {
+        let l: Vec<String> = self.objectives.iter().map(|e| e.to_lang(model)).collect();
+        format!(
+            "pareto {} {}",
+            if self.minimize {
+                "minimize"
+            } else {
+                "maximize"
+            },
+            l.join(", ")
+        )
+    }
+}
+
+//------------------------- ToDebug -------------------------
+
+impl ToDebug for Pareto {
+    fn to_debug(&self, model: &Model) -> String {
+        let l: Vec<String> = self.objectives.iter().map(|e| e.to_debug(model)).collect();
+        format!(
+            "pareto {} {}",
+            if self.minimize {
+                "minimize"
+            } else {
+                "maximize"
+            },
+            l.join(", ")
+        )
+    }
+}
@@ -6,7 +6,11 @@ use crate::model::*;
 #[derive(Clone, Debug)]
 pub enum SearchType {
     Solve,
-    Optimize(Optimization),
+    Enumerate(Enumeration),
+    /// lexicographic: the first objective has the highest priority
+    Optimize(Vec<Optimization>),
+    /// non-dominated traces
+    Pareto(Pareto),
 }
 
 impl SearchType {
@@ -15,9 +19,19 @@ impl SearchType {
     pub fn resolve_expr(&self, model: &Model, entries: &Vec<Entry>) -> Result<Self, Error> {
         match self {
             SearchType::Solve => Ok(self.clone()),
-            SearchType::Optimize(optimization) => {
-                let optimization = optimization.resolve_expr(model, entries)?;
-                Ok(SearchType::Optimize(optimization))
+            SearchType::Enumerate(enumeration) => {
+                let enumeration = enumeration.resolve_expr(model, entries)?;
+                Ok(SearchType::Enumerate(enumeration))
+            }
+            SearchType::Optimize(list) => {
+                let mut optimizations = vec![];
+                for optimization in list.iter() {
+                    optimizations.push(optimization.resolve_expr(model, entries)?);
+                }
+                Ok(SearchType::Optimize(optimizations))
+            }
+            SearchType::Pareto(pareto) => {
+                Ok(SearchType::Pareto(pareto.resolve_expr(model, entries)?))
             }
         }
     }
@@ -25,14 +39,31 @@ impl SearchType {
     pub fn is_optimization(&self) -> bool {
         match self {
             SearchType::Solve => false,
+            SearchType::Enumerate(_) => false,
             SearchType::Optimize(_) => true,
+            SearchType::Pareto(_) => false,
+        }
+    }
+
+    pub fn enumeration(&self) -> Option<&Enumeration> {
+        match self {
+            SearchType::Enumerate(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    pub fn pareto(&self) -> Option<&Pareto> {
+        match self {
+            SearchType::Pareto(p) => Some(p),
+            _ => None,
         }
     }
 
-    pub fn optimization(&self) -> Option<&Optimization> {
+    /// The objectives, by priority (empty without optimization)
+    pub fn optimizations(&self) -> &[Optimization] {
         match self {
-            SearchType::Solve => None,
-            SearchType::Optimize(o) => Some(o),
+            SearchType::Optimize(list) => list,
+            _ => &[],
         }
     }
 
@@ -41,7 +72,14 @@ impl SearchType {
     pub fn check_type(&self, model: &Model) -> Result<(), Error> {
         match self {
             SearchType::Solve => Ok(()),
-            SearchType::Optimize(opt) => opt.check_type(model),
+            SearchType::Enumerate(e) => e.check_type(model),
+            SearchType::Pareto(p) => p.check_type(model),
+            SearchType::Optimize(list) => {
+                for opt in list.iter() {
+                    opt.check_type(model)?;
+                }
+                Ok(())
+            }
         }
     }
 
@@ -49,8 +87,14 @@ impl SearchType {
 
     pub fn check_time(&self) -> Result<(), Error> {
         match self {
-            SearchType::Solve => Ok(()),
-            SearchType::Optimize(opt) => opt.check_time(),
+            SearchType::Solve | SearchType::Enumerate(_) => Ok(()),
+            SearchType::Pareto(p) => p.check_time(),
+            SearchType::Optimize(list) => {
+                for opt in list.iter() {
+                    opt.check_time()?;
+                }
+                Ok(())
+            }
         }
     }
 
@@ -58,8 +102,11 @@ impl SearchType {
 
     pub fn propagate_expr(&self, model: &Model) -> Self {
         match self {
-            SearchType::Solve => self.clone(),
-            SearchType::Optimize(opt) => Self::Optimize(opt.propagate_expr(model)),
+            SearchType::Solve | SearchType::Enumerate(_) => self.clone(),
+            SearchType::Pareto(p) => Self::Pareto(p.propagate_expr(model)),
+            SearchType::Optimize(list) => {
+                Self::Optimize(list.iter().map(|opt| opt.propagate_expr(model)).collect())
+            }
         }
     }
 }
@@ -70,7 +117,12 @@ impl ToLang for SearchType {
     fn to_lang(&self, model: &Model) -> String {
         match self {
             SearchType::Solve => "solve".to_string(),
-            SearchType::Optimize(opt) => opt.to_lang(model),
+            SearchType::Enumerate(e) => e.to_lang(model),
+            SearchType::Pareto(p) => p.to_lang(model),
+            SearchType::Optimize(list) => {
+                let l: Vec<String> = list.iter().map(|opt| opt.to_lang(model)).collect();
+                l.join(" then ")
+            }
         }
     }
 }
@@ -81,7 +133,12 @@ impl ToDebug for SearchType {
     fn to_debug(&self, model: &Model) -> String {
         match self {
             SearchType::Solve => "solve".to_string(),
-            SearchType::Optimize(opt) => opt.to_debug(model),
+            SearchType::Enumerate(e) => e.to_debug(model),
+            SearchType::Pareto(p) => p.to_debug(model),
+            SearchType::Optimize(list) => {
+                let l: Vec<String> = list.iter().map(|opt| opt.to_debug(model)).collect();
+                l.join(" then ")
+            }
         }
     }
 }
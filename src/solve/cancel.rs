@@ -0,0 +1,60 @@
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Cancellation shared by the clones of the arguments.
+///
+/// Once cancelled, the strategies stop before their next solver call and
+/// the solvers running a check are killed (their check answers unknown).
+#[derive(Clone, Debug, Default)]
+pub struct Cancel {
+    cancelled: Arc<AtomicBool>,
+    /// process ids of the solvers running a check
+    running: Arc<Mutex<Vec<u32>>>,
+}
+
+impl Cancel {
+    pub fn cancel(&self) {
+        let running = self.running.lock().unwrap();
+        self.cancelled.store(true, Ordering::Relaxed);
+        for pid in running.iter() {
+            let _ = Command::new("kill").arg(pid.to_string()).status();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Register a solver starting a check, false if already cancelled
+    pub fn start(&self, pid: u32) -> bool {
+        let mut running = self.running.lock().unwrap();
+        if self.is_cancelled() {
+            false
+        } else {
+            running.push(pid);
+            true
+        }
+    }
+
+    /// Unregister a solver at the end of its check
+    pub fn end(&self, pid: u32) {
+        self.running.lock().unwrap().retain(|p| *p != pid);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_running() {
+        let mut child = Command::new("sleep").arg("60").spawn().unwrap();
+        let cancel = Cancel::default();
+        assert!(cancel.start(child.id()));
+        cancel.clone().cancel();
+        assert!(!child.wait().unwrap().success());
+        assert!(cancel.is_cancelled());
+        assert!(!cancel.start(child.id()));
+    }
+}
@@ -0,0 +1,40 @@
+use super::*;
+use crate::common::*;
+use crate::model::*;
+
+/// A field quoted if it contains a comma, a quote or a line break (RFC 4180)
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+//------------------------- To CSV -------------------------
+
+impl Solution {
+    /// One row per state and one column per variable/definition.
+    /// The `loop` column is `true` on the state the last state loops to.
+    pub fn to_csv(&self, model: &Model) -> String {
+        let traces = self.traces(model);
+
+        let mut res = "state,loop".to_string();
+        for (name, _, _) in traces.iter() {
+            res += &format!(",{}", csv_field(name));
+        }
+        res += "\n";
+
+        for state in 0..self.states {
+            res += &format!("{},{}", state, self.loop_index == Some(state));
+            for (_, _, values) in traces.iter() {
+                match &values[state] {
+                    Some(value) => res += &format!(",{}", csv_field(&value.to_lang(model))),
+                    None => res += ",",
+                }
+            }
+            res += "\n";
+        }
+        res
+    }
+}
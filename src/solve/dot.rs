@@ -0,0 +1,45 @@
+use super::*;
+use crate::common::*;
+use crate::model::*;
+
+//------------------------- To Dot -------------------------
+
+impl Solution {
+    /// Graphviz lasso of the trace: one node per state, labelled with the
+    /// values that changed, and a loop-back edge to `loop_index`.
+    pub fn to_dot(&self, model: &Model) -> String {
+        let traces = self.traces(model);
+
+        let mut res = "digraph trace {\n".to_string();
+        res += "    rankdir=LR;\n";
+        res += "    node [shape=box, fontname=\"monospace\"];\n";
+
+        let mut previous: Vec<Option<String>> = vec![None; traces.len()];
+        for state in 0..self.states {
+            let mut label = format!("State {}\\l", state);
+            for (i, (name, _, values)) in traces.iter().enumerate() {
+                if let Some(value) = &values[state] {
+                    let line = format!("{} = {}\\l", name, value.to_lang(model));
+                    if previous[i].as_ref() != Some(&line) {
+                        label += &line;
+                        previous[i] = Some(line);
+                    }
+                }
+            }
+            res += &format!("    s{} [label=\"{}\"];\n", state, label);
+        }
+        for state in 1..self.states {
+            res += &format!("    s{} -> s{};\n", state - 1, state);
+        }
+        if let Some(index) = self.loop_index {
+            res += &format!(
+                "    s{} -> s{} [style=dashed, label=\"loop\"];\n",
+                self.states - 1,
+                index
+            );
+        }
+
+        res += "}\n";
+        res
+    }
+}
@@ -0,0 +1,268 @@
+use super::*;
+use crate::model::*;
+use crate::search::*;
+use crate::Args;
+use smt_sb::SatResult;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum Semantic {
+    Truncated,
+    Infinite,
+    Finite,
+}
+
+impl Semantic {
+    /// The semantics of a path type with k transitions
+    pub(super) fn list(
+        infinite: bool,
+        truncated: bool,
+        finite: bool,
+        transitions: usize,
+    ) -> Vec<Semantic> {
+        let mut res = vec![];
+        if truncated {
+            res.push(Semantic::Truncated);
+        }
+        if infinite && transitions > 0 {
+            res.push(Semantic::Infinite);
+        }
+        if finite {
+            res.push(Semantic::Finite);
+        }
+        res
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Semantic::Truncated => "truncated",
+            Semantic::Infinite => "infinite",
+            Semantic::Finite => "finite",
+        }
+    }
+}
+
+/// Is the finite candidate really finite (no following state)
+fn is_finite(model: &Model, args: &Args, transitions: usize, solution: &Solution) -> SatResult {
+    let mut solver = Solver::new(
+        model,
+        args,
+        log_file(args.log_folder.clone(), "is_finite", transitions),
+    );
+    solver.create_finite_future(transitions + 1, solution);
+    let result = solver.check();
+    solver.exit();
+    result
+}
+
+/// One trace of the semantic with k transitions and the additional constraints
+/// (Solution, NoSolution or Unknown, Unbounded if an optimized objective is infinite).
+/// For the finite semantic, the candidates with a following state are discarded.
+pub(super) fn find_solution(
+    model: &Model,
+    args: &Args,
+    semantic: Semantic,
+    transitions: usize,
+    constrain: &dyn Fn(&mut Solver),
+) -> Response {
+    // finite candidates that have a following state
+    let mut not_finite: Vec<Solution> = vec![];
+    loop {
+        let mut solver = Solver::new(
+            model,
+            args,
+            log_file_n(
+                args.log_folder.clone(),
+                semantic.name(),
+                transitions,
+                not_finite.len(),
+            ),
+        );
+        solver
+            .add_comment(&format!("search {} k={}", semantic.name(), transitions))
+            .unwrap();
+        match semantic {
+            Semantic::Truncated => solver.create_truncated(transitions),
+            Semantic::Infinite => solver.create_infinite(transitions),
+            Semantic::Finite => solver.create_finite(transitions, &not_finite),
+        }
+        constrain(&mut solver);
+
+        let result = solver.check();
+        match result {
+            SatResult::Unknown => {
+                solver.exit();
+                return Response::Unknown;
+            }
+            SatResult::Unsat => {
+                solver.exit();
+                return Response::NoSolution(transitions, vec![]);
+            }
+            SatResult::Sat => {
+                let solution = Solution::from_solver(&mut solver, semantic == Semantic::Finite);
+                let unbounded = solver.unbounded_objective();
+                solver.exit();
+                if semantic != Semantic::Finite {
+                    return solution_response(unbounded, solution);
+                }
+                match is_finite(model, args, transitions, &solution) {
+                    SatResult::Unknown => return Response::Unknown,
+                    SatResult::Unsat => return solution_response(unbounded, solution),
+                    SatResult::Sat => not_finite.push(solution),
+                }
+            }
+        }
+    }
+}
+
+/// Enumeration in progress
+struct Enumerator<'a> {
+    model: &'a Model,
+    args: &'a Args,
+    number: Option<usize>,
+    projection: Vec<DeclarationId>,
+    solutions: Vec<Solution>,
+}
+
+impl<'a> Enumerator<'a> {
+    fn is_full(&self) -> bool {
+        match self.number {
+            Some(n) => self.solutions.len() >= n,
+            None => false,
+        }
+    }
+
+    fn response(self, otherwise: Response) -> Response {
+        if self.solutions.is_empty() {
+            otherwise
+        } else {
+            Response::Solutions(self.solutions)
+        }
+    }
+
+    /// Block the solutions already found with the same number of states
+    fn remove_solutions(&self, solver: &mut Solver) {
+        for solution in self.solutions.iter() {
+            if solution.states == solver.states() {
+                solver.remove_projected_solution(solution, &self.projection);
+            }
+        }
+    }
+
+    /// Add the distinct solutions of a semantic with k transitions.
+    /// Return false if the solver answers unknown.
+    fn enumerate(&mut self, semantic: Semantic, transitions: usize) -> bool {
+        while !self.is_full() {
+            let response = find_solution(self.model, self.args, semantic, transitions, &|solver| {
+                self.remove_solutions(solver)
+            });
+            match response {
+                Response::Solution(solution) => self.solutions.push(solution),
+                Response::NoSolution(_, _) => return true,
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+/// 'solve all' or 'solve N': distinct traces (on the projection), by increasing number of transitions.
+/// Without maximal number of transitions, 'solve all' stops at the first number with solutions.
+pub fn resolve_enumerate(
+    model: &Model,
+    _pretty: &mut d_stuff::Pretty,
+    args: &Args,
+    enumeration: &Enumeration,
+) -> Response {
+    let mut enumerator = Enumerator {
+        model,
+        args,
+        number: enumeration.number,
+        projection: enumeration.projection_ids(),
+        solutions: vec![],
+    };
+
+    let (tn, infinite, truncated, finite, complete) = match model.search().path_type() {
+        PathType::Initial => {
+            if !enumerator.enumerate(Semantic::Truncated, 0) {
+                return enumerator.response(Response::Unknown);
+            }
+            return enumerator.response(Response::NoSolution(0, vec![]));
+        }
+        PathType::Path {
+            infinite,
+            truncated,
+            finite,
+            complete,
+        } => (
+            model.search().transitions(),
+            infinite,
+            truncated,
+            finite,
+            complete,
+        ),
+    };
+
+    let mut transitions = tn.min();
+    loop {
+        if args.verbose > 2 {
+            println!(
+                "========================= {} transition =========================",
+                transitions
+            );
+        }
+
+        // -------------------- Bound Reached --------------------
+        if let Some(max) = tn.max() {
+            if transitions > max {
+                return enumerator.response(Response::BoundReached);
+            }
+        }
+
+        // -------------------- Traces --------------------
+        for semantic in Semantic::list(infinite, truncated, finite, transitions) {
+            if !enumerator.enumerate(semantic, transitions) {
+                return enumerator.response(Response::Unknown);
+            }
+        }
+
+        let last = enumerator.is_full() || (enumerator.number.is_none() && tn.max().is_none());
+        if last && !enumerator.solutions.is_empty() {
+            return Response::Solutions(enumerator.solutions);
+        }
+
+        // -------------------- Complete/Future --------------------
+        if complete {
+            let mut solver = Solver::new(
+                model,
+                args,
+                log_file(args.log_folder.clone(), "complete", transitions),
+            );
+            solver
+                .add_comment(&format!("enumerate future + unicity k={}", transitions))
+                .unwrap();
+            solver.create_future(transitions);
+            let result = solver.check();
+            match result {
+                SatResult::Unknown => {
+                    solver.exit();
+                    return enumerator.response(Response::Unknown);
+                }
+                SatResult::Unsat => {
+                    solver.exit();
+                    let core = recheck_unsat_core(
+                        model,
+                        args,
+                        log_file(args.log_folder.clone(), "core", transitions),
+                        |solver| solver.create_future(transitions),
+                    );
+                    return enumerator.response(Response::NoSolution(transitions, core));
+                }
+                SatResult::Sat => {
+                    solver.exit();
+                }
+            }
+        }
+
+        transitions += 1;
+    }
+}
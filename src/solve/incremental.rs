@@ -20,6 +20,7 @@ pub fn resolve_incremental(
 
     let mut solver = Solver::new(
         model,
+        args,
         log_file(args.log_folder.clone(), "incremanetal", transitions),
     );
     solver.create_path(transitions);
@@ -27,6 +28,10 @@ pub fn resolve_incremental(
     solver.push();
 
     loop {
+        if args.is_cancelled() {
+            solver.exit();
+            return Response::Unknown;
+        }
         #[cfg(debug_assertions)]
         {
             println!(
@@ -108,6 +113,10 @@ pub fn resolve_incremental(
             let mut solutions: Vec<Solution> = Vec::new();
 
             loop {
+                if args.is_cancelled() {
+                    solver.exit();
+                    return Response::Unknown;
+                }
                 solver
                     .add_comment(&format!("incremental finite k={}", transitions))
                     .unwrap();
@@ -192,7 +201,13 @@ pub fn resolve_incremental(
                 }
                 SatResult::Unsat => {
                     solver.exit();
-                    return Response::NoSolution(transitions);
+                    let core = recheck_unsat_core(
+                        model,
+                        args,
+                        log_file(args.log_folder.clone(), "core", transitions),
+                        |solver| solver.create_future(transitions),
+                    );
+                    return Response::NoSolution(transitions, core);
                 }
                 SatResult::Sat => {
                     solver.pop();
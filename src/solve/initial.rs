@@ -14,7 +14,7 @@ pub fn resolve_initial(model: &Model, _pretty: &mut d_stuff::Pretty, args: &Args
 
     let start_time = Instant::now();
 
-    let mut solver = Solver::new(model, log_file);
+    let mut solver = Solver::new(model, args, log_file);
     solver.create_truncated(0);
     let finish_time = Instant::now();
 
@@ -34,7 +34,13 @@ pub fn resolve_initial(model: &Model, _pretty: &mut d_stuff::Pretty, args: &Args
         }
         SatResult::Unsat => {
             solver.exit();
-            return Response::NoSolution(0);
+            let log_file = args
+                .log_folder
+                .as_ref()
+                .map(|folder| format!("{}/core_0.smt", folder));
+            let core =
+                recheck_unsat_core(model, args, log_file, |solver| solver.create_truncated(0));
+            return Response::NoSolution(0, core);
         }
         SatResult::Sat => {
             let solution = Solution::from_solver(&mut solver, false);
@@ -17,11 +17,11 @@ pub fn resolve_initial_optimize(
     let mut best_solution: Option<Solution> = None;
 
     loop {
-        let mut solver = Solver::new(model, log_file.clone());
+        let mut solver = Solver::new(model, args, log_file.clone());
         solver.create_truncated(0);
         solver.add_optimization();
         if let Some(solution) = &best_solution {
-            solver.add_best_objective_constraint(solution.objective.as_ref().unwrap());
+            solver.add_best_objective_constraint(&solution.objectives);
         }
 
         let result = solver.check();
@@ -35,11 +35,24 @@ pub fn resolve_initial_optimize(
                 solver.exit();
                 match &best_solution {
                     Some(solution) => return Response::Solution(solution.clone()),
-                    None => return Response::NoSolution(0),
+                    None => {
+                        let log_file = args
+                            .log_folder
+                            .as_ref()
+                            .map(|folder| format!("{}/core_0.smt", folder));
+                        let core = recheck_unsat_core(model, args, log_file, |solver| {
+                            solver.create_truncated(0)
+                        });
+                        return Response::NoSolution(0, core);
+                    }
                 }
             }
             SatResult::Sat => {
                 let solution = Solution::from_solver(&mut solver, false);
+                if let Some(index) = solver.unbounded_objective() {
+                    solver.exit();
+                    return Response::Unbounded(index, solution);
+                }
                 solver.exit();
                 best_solution = Some(solution);
             }
@@ -0,0 +1,166 @@
+use super::*;
+use crate::common::*;
+use crate::expr::*;
+use crate::model::*;
+use serde_json::{json, Map, Value};
+
+/// Version of the JSON output schema (see docs/json.md).
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+//------------------------- Value -------------------------
+
+fn value_to_json(model: &Model, value: &Option<Expr>) -> Value {
+    match value {
+        Some(expr) => match expr.expression() {
+            Expression::Bool(value) => json!(value),
+            Expression::Int(value) => json!(value),
+            Expression::EnumerateElement(id) => json!(model.get(*id).unwrap().name()),
+            Expression::SetValue(list) => Value::Array(
+                list.iter()
+                    .map(|e| value_to_json(model, &Some(e.clone())))
+                    .collect(),
+            ),
+            Expression::ArrayValue(list) => {
+                let mut map = Map::new();
+                for (i, v) in list.iter() {
+                    map.insert(i.to_lang(model), value_to_json(model, &Some(v.clone())));
+                }
+                Value::Object(map)
+            }
+            // Real (and any other value) are given as string to keep exact values
+            _ => json!(expr.to_lang(model)),
+        },
+        None => Value::Null,
+    }
+}
+
+//------------------------- Solution -------------------------
+
+impl Solution {
+    pub fn to_json(&self, model: &Model) -> Value {
+        // Constantes
+        let mut constants = Map::new();
+        for (id, value) in self.cst_dec.iter() {
+            if let Some(dec) = model.get(*id) {
+                constants.insert(dec.name().to_string(), value_to_json(model, value));
+            }
+        }
+
+        // States
+        let mut states = vec![];
+        for state in 0..self.states {
+            let mut variables = Map::new();
+            for (id, v) in self.var_dec.iter() {
+                if let Some(dec) = model.get(*id) {
+                    variables.insert(dec.name().to_string(), value_to_json(model, &v[state]));
+                }
+            }
+            let mut definitions = Map::new();
+            for (id, v) in self.var_def.iter() {
+                if let Some(def) = model.get(*id) {
+                    definitions.insert(def.name().to_string(), value_to_json(model, &v[state]));
+                }
+            }
+            states.push(json!({
+                "index": state,
+                "variables": variables,
+                "definitions": definitions,
+            }));
+        }
+
+        json!({
+            "loop_index": self.loop_index,
+            "objective": value_to_json(model, &self.objectives.first().cloned()),
+            "objectives": self
+                .objectives
+                .iter()
+                .map(|x| value_to_json(model, &Some(x.clone())))
+                .collect::<Vec<_>>(),
+            "constants": constants,
+            "states": states,
+        })
+    }
+}
+
+//------------------------- Response -------------------------
+
+impl Response {
+    pub fn kind(&self) -> &str {
+        match self {
+            Response::NoSolution(_, _) => "no_solution",
+            Response::Unknown => "unknown",
+            Response::BoundReached => "bound_reached",
+            Response::Solution(_) => "solution",
+            Response::BestSolution(_) => "best_solution",
+            Response::Solutions(_) => "solutions",
+            Response::Unbounded(_, _) => "unbounded",
+        }
+    }
+
+    pub fn to_json(&self, model: &Model) -> Value {
+        let mut res = Map::new();
+        res.insert("response".into(), json!(self.kind()));
+        match self {
+            Response::NoSolution(k, core) => {
+                res.insert("k".into(), json!(k));
+                res.insert("solution".into(), Value::Null);
+                let core: Vec<Value> = core
+                    .iter()
+                    .map(|item| {
+                        let (name, position) = item.naming(model);
+                        json!({
+                            "name": name,
+                            "position": position.map(|p| p.to_string()),
+                        })
+                    })
+                    .collect();
+                res.insert("core".into(), json!(core));
+            }
+            Response::Unknown | Response::BoundReached => {
+                res.insert("k".into(), Value::Null);
+                res.insert("solution".into(), Value::Null);
+            }
+            Response::Solution(solution) | Response::BestSolution(solution) => {
+                res.insert("k".into(), json!(solution.states - 1));
+                res.insert("solution".into(), solution.to_json(model));
+            }
+            Response::Solutions(solutions) => {
+                let k = solutions.first().map(|s| s.states - 1);
+                res.insert("k".into(), json!(k));
+                res.insert(
+                    "solution".into(),
+                    solutions
+                        .first()
+                        .map(|s| s.to_json(model))
+                        .unwrap_or(Value::Null),
+                );
+                let list: Vec<Value> = solutions.iter().map(|s| s.to_json(model)).collect();
+                res.insert("solutions".into(), json!(list));
+            }
+            Response::Unbounded(index, solution) => {
+                res.insert("k".into(), json!(solution.states - 1));
+                res.insert("solution".into(), solution.to_json(model));
+                res.insert("unbounded".into(), json!(index));
+            }
+        }
+        Value::Object(res)
+    }
+}
+
+//------------------------- Vacuity -------------------------
+
+pub fn vacuity_to_json(response: &Response, results: &[VacuityResult]) -> Value {
+    let list: Vec<Value> = results
+        .iter()
+        .map(|result| {
+            json!({
+                "atom": result.atom,
+                "position": result.position.as_ref().map(|p| p.to_string()),
+                "with_true": result.with_true.kind(),
+                "with_false": result.with_false.kind(),
+                "vacuous": result.is_vacuous(response),
+            })
+        })
+        .collect();
+    json!(list)
+}
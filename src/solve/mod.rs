@@ -4,6 +4,15 @@ pub use response::*;
 pub mod solution;
 pub use solution::*;
 
+pub mod vcd;
+
+pub mod csv;
+
+pub mod dot;
+
+pub mod json;
+pub use json::*;
+
 pub mod resolve;
 pub use resolve::*;
 
@@ -28,9 +37,33 @@ pub use parallel_optimize::*;
 pub mod incremental;
 pub use incremental::*;
 
+pub mod sweep;
+pub use sweep::*;
+
+pub mod portfolio;
+pub use portfolio::*;
+
+pub mod enumerate;
+pub use enumerate::*;
+
+pub mod pareto;
+pub use pareto::*;
+
+pub mod vacuity;
+pub use vacuity::*;
+
+pub mod unsat_core;
+pub use unsat_core::*;
+
+pub mod cancel;
+pub use cancel::*;
+
 pub mod solver;
 pub use solver::*;
 
+pub mod sexpr;
+pub use sexpr::*;
+
 #[derive(Clone, Copy, Debug)]
 pub enum ExecuteRequest {
     Truncated(usize),
@@ -60,6 +93,27 @@ fn bound_reached(tn: crate::search::TransitionNumber, transitions: usize) -> boo
     return false;
 }
 
+/// A cancelled job answers Unknown without solving (the receiver may be gone)
+fn cancelled(args: &Args, tx: &Sender<ExecuteResponse>, request: ExecuteRequest) -> bool {
+    if args.is_cancelled() {
+        let _ = tx.send(ExecuteResponse {
+            request,
+            response: Response::Unknown,
+        });
+        true
+    } else {
+        false
+    }
+}
+
+/// The solution, or the unbounded objective of an optimization
+fn solution_response(unbounded: Option<usize>, solution: Solution) -> Response {
+    match unbounded {
+        Some(index) => Response::Unbounded(index, solution),
+        None => Response::Solution(solution),
+    }
+}
+
 fn execute_complete(
     model: &Model,
     transitions: usize,
@@ -70,9 +124,13 @@ fn execute_complete(
     let tx = tx.clone();
     let model = model.clone();
     let file = log_file(args.log_folder.clone(), "complete", transitions);
+    let args = args.clone();
 
     pool.execute(move || {
-        let mut solver = Solver::new(&model, file);
+        if cancelled(&args, &tx, ExecuteRequest::Complete(transitions)) {
+            return;
+        }
+        let mut solver = Solver::new(&model, &args, file);
         solver
             .add_comment(&format!("resolve_perf future + unicity k={}", transitions))
             .unwrap();
@@ -91,9 +149,15 @@ fn execute_complete(
             }
             SatResult::Unsat => {
                 solver.exit();
+                let core = recheck_unsat_core(
+                    &model,
+                    &args,
+                    log_file(args.log_folder.clone(), "core", transitions),
+                    |solver| solver.create_future(transitions),
+                );
                 tx.send(ExecuteResponse {
                     request: ExecuteRequest::Complete(transitions),
-                    response: Response::NoSolution(transitions),
+                    response: Response::NoSolution(transitions, core),
                 })
                 .unwrap();
             }
@@ -119,14 +183,19 @@ fn execute_truncated(
     let tx = tx.clone();
     let model = model.clone();
     let file = log_file(args.log_folder.clone(), "truncated", transitions);
+    let args = args.clone();
 
     pool.execute(move || {
-        let mut solver = Solver::new(&model, file);
+        if cancelled(&args, &tx, ExecuteRequest::Truncated(transitions)) {
+            return;
+        }
+        let mut solver = Solver::new(&model, &args, file);
 
         solver
             .add_comment(&format!("resolve_perf truncated k={}", transitions))
             .unwrap();
         solver.create_truncated(transitions);
+        solver.add_optimization();
 
         let result = solver.check();
 
@@ -149,10 +218,11 @@ fn execute_truncated(
             }
             SatResult::Sat => {
                 let solution = Solution::from_solver(&mut solver, false);
+                let response = solution_response(solver.unbounded_objective(), solution);
                 solver.exit();
                 tx.send(ExecuteResponse {
                     request: ExecuteRequest::Truncated(transitions),
-                    response: Response::Solution(solution),
+                    response,
                 })
                 .unwrap();
             }
@@ -170,13 +240,18 @@ fn execute_infinite(
     let tx = tx.clone();
     let model = model.clone();
     let file = log_file(args.log_folder.clone(), "infinite", transitions);
+    let args = args.clone();
 
     pool.execute(move || {
-        let mut solver = Solver::new(&model, file);
+        if cancelled(&args, &tx, ExecuteRequest::Infinite(transitions)) {
+            return;
+        }
+        let mut solver = Solver::new(&model, &args, file);
         solver
             .add_comment(&format!("resolve_perf infinte k={}", transitions))
             .unwrap();
         solver.create_infinite(transitions);
+        solver.add_optimization();
 
         let result = solver.check();
 
@@ -199,10 +274,11 @@ fn execute_infinite(
             }
             SatResult::Sat => {
                 let solution = Solution::from_solver(&mut solver, false);
+                let response = solution_response(solver.unbounded_objective(), solution);
                 solver.exit();
                 tx.send(ExecuteResponse {
                     request: ExecuteRequest::Infinite(transitions),
-                    response: Response::Solution(solution),
+                    response,
                 })
                 .unwrap();
             }
@@ -221,12 +297,17 @@ fn execute_finite(
     let model = model.clone();
     let log_folder = args.log_folder.clone();
     // let file = log_file(log_folder, "finite", transitions);
+    let args = args.clone();
 
     pool.execute(move || {
         loop {
+            if cancelled(&args, &tx, ExecuteRequest::Finite(transitions)) {
+                return;
+            }
             let mut solutions: Vec<Solution> = Vec::new();
             let mut solver = Solver::new(
                 &model,
+                &args,
                 log_file_n(log_folder.clone(), "finite", transitions, solutions.len()),
             );
 
@@ -242,6 +323,7 @@ fn execute_finite(
                     .unwrap();
             }
             solver.create_finite(transitions, &solutions);
+            solver.add_optimization();
             let result = solver.check();
 
             match result {
@@ -265,11 +347,13 @@ fn execute_finite(
                 }
                 SatResult::Sat => {
                     let solution = Solution::from_solver(&mut solver, true);
+                    let unbounded = solver.unbounded_objective();
                     solver.exit();
 
                     // Check if is_finite
                     let mut solver = Solver::new(
                         &model,
+                        &args,
                         log_file_n(
                             log_folder.clone(),
                             "is_finite",
@@ -311,7 +395,7 @@ fn execute_finite(
                             solver.exit();
                             tx.send(ExecuteResponse {
                                 request: ExecuteRequest::Infinite(transitions),
-                                response: Response::Solution(solution),
+                                response: solution_response(unbounded, solution),
                             })
                             .unwrap();
                             break;
@@ -35,6 +35,9 @@ pub fn resolve_parallel(
     }
 
     loop {
+        if args.is_cancelled() {
+            return Response::Unknown;
+        }
         // -------------------- Bound Reached --------------------
         if bound_reached(tn, transitions) {
             if running == 0 {
@@ -132,7 +135,7 @@ pub fn resolve_parallel(
         running -= 1;
 
         match msg.response {
-            Response::NoSolution(_) => return msg.response,
+            Response::NoSolution(_, _) => return msg.response,
             Response::Unknown => {
                 #[cfg(debug_assertions)]
                 {
@@ -173,6 +176,9 @@ pub fn resolve_parallel_complete(
     let mut running = 0;
 
     loop {
+        if args.is_cancelled() {
+            return Response::Unknown;
+        }
         // -------------------- Bound Reached --------------------
         if bound_reached(tn, transitions) {
             if running == 0 {
@@ -244,7 +250,7 @@ pub fn resolve_parallel_complete(
         running -= 1;
 
         match msg.response {
-            Response::NoSolution(_) => return msg.response,
+            Response::NoSolution(_, _) => return msg.response,
             Response::Unknown => {
                 #[cfg(debug_assertions)]
                 {
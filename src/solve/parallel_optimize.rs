@@ -38,12 +38,15 @@ pub fn resolve_parallel_optimize(
     }
 
     loop {
+        if args.is_cancelled() {
+            return Response::Unknown;
+        }
         // -------------------- Bound Reached --------------------
         if bound_reached(tn, transitions) {
             if running == 0 {
                 match &best_solution {
                     Some(solution) => return Response::BestSolution(solution.clone()),
-                    None => return Response::NoSolution(transitions),
+                    None => return Response::NoSolution(transitions, vec![]),
                 }
             }
         } else {
@@ -138,7 +141,7 @@ pub fn resolve_parallel_optimize(
         running -= 1;
 
         match msg.response {
-            Response::NoSolution(_) => match &best_solution {
+            Response::NoSolution(_, _) => match &best_solution {
                 Some(solution) => return Response::BestSolution(solution.clone()),
                 None => return Response::BoundReached,
             },
@@ -167,36 +170,20 @@ pub fn resolve_parallel_optimize(
             },
             Response::Solution(solution) => match &best_solution {
                 Some(best) => {
-                    if model
-                        .search()
-                        .search_type()
-                        .optimization()
-                        .unwrap()
-                        .minimize
-                    {
-                        if solution.compare_objective(best) == Some(Less) {
-                            if args.verbose > 0 {
-                                best_solution = Some(solution.clone());
-                                pretty.add(Response::Solution(solution).to_entry(&model));
-                                pretty.print();
-                            } else {
-                                best_solution = Some(solution);
-                            }
-                        }
-                    } else {
-                        if solution.compare_objective(best) == Some(Greater) {
-                            if args.verbose > 0 {
-                                best_solution = Some(solution.clone());
-                                pretty.add(Response::Solution(solution).to_entry(&model));
-                                pretty.print();
-                            } else {
-                                best_solution = Some(solution);
-                            }
+                    let optimizations = model.search().search_type().optimizations();
+                    if solution.compare_objectives(best, optimizations) == Some(Less) {
+                        if args.verbose > 0 {
+                            best_solution = Some(solution.clone());
+                            pretty.add(Response::Solution(solution).to_entry(&model));
+                            pretty.print();
+                        } else {
+                            best_solution = Some(solution);
                         }
                     }
                 }
                 None => best_solution = Some(solution),
             },
+            Response::Unbounded(_, _) => return msg.response,
             _ => panic!("TODO"),
         }
     }
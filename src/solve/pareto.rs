@@ -0,0 +1,174 @@
+use super::enumerate::{find_solution, Semantic};
+use super::*;
+use crate::model::*;
+use crate::search::*;
+use crate::Args;
+use smt_sb::SatResult;
+
+/// Pareto front in progress
+struct ParetoFront<'a> {
+    model: &'a Model,
+    args: &'a Args,
+    minimize: bool,
+    front: Vec<Solution>,
+}
+
+impl<'a> ParetoFront<'a> {
+    fn response(self, otherwise: Response) -> Response {
+        match otherwise {
+            Response::Unbounded(_, _) => otherwise,
+            _ if self.front.is_empty() => otherwise,
+            _ => Response::Solutions(self.front),
+        }
+    }
+
+    /// An optimal trace that dominates the candidate (Unbounded if an objective is infinite)
+    fn improve(&self, semantic: Semantic, transitions: usize, candidate: &Solution) -> Response {
+        find_solution(self.model, self.args, semantic, transitions, &|solver| {
+            solver.add_pareto_objectives();
+            solver.add_dominate_constraint(&candidate.objectives);
+            solver.add_pareto_optimization();
+        })
+    }
+
+    /// Add the non-dominated traces of a semantic with k transitions.
+    /// Stop with Unknown, or Unbounded if an objective is infinite.
+    fn explore(&mut self, semantic: Semantic, transitions: usize) -> Result<(), Response> {
+        loop {
+            // a trace not dominated by the front
+            let response = find_solution(self.model, self.args, semantic, transitions, &|solver| {
+                solver.add_pareto_objectives();
+                for solution in self.front.iter() {
+                    solver.add_not_dominated_constraint(&solution.objectives);
+                }
+            });
+            let mut candidate = match response {
+                Response::Solution(solution) => solution,
+                Response::NoSolution(_, _) => return Ok(()),
+                _ => return Err(Response::Unknown),
+            };
+            // improved until no trace dominates it
+            loop {
+                match self.improve(semantic, transitions, &candidate) {
+                    Response::Solution(solution) => candidate = solution,
+                    Response::NoSolution(_, _) => break,
+                    Response::Unbounded(index, solution) => {
+                        return Err(Response::Unbounded(index, solution))
+                    }
+                    _ => return Err(Response::Unknown),
+                }
+            }
+            if self.args.verbose > 1 {
+                let l: Vec<String> = candidate
+                    .objectives
+                    .iter()
+                    .map(|x| x.to_lang(self.model))
+                    .collect();
+                println!("pareto point k={}: {}", transitions, l.join(", "));
+            }
+            let minimize = self.minimize;
+            self.front.retain(|s| !candidate.dominates(s, minimize));
+            self.front.push(candidate);
+        }
+    }
+}
+
+/// 'pareto minimize|maximize a, b': the non-dominated traces, by increasing number of transitions.
+/// Without maximal number of transitions, it stops at the first number with traces.
+pub fn resolve_pareto(
+    model: &Model,
+    _pretty: &mut d_stuff::Pretty,
+    args: &Args,
+    pareto: &Pareto,
+) -> Response {
+    let mut front = ParetoFront {
+        model,
+        args,
+        minimize: pareto.minimize,
+        front: vec![],
+    };
+
+    let (tn, infinite, truncated, finite, complete) = match model.search().path_type() {
+        PathType::Initial => {
+            if let Err(response) = front.explore(Semantic::Truncated, 0) {
+                return front.response(response);
+            }
+            return front.response(Response::NoSolution(0, vec![]));
+        }
+        PathType::Path {
+            infinite,
+            truncated,
+            finite,
+            complete,
+        } => (
+            model.search().transitions(),
+            infinite,
+            truncated,
+            finite,
+            complete,
+        ),
+    };
+
+    let mut transitions = tn.min();
+    loop {
+        if args.verbose > 2 {
+            println!(
+                "========================= {} transition =========================",
+                transitions
+            );
+        }
+
+        // -------------------- Bound Reached --------------------
+        if let Some(max) = tn.max() {
+            if transitions > max {
+                return front.response(Response::BoundReached);
+            }
+        }
+
+        // -------------------- Traces --------------------
+        for semantic in Semantic::list(infinite, truncated, finite, transitions) {
+            if let Err(response) = front.explore(semantic, transitions) {
+                return front.response(response);
+            }
+        }
+
+        if tn.max().is_none() && !front.front.is_empty() {
+            return Response::Solutions(front.front);
+        }
+
+        // -------------------- Complete/Future --------------------
+        if complete {
+            let mut solver = Solver::new(
+                model,
+                args,
+                log_file(args.log_folder.clone(), "complete", transitions),
+            );
+            solver
+                .add_comment(&format!("pareto future + unicity k={}", transitions))
+                .unwrap();
+            solver.create_future(transitions);
+            let result = solver.check();
+            match result {
+                SatResult::Unknown => {
+                    solver.exit();
+                    return front.response(Response::Unknown);
+                }
+                SatResult::Unsat => {
+                    solver.exit();
+                    let core = recheck_unsat_core(
+                        model,
+                        args,
+                        log_file(args.log_folder.clone(), "core", transitions),
+                        |solver| solver.create_future(transitions),
+                    );
+                    return front.response(Response::NoSolution(transitions, core));
+                }
+                SatResult::Sat => {
+                    solver.exit();
+                }
+            }
+        }
+
+        transitions += 1;
+    }
+}
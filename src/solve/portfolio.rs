@@ -0,0 +1,195 @@
+use std::sync::mpsc::channel;
+use std::thread;
+
+use super::*;
+use crate::model::Model;
+use crate::search::*;
+use crate::Args;
+use crate::ToLang;
+
+#[derive(Clone, Copy, Debug)]
+enum Strategy {
+    Incremental,
+    Sequence,
+    Parallel,
+}
+
+fn is_definitive(response: &Response) -> bool {
+    match response {
+        Response::NoSolution(_, _) => true,
+        Response::Unknown => false,
+        Response::BoundReached => false,
+        Response::Solution(_) => true,
+        Response::BestSolution(_) => true,
+        Response::Solutions(_) => true,
+        Response::Unbounded(_, _) => true,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_strategy(
+    strategy: Strategy,
+    model: &Model,
+    args: &Args,
+    infinite: bool,
+    truncated: bool,
+    finite: bool,
+    complete: bool,
+    tn: TransitionNumber,
+    pool_size: usize,
+) -> Response {
+    let mut pretty = d_stuff::Pretty::new();
+    let optimization = model.search().search_type().is_optimization();
+    match strategy {
+        Strategy::Incremental => resolve_incremental(
+            model,
+            &mut pretty,
+            args,
+            infinite,
+            truncated,
+            finite,
+            complete,
+            tn,
+        ),
+        Strategy::Sequence => {
+            if optimization {
+                resolve_sequence_optimize(
+                    model,
+                    &mut pretty,
+                    args,
+                    infinite,
+                    truncated,
+                    finite,
+                    complete,
+                    tn,
+                )
+            } else {
+                resolve_sequence(
+                    model,
+                    &mut pretty,
+                    args,
+                    infinite,
+                    truncated,
+                    finite,
+                    complete,
+                    tn,
+                )
+            }
+        }
+        Strategy::Parallel => {
+            if !truncated && !infinite && !finite && complete {
+                resolve_parallel_complete(model, &mut pretty, args, tn, pool_size)
+            } else if optimization {
+                resolve_parallel_optimize(
+                    model,
+                    &mut pretty,
+                    args,
+                    infinite,
+                    truncated,
+                    finite,
+                    complete,
+                    tn,
+                    pool_size,
+                )
+            } else {
+                resolve_parallel(
+                    model,
+                    &mut pretty,
+                    args,
+                    infinite,
+                    truncated,
+                    finite,
+                    complete,
+                    tn,
+                    pool_size,
+                )
+            }
+        }
+    }
+}
+
+/// Run the incremental, sequence and parallel strategies concurrently (with
+/// the same solver configuration) and return the first definitive response
+/// (solution or proof of no solution). The other strategies are then
+/// cancelled: their running solvers are killed.
+/// If no strategy concludes, BoundReached is preferred to Unknown.
+#[allow(clippy::too_many_arguments)]
+pub fn resolve_portfolio(
+    model: &Model,
+    _pretty: &mut d_stuff::Pretty,
+    args: &Args,
+    infinite: bool,
+    truncated: bool,
+    finite: bool,
+    complete: bool,
+    tn: TransitionNumber,
+    pool_size: usize,
+) -> Response {
+    // the incremental solver does not handle optimization
+    let strategies = if model.search().search_type().is_optimization() {
+        vec![Strategy::Sequence, Strategy::Parallel]
+    } else {
+        vec![
+            Strategy::Incremental,
+            Strategy::Sequence,
+            Strategy::Parallel,
+        ]
+    };
+
+    let (tx, rx) = channel();
+    // shared by the strategies to stop the losing ones
+    let mut args = args.clone();
+    args.new_cancel();
+
+    for (i, strategy) in strategies.iter().enumerate() {
+        let strategy = *strategy;
+        let tx = tx.clone();
+        let model = model.clone();
+        let mut args = args.clone();
+        // only the first strategy reports intermediate solutions
+        if i > 0 {
+            args.verbose = 0;
+        }
+        args.log_folder = args
+            .log_folder
+            .map(|folder| format!("{}/{}", folder, format!("{:?}", strategy).to_lowercase()));
+        if let Some(folder) = &args.log_folder {
+            let _ = std::fs::create_dir_all(folder);
+        }
+
+        thread::spawn(move || {
+            let response = run_strategy(
+                strategy, &model, &args, infinite, truncated, finite, complete, tn, pool_size,
+            );
+            // the receiver may already be gone if another strategy won
+            let _ = tx.send((strategy, response));
+        });
+    }
+    // only the strategies keep a sender
+    drop(tx);
+
+    let mut bound_reached = false;
+    for _ in 0..strategies.len() {
+        let (strategy, response) = match rx.recv() {
+            Ok(received) => received,
+            // the remaining strategies stopped without answering (panic)
+            Err(_) => break,
+        };
+        if args.verbose > 2 {
+            println!("portfolio: {:?} -> {}", strategy, response.to_lang(model));
+        }
+        if is_definitive(&response) {
+            args.cancel();
+            return response;
+        }
+        if let Response::BoundReached = response {
+            bound_reached = true;
+        }
+    }
+
+    if bound_reached {
+        Response::BoundReached
+    } else {
+        Response::Unknown
+    }
+}
@@ -11,6 +11,18 @@ pub fn resolve<'a>(model: &mut Model, pretty: &mut d_stuff::Pretty, args: &Args)
     // Flatten
     model.flatten_ltl();
 
+    // Enumerate
+    if let Some(enumeration) = model.search().search_type().enumeration() {
+        let enumeration = enumeration.clone();
+        return resolve_enumerate(model, pretty, args, &enumeration);
+    }
+
+    // Pareto
+    if let Some(pareto) = model.search().search_type().pareto() {
+        let pareto = pareto.clone();
+        return resolve_pareto(model, pretty, args, &pareto);
+    }
+
     // Solve
     match model.search().path_type() {
         PathType::Initial => {
@@ -26,7 +38,25 @@ pub fn resolve<'a>(model: &mut Model, pretty: &mut d_stuff::Pretty, args: &Args)
             finite,
             complete,
         } => {
-            if args.incremental {
+            if args.portfolio {
+                let num = num_cpus::get();
+                let pool_size = if args.threads <= 1 {
+                    num
+                } else {
+                    args.threads as usize
+                };
+                return resolve_portfolio(
+                    &model,
+                    pretty,
+                    args,
+                    infinite,
+                    truncated,
+                    finite,
+                    complete,
+                    model.search().transitions(),
+                    pool_size,
+                );
+            } else if args.incremental {
                 return resolve_incremental(
                     &model,
                     pretty,
@@ -166,7 +196,7 @@ pub fn resolve_perf(
         if complete && future_duration <= path_duration {
             let start_time = Instant::now();
 
-            let mut solver = Solver::new(model, log_file(args, log_count));
+            let mut solver = Solver::new(model, args, log_file(args, log_count));
             log_count += 1;
 
             solver
@@ -196,7 +226,10 @@ pub fn resolve_perf(
                 }
                 SatResult::Unsat => {
                     solver.exit();
-                    return Response::NoSolution(transitions);
+                    let core = recheck_unsat_core(model, args, log_file(args, log_count), |solver| {
+                        solver.create_future(transitions)
+                    });
+                    return Response::NoSolution(transitions, core);
                 }
                 SatResult::Sat => {
                     solver.exit();
@@ -209,7 +242,7 @@ pub fn resolve_perf(
         if truncated {
             let start_time = Instant::now();
 
-            let mut solver = Solver::new(model, log_file(args, log_count));
+            let mut solver = Solver::new(model, args, log_file(args, log_count));
             log_count += 1;
 
             solver
@@ -252,7 +285,7 @@ pub fn resolve_perf(
             // ---------- Infinite ----------
             let start_time = Instant::now();
 
-            let mut solver = Solver::new(model, log_file(args, log_count));
+            let mut solver = Solver::new(model, args, log_file(args, log_count));
             log_count += 1;
 
             solver
@@ -302,7 +335,7 @@ pub fn resolve_perf(
                 //     println!("{}", sol.to_lang(model));
                 // }
                 // println!("==========");
-                let mut solver = Solver::new(model, log_file(args, log_count));
+                let mut solver = Solver::new(model, args, log_file(args, log_count));
                 log_count += 1;
 
                 solver
@@ -336,7 +369,7 @@ pub fn resolve_perf(
                         solver.exit();
 
                         // Check if is_finite
-                        let mut solver = Solver::new(model, log_file(args, log_count));
+                        let mut solver = Solver::new(model, args, log_file(args, log_count));
                         log_count += 1;
 
                         solver
@@ -396,7 +429,7 @@ pub fn resolve_perf(
                 // -------------------- Future --------------------
                 if complete && future_transitions < transitions {
                     let start_time = Instant::now();
-                    let mut solver = Solver::new(model, log_file(args, log_count));
+                    let mut solver = Solver::new(model, args, log_file(args, log_count));
                     log_count += 1;
 
                     solver
@@ -426,7 +459,13 @@ pub fn resolve_perf(
                         }
                         SatResult::Unsat => {
                             solver.exit();
-                            return Response::NoSolution(transitions);
+                            let core = recheck_unsat_core(
+                                model,
+                                args,
+                                log_file(args, log_count),
+                                |solver| solver.create_future(transitions),
+                            );
+                            return Response::NoSolution(transitions, core);
                         }
                         SatResult::Sat => {
                             solver.exit();
@@ -4,11 +4,28 @@ use crate::model::*;
 
 #[derive(Clone, Debug)]
 pub enum Response {
-    NoSolution(usize),
+    /// number of transitions and the model items of the unsat core
+    NoSolution(usize, Vec<CoreItem>),
     Unknown,
     BoundReached,
     Solution(Solution),
     BestSolution(Solution),
+    /// distinct traces of 'solve all' or 'solve N'
+    Solutions(Vec<Solution>),
+    /// index of the unbounded objective and a trace
+    Unbounded(usize, Solution),
+}
+
+impl Response {
+    pub fn solution(&self) -> Option<&Solution> {
+        match self {
+            Response::Solution(solution) => Some(solution),
+            Response::BestSolution(solution) => Some(solution),
+            Response::Solutions(solutions) => solutions.first(),
+            Response::Unbounded(_, solution) => Some(solution),
+            _ => None,
+        }
+    }
 }
 
 //------------------------- To Lang -------------------------
@@ -16,15 +33,51 @@ pub enum Response {
 impl ToLang for Response {
     fn to_lang(&self, model: &Model) -> String {
         match self {
-            Response::NoSolution(k) => format!("no solution k={}", k),
+            Response::NoSolution(k, core) => {
+                if core.is_empty() {
+                    format!("no solution k={}", k)
+                } else {
+                    format!(
+                        "no solution k={}\nno trace because {} conflict",
+                        k,
+                        core_to_lang(model, core)
+                    )
+                }
+            }
             Response::Unknown => "unknown".to_string(),
             Response::BoundReached => "bound reached".to_string(),
             Response::Solution(solution) => format!("one solution:\n{}", solution.to_lang(model)),
             Response::BestSolution(solution) => {
                 format!("best solution:\n{}", solution.to_lang(model))
             }
+            Response::Solutions(solutions) => {
+                let mut res = format!("{} solutions:", solutions.len());
+                for (i, solution) in solutions.iter().enumerate() {
+                    res += &format!("\n========== Solution {} ==========\n", i + 1);
+                    res += &solution.to_lang(model);
+                }
+                res
+            }
+            Response::Unbounded(index, solution) => format!(
+                "unbounded objective: {}\n{}",
+                unbounded_to_lang(model, *index),
+                solution.to_lang(model)
+            ),
+        }
+    }
+}
+
+fn unbounded_to_lang(model: &Model, index: usize) -> String {
+    let search_type = model.search().search_type();
+    if let Some(pareto) = search_type.pareto() {
+        if let Some(objective) = pareto.objectives.get(index) {
+            return objective.to_lang(model);
         }
     }
+    match search_type.optimizations().get(index) {
+        Some(optimization) => optimization.objective.to_lang(model),
+        None => index.to_string(),
+    }
 }
 
 //------------------------- To Entry -------------------------
@@ -32,20 +85,51 @@ impl ToLang for Response {
 impl ToEntry for Response {
     fn to_entry(&self, model: &Model) -> d_stuff::Entry {
         match self {
-            Response::NoSolution(k) => d_stuff::Entry::new(
-                d_stuff::Status::Failure,
-                d_stuff::Text::new(
-                    "Solve ",
-                    termion::style::Bold.to_string(),
-                    termion::color::Blue.fg_str(),
-                ),
-                Some(d_stuff::Text::new(
-                    format!("UNSAT k={}", k),
-                    termion::style::Reset.to_string(),
-                    termion::color::Red.fg_str(),
-                )),
-                vec![],
-            ),
+            Response::NoSolution(k, core) => {
+                let mut messages = vec![];
+                if !core.is_empty() {
+                    messages.push(d_stuff::Message::new(
+                        None,
+                        d_stuff::Text::new(
+                            format!("no trace because {} conflict", core_to_lang(model, core)),
+                            termion::style::Reset.to_string(),
+                            termion::color::White.fg_str(),
+                        ),
+                    ));
+                }
+                for item in core.iter() {
+                    let (name, position) = item.naming(model);
+                    messages.push(d_stuff::Message::new(
+                        Some(d_stuff::Text::new(
+                            "Conflict",
+                            termion::style::Reset.to_string(),
+                            termion::color::Red.fg_str(),
+                        )),
+                        d_stuff::Text::new(
+                            format!("'{}'", name),
+                            termion::style::Reset.to_string(),
+                            termion::color::LightBlue.fg_str(),
+                        ),
+                    ));
+                    if let Some(position) = position {
+                        messages.push(position.to_message());
+                    }
+                }
+                d_stuff::Entry::new(
+                    d_stuff::Status::Failure,
+                    d_stuff::Text::new(
+                        "Solve ",
+                        termion::style::Bold.to_string(),
+                        termion::color::Blue.fg_str(),
+                    ),
+                    Some(d_stuff::Text::new(
+                        format!("UNSAT k={}", k),
+                        termion::style::Reset.to_string(),
+                        termion::color::Red.fg_str(),
+                    )),
+                    messages,
+                )
+            }
             Response::Unknown => d_stuff::Entry::new(
                 d_stuff::Status::Question,
                 d_stuff::Text::new(
@@ -116,6 +200,72 @@ impl ToEntry for Response {
                     ),
                 )],
             ),
+            Response::Solutions(solutions) => d_stuff::Entry::new(
+                d_stuff::Status::Success,
+                d_stuff::Text::new(
+                    "Solve ",
+                    termion::style::Bold.to_string(),
+                    termion::color::Blue.fg_str(),
+                ),
+                Some(d_stuff::Text::new(
+                    format!("SAT {} solutions", solutions.len()),
+                    termion::style::Reset.to_string(),
+                    termion::color::Green.fg_str(),
+                )),
+                solutions
+                    .iter()
+                    .enumerate()
+                    .map(|(i, solution)| {
+                        d_stuff::Message::new(
+                            Some(d_stuff::Text::new(
+                                format!("Solution {}", i + 1),
+                                termion::style::Reset.to_string(),
+                                termion::color::Cyan.fg_str(),
+                            )),
+                            d_stuff::Text::new(
+                                solution.to_lang(model),
+                                termion::style::Reset.to_string(),
+                                termion::color::White.fg_str(),
+                            ),
+                        )
+                    })
+                    .collect(),
+            ),
+            Response::Unbounded(index, solution) => d_stuff::Entry::new(
+                d_stuff::Status::Question,
+                d_stuff::Text::new(
+                    "Solve ",
+                    termion::style::Bold.to_string(),
+                    termion::color::Blue.fg_str(),
+                ),
+                Some(d_stuff::Text::new(
+                    "UNBOUNDED",
+                    termion::style::Reset.to_string(),
+                    termion::color::Red.fg_str(),
+                )),
+                vec![
+                    d_stuff::Message::new(
+                        Some(d_stuff::Text::new(
+                            "Objective",
+                            termion::style::Reset.to_string(),
+                            termion::color::Red.fg_str(),
+                        )),
+                        d_stuff::Text::new(
+                            format!("'{}' is unbounded", unbounded_to_lang(model, *index)),
+                            termion::style::Reset.to_string(),
+                            termion::color::LightBlue.fg_str(),
+                        ),
+                    ),
+                    d_stuff::Message::new(
+                        None,
+                        d_stuff::Text::new(
+                            solution.to_lang(model),
+                            termion::style::Reset.to_string(),
+                            termion::color::White.fg_str(),
+                        ),
+                    ),
+                ],
+            ),
         }
     }
 }
@@ -19,6 +19,9 @@ pub fn resolve_sequence(
     let mut transitions = tn.min();
 
     loop {
+        if args.is_cancelled() {
+            return Response::Unknown;
+        }
         #[cfg(debug_assertions)]
         {
             println!(
@@ -49,6 +52,7 @@ pub fn resolve_sequence(
 
             let mut solver = Solver::new(
                 model,
+                args,
                 log_file(args.log_folder.clone(), "truncated", transitions),
             );
 
@@ -83,6 +87,7 @@ pub fn resolve_sequence(
 
             let mut solver = Solver::new(
                 model,
+                args,
                 log_file(args.log_folder.clone(), "inifinite", transitions),
             );
 
@@ -114,11 +119,15 @@ pub fn resolve_sequence(
             let mut solutions: Vec<Solution> = Vec::new();
 
             loop {
+                if args.is_cancelled() {
+                    return Response::Unknown;
+                }
                 if args.verbose > 2 {
                     println!("> finite {} <", transitions);
                 }
                 let mut solver = Solver::new(
                     model,
+                    args,
                     log_file_n(
                         args.log_folder.clone(),
                         "finite",
@@ -160,6 +169,7 @@ pub fn resolve_sequence(
                         }
                         let mut solver = Solver::new(
                             model,
+                            args,
                             log_file_n(
                                 args.log_folder.clone(),
                                 "is_finite",
@@ -213,6 +223,7 @@ pub fn resolve_sequence(
             }
             let mut solver = Solver::new(
                 model,
+                args,
                 log_file(args.log_folder.clone(), "complete", transitions),
             );
 
@@ -230,7 +241,13 @@ pub fn resolve_sequence(
                 }
                 SatResult::Unsat => {
                     solver.exit();
-                    return Response::NoSolution(transitions);
+                    let core = recheck_unsat_core(
+                        model,
+                        args,
+                        log_file(args.log_folder.clone(), "core", transitions),
+                        |solver| solver.create_future(transitions),
+                    );
+                    return Response::NoSolution(transitions, core);
                 }
                 SatResult::Sat => {
                     solver.exit();
@@ -21,6 +21,9 @@ pub fn resolve_sequence_optimize(
     let mut best_solution: Option<Solution> = None;
 
     loop {
+        if args.is_cancelled() {
+            return Response::Unknown;
+        }
         #[cfg(debug_assertions)]
         {
             println!(
@@ -54,6 +57,7 @@ pub fn resolve_sequence_optimize(
             }
             let mut solver = Solver::new(
                 model,
+                args,
                 log_file(args.log_folder.clone(), "truncated", transitions),
             );
 
@@ -63,7 +67,7 @@ pub fn resolve_sequence_optimize(
             solver.create_truncated(transitions);
             solver.add_optimization();
             if let Some(solution) = &best_solution {
-                solver.add_best_objective_constraint(solution.objective.as_ref().unwrap());
+                solver.add_best_objective_constraint(&solution.objectives);
             }
 
             let result = solver.check();
@@ -78,6 +82,10 @@ pub fn resolve_sequence_optimize(
                 }
                 SatResult::Sat => {
                     let solution = Solution::from_solver(&mut solver, false);
+                    if let Some(index) = solver.unbounded_objective() {
+                        solver.exit();
+                        return Response::Unbounded(index, solution);
+                    }
                     solver.exit();
                     if args.verbose > 0 {
                         best_solution = Some(solution.clone());
@@ -99,6 +107,7 @@ pub fn resolve_sequence_optimize(
 
             let mut solver = Solver::new(
                 model,
+                args,
                 log_file(args.log_folder.clone(), "inifinite", transitions),
             );
 
@@ -108,7 +117,7 @@ pub fn resolve_sequence_optimize(
             solver.create_infinite(transitions);
             solver.add_optimization();
             if let Some(solution) = &best_solution {
-                solver.add_best_objective_constraint(solution.objective.as_ref().unwrap());
+                solver.add_best_objective_constraint(&solution.objectives);
             }
 
             let result = solver.check();
@@ -123,6 +132,10 @@ pub fn resolve_sequence_optimize(
                 }
                 SatResult::Sat => {
                     let solution = Solution::from_solver(&mut solver, false);
+                    if let Some(index) = solver.unbounded_objective() {
+                        solver.exit();
+                        return Response::Unbounded(index, solution);
+                    }
                     solver.exit();
                     if args.verbose > 0 {
                         best_solution = Some(solution.clone());
@@ -144,8 +157,12 @@ pub fn resolve_sequence_optimize(
             let mut solutions: Vec<Solution> = Vec::new();
 
             loop {
+                if args.is_cancelled() {
+                    return Response::Unknown;
+                }
                 let mut solver = Solver::new(
                     model,
+                    args,
                     log_file_n(
                         args.log_folder.clone(),
                         "finite",
@@ -168,7 +185,7 @@ pub fn resolve_sequence_optimize(
                 solver.create_finite(transitions, &solutions);
                 solver.add_optimization();
                 if let Some(solution) = &best_solution {
-                    solver.add_best_objective_constraint(solution.objective.as_ref().unwrap());
+                    solver.add_best_objective_constraint(&solution.objectives);
                 }
 
                 let result = solver.check();
@@ -184,11 +201,13 @@ pub fn resolve_sequence_optimize(
                     }
                     SatResult::Sat => {
                         let solution = Solution::from_solver(&mut solver, true);
+                        let unbounded = solver.unbounded_objective();
                         solver.exit();
 
                         // Check if is_finite
                         let mut solver = Solver::new(
                             model,
+                            args,
                             log_file_n(
                                 args.log_folder.clone(),
                                 "is_finite",
@@ -215,9 +234,7 @@ pub fn resolve_sequence_optimize(
                         solver.create_finite_future(transitions + 1, &solution);
                         solver.add_optimization();
                         if let Some(solution) = &best_solution {
-                            solver.add_best_objective_constraint(
-                                solution.objective.as_ref().unwrap(),
-                            );
+                            solver.add_best_objective_constraint(&solution.objectives);
                         }
 
                         let result = solver.check();
@@ -229,6 +246,9 @@ pub fn resolve_sequence_optimize(
                             }
                             SatResult::Unsat => {
                                 solver.exit();
+                                if let Some(index) = unbounded {
+                                    return Response::Unbounded(index, solution);
+                                }
                                 if args.verbose > 0 {
                                     best_solution = Some(solution.clone());
                                     pretty.add(Response::Solution(solution).to_entry(&model));
@@ -256,6 +276,7 @@ pub fn resolve_sequence_optimize(
             }
             let mut solver = Solver::new(
                 model,
+                args,
                 log_file(args.log_folder.clone(), "complete", transitions),
             );
 
@@ -265,7 +286,7 @@ pub fn resolve_sequence_optimize(
             solver.create_future(transitions);
             solver.add_optimization();
             if let Some(solution) = &best_solution {
-                solver.add_best_objective_constraint(solution.objective.as_ref().unwrap());
+                solver.add_best_objective_constraint(&solution.objectives);
             }
 
             let result = solver.check();
@@ -279,7 +300,15 @@ pub fn resolve_sequence_optimize(
                     solver.exit();
                     match &best_solution {
                         Some(solution) => return Response::BestSolution(solution.clone()),
-                        None => return Response::NoSolution(transitions),
+                        None => {
+                            let core = recheck_unsat_core(
+                                model,
+                                args,
+                                log_file(args.log_folder.clone(), "core", transitions),
+                                |solver| solver.create_future(transitions),
+                            );
+                            return Response::NoSolution(transitions, core);
+                        }
                     }
                 }
                 SatResult::Sat => {
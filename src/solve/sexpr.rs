@@ -0,0 +1,120 @@
+/// S-expression of a solver response
+#[derive(Clone, Debug, PartialEq)]
+pub enum SExpr {
+    Atom(String),
+    List(Vec<SExpr>),
+}
+
+impl SExpr {
+    /// The s-expressions of a response (an unbalanced parenthesis is ignored)
+    pub fn parse(text: &str) -> Vec<SExpr> {
+        let mut stack: Vec<Vec<SExpr>> = vec![vec![]];
+        let mut atom = String::new();
+        for c in text.chars() {
+            if c == '(' || c == ')' || c.is_whitespace() {
+                if !atom.is_empty() {
+                    stack
+                        .last_mut()
+                        .unwrap()
+                        .push(SExpr::Atom(std::mem::take(&mut atom)));
+                }
+                if c == '(' {
+                    stack.push(vec![]);
+                } else if c == ')' && stack.len() > 1 {
+                    let list = stack.pop().unwrap();
+                    stack.last_mut().unwrap().push(SExpr::List(list));
+                }
+            } else {
+                atom.push(c);
+            }
+        }
+        if !atom.is_empty() {
+            stack.last_mut().unwrap().push(SExpr::Atom(atom));
+        }
+        stack.swap_remove(0)
+    }
+
+    /// The value of a numeral with infinite and infinitesimal parts (as z3 gives
+    /// the objectives): (coefficient of 'oo', finite part), 'epsilon' being ignored.
+    /// None if it is not a numeral.
+    fn infinite_value(&self) -> Option<(f64, f64)> {
+        match self {
+            SExpr::Atom(atom) if atom == "oo" => Some((1.0, 0.0)),
+            SExpr::Atom(atom) if atom == "epsilon" => Some((0.0, 0.0)),
+            SExpr::Atom(atom) => atom.parse::<f64>().ok().map(|x| (0.0, x)),
+            SExpr::List(list) => {
+                let (op, args) = match list.split_first() {
+                    Some((SExpr::Atom(op), args)) if !args.is_empty() => (op.as_str(), args),
+                    _ => return None,
+                };
+                let values = args
+                    .iter()
+                    .map(|arg| arg.infinite_value())
+                    .collect::<Option<Vec<_>>>()?;
+                let (first, rest) = values.split_first().unwrap();
+                match op {
+                    "+" => Some(
+                        values
+                            .iter()
+                            .fold((0.0, 0.0), |a, b| (a.0 + b.0, a.1 + b.1)),
+                    ),
+                    "-" if rest.is_empty() => Some((-first.0, -first.1)),
+                    "-" => Some(rest.iter().fold(*first, |a, b| (a.0 - b.0, a.1 - b.1))),
+                    // at most one factor is infinite
+                    "*" => Some(
+                        rest.iter()
+                            .fold(*first, |a, b| (a.0 * b.1 + a.1 * b.0, a.1 * b.1)),
+                    ),
+                    "/" => rest.iter().try_fold(*first, |a, b| {
+                        (b.0 == 0.0 && b.1 != 0.0).then(|| (a.0 / b.1, a.1 / b.1))
+                    }),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// The coefficient of 'oo' of a numeral, None if it is not a numeral
+    pub fn infinite_part(&self) -> Option<f64> {
+        self.infinite_value().map(|(infinite, _)| infinite)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn infinite_part(text: &str) -> Option<f64> {
+        SExpr::parse(text)[0].infinite_part()
+    }
+
+    #[test]
+    fn sexpr_parse() {
+        let atom = |s: &str| SExpr::Atom(s.to_string());
+        assert_eq!(
+            SExpr::parse("(objectives\n (o oo)\n)"),
+            vec![SExpr::List(vec![
+                atom("objectives"),
+                SExpr::List(vec![atom("o"), atom("oo")])
+            ])]
+        );
+    }
+
+    #[test]
+    fn sexpr_infinite_part() {
+        assert_eq!(infinite_part("oo"), Some(1.0));
+        assert_eq!(infinite_part("(* (- 1) oo)"), Some(-1.0));
+        assert_eq!(infinite_part("(* (- 1.0) oo)"), Some(-1.0));
+        assert_eq!(infinite_part("(- oo)"), Some(-1.0));
+        assert_eq!(infinite_part("(+ (* 2 oo) 3)"), Some(2.0));
+        assert_eq!(infinite_part("3"), Some(0.0));
+        assert_eq!(infinite_part("(- 3)"), Some(0.0));
+        assert_eq!(infinite_part("(/ 1 2)"), Some(0.0));
+        // strict bounds
+        assert_eq!(infinite_part("(+ 3 (* (- 1) epsilon))"), Some(0.0));
+        assert_eq!(infinite_part("(- 3 epsilon)"), Some(0.0));
+        // not numerals
+        assert_eq!(infinite_part("foo"), None);
+        assert_eq!(infinite_part("(f oo)"), None);
+    }
+}
@@ -2,6 +2,7 @@ use super::*;
 use crate::common::*;
 use crate::expr::*;
 use crate::model::*;
+use crate::search::Optimization;
 use crate::typing::*;
 use std::cmp::Ordering;
 use std::cmp::Ordering::*;
@@ -14,7 +15,8 @@ pub struct Solution {
     pub cst_dec: HashMap<DeclarationId, Option<Expr>>,
     pub var_dec: HashMap<DeclarationId, Vec<Option<Expr>>>,
     pub var_def: HashMap<DefinitionId, Vec<Option<Expr>>>,
-    pub objective: Option<Expr>,
+    /// values of the objectives, by priority
+    pub objectives: Vec<Expr>,
 }
 
 impl Solution {
@@ -31,7 +33,20 @@ impl Solution {
             Type::Int => 0.into(),
             Type::Interval(_) => 0.into(),
             Type::IntInterval(min, _) => (*min).into(),
+            Type::Unsigned(_) => 0.into(),
+            Type::Signed(_) => 0.into(),
             Type::Real => 0.into(),
+            Type::Array(index, element) => {
+                let value = Self::get_default_value(model, element);
+                let list = index
+                    .get_type(model)
+                    .all(model)
+                    .into_iter()
+                    .map(|i| (i, value.clone()))
+                    .collect();
+                Expr::new(Expression::ArrayValue(list), None)
+            }
+            Type::Set(_) => Expr::new(Expression::SetValue(vec![]), None),
             //
             Type::Undefined => panic!(),
             Type::Unresolved(_, _) => panic!(),
@@ -100,18 +115,37 @@ impl Solution {
             }
         }
 
-        // Objective
-        let objective = match solver.model().search().search_type().optimization() {
-            Some(opt) => {
-                let opt = opt.clone();
-                let eval = solver.eval(&opt.objective, 0);
-                match eval {
-                    Some(eval) => Some(eval),
-                    None => Some(solver.eval(&opt.bound, 0).unwrap()),
+        // Objectives
+        let mut objectives = vec![];
+        let optimizations = solver
+            .model()
+            .search()
+            .search_type()
+            .optimizations()
+            .to_vec();
+        for opt in optimizations.iter() {
+            let eval = solver.eval(&opt.objective, 0);
+            match (eval, &opt.bound) {
+                (Some(eval), _) => objectives.push(eval),
+                (None, Some(bound)) => objectives.push(solver.eval(bound, 0).unwrap()),
+                (None, None) => {
+                    let typ = opt.objective.get_type(solver.model());
+                    objectives.push(Self::get_default_value(solver.model(), &typ))
+                }
+            }
+        }
+        let pareto = solver.model().search().search_type().pareto().cloned();
+        if let Some(pareto) = pareto {
+            for objective in pareto.objectives.iter() {
+                match solver.eval(objective, 0) {
+                    Some(eval) => objectives.push(eval),
+                    None => {
+                        let typ = objective.get_type(solver.model());
+                        objectives.push(Self::get_default_value(solver.model(), &typ))
+                    }
                 }
             }
-            None => None,
-        };
+        }
 
         Self {
             states: solver.states(),
@@ -119,44 +153,80 @@ impl Solution {
             cst_dec,
             var_dec,
             var_def,
-            objective,
+            objectives,
         }
     }
 
-    pub fn compare_objective(&self, other: &Solution) -> Option<Ordering> {
-        match (self.objective.as_ref(), other.objective.as_ref()) {
-            (Some(x), Some(y)) => match (x.expression(), y.expression()) {
-                (Expression::Int(x), Expression::Int(y)) => {
-                    if x == y {
-                        return Some(Equal);
-                    }
-                    if x < y {
-                        return Some(Less);
-                    }
-                    return Some(Greater);
-                }
-                (Expression::Real(x), Expression::Real(y)) => {
-                    if x == y {
-                        return Some(Equal);
-                    }
-                    if x < y {
-                        return Some(Less);
-                    }
-                    return Some(Greater);
-                }
-                _ => None,
-            },
+    /// Variables then definitions, in model order, with their type and values by state.
+    pub fn traces<'a>(&'a self, model: &Model) -> Vec<(String, Type, &'a Vec<Option<Expr>>)> {
+        let mut res = vec![];
+        for id in model.var_declaration_ids() {
+            if let Some(values) = self.var_dec.get(&id) {
+                let dec = model.get(id).unwrap();
+                res.push((dec.name().to_string(), dec.get_type(model), values));
+            }
+        }
+        for id in model.definition_ids() {
+            if let Some(values) = self.var_def.get(&id) {
+                let def = model.get(id).unwrap();
+                res.push((def.name().to_string(), def.get_type(model), values));
+            }
+        }
+        res
+    }
+
+    fn compare_value(x: &Expr, y: &Expr) -> Option<Ordering> {
+        match (x.expression(), y.expression()) {
+            (Expression::Int(x), Expression::Int(y)) => Some(x.cmp(y)),
+            (Expression::Real(x), Expression::Real(y)) => x.partial_cmp(y),
             _ => None,
         }
     }
+
+    /// Lexicographic comparison of the objectives: Less if self is better
+    pub fn compare_objectives(
+        &self,
+        other: &Solution,
+        optimizations: &[Optimization],
+    ) -> Option<Ordering> {
+        for ((x, y), opt) in self
+            .objectives
+            .iter()
+            .zip(other.objectives.iter())
+            .zip(optimizations)
+        {
+            let ord = Self::compare_value(x, y)?;
+            let ord = if opt.minimize { ord } else { ord.reverse() };
+            if ord != Equal {
+                return Some(ord);
+            }
+        }
+        Some(Equal)
+    }
+
+    /// Pareto dominance of the objectives: better or equal on all, strictly better on one
+    pub fn dominates(&self, other: &Solution, minimize: bool) -> bool {
+        let mut strict = false;
+        for (x, y) in self.objectives.iter().zip(other.objectives.iter()) {
+            let ord = match Self::compare_value(x, y) {
+                Some(ord) if minimize => ord,
+                Some(ord) => ord.reverse(),
+                None => return false,
+            };
+            match ord {
+                Less => strict = true,
+                Equal => {}
+                Greater => return false,
+            }
+        }
+        strict
+    }
 }
 
 //------------------------- To Lang -------------------------
 
 impl ToLang for Solution {
     fn to_lang(&self, model: &Model) -> String {
-        println!("{}", model.debug());
-
         let mut res = "".to_string();
 
         // Constantes
@@ -169,8 +239,9 @@ impl ToLang for Solution {
             }
         }
 
-        if let Some(objective) = &self.objective {
-            res += &format!("objective = {}\n", objective.to_lang(model));
+        if !self.objectives.is_empty() {
+            let l: Vec<String> = self.objectives.iter().map(|x| x.to_lang(model)).collect();
+            res += &format!("objective = {}\n", l.join(", "));
         }
 
         // First State
@@ -212,9 +283,7 @@ impl ToLang for Solution {
             }
             // Definitions
             for (id, v) in self.var_def.iter() {
-                println!("id: {:?}", id);
                 let def = model.get(*id).unwrap();
-                println!("def: {:?}", def);
                 if let Some(value) = &v[state] {
                     let line = format!("{} = {}\n", def.to_lang(model), value.to_lang(model));
                     if prec_def.get(id) != Some(&line) {
@@ -233,3 +302,73 @@ impl ToLang for Solution {
         res
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fraction::Fraction;
+
+    fn solution(objectives: Vec<Expr>) -> Solution {
+        Solution {
+            states: 1,
+            loop_index: None,
+            cst_dec: HashMap::new(),
+            var_dec: HashMap::new(),
+            var_def: HashMap::new(),
+            objectives,
+        }
+    }
+
+    fn optimization(minimize: bool) -> Optimization {
+        Optimization {
+            minimize,
+            objective: 0.into(),
+            bound: None,
+        }
+    }
+
+    #[test]
+    fn compare_objectives_lexicographic() {
+        let opts = [optimization(true), optimization(false)];
+        let a = solution(vec![1.into(), 5.into()]);
+        let b = solution(vec![2.into(), 9.into()]);
+        let c = solution(vec![1.into(), 7.into()]);
+        assert_eq!(a.compare_objectives(&b, &opts), Some(Less));
+        assert_eq!(b.compare_objectives(&a, &opts), Some(Greater));
+        // same first objective, the second one is maximized
+        assert_eq!(a.compare_objectives(&c, &opts), Some(Greater));
+        assert_eq!(a.compare_objectives(&a, &opts), Some(Equal));
+    }
+
+    #[test]
+    fn compare_objectives_real() {
+        let opts = [optimization(true)];
+        let a = solution(vec![Fraction::new(1u64, 2u64).into()]);
+        let b = solution(vec![Fraction::new(3u64, 4u64).into()]);
+        assert_eq!(a.compare_objectives(&b, &opts), Some(Less));
+        let c = solution(vec![true.into()]);
+        assert_eq!(a.compare_objectives(&c, &opts), None);
+    }
+
+    #[test]
+    fn dominates_minimize() {
+        let a = solution(vec![1.into(), 2.into()]);
+        let b = solution(vec![1.into(), 3.into()]);
+        let c = solution(vec![0.into(), 4.into()]);
+        assert!(a.dominates(&b, true));
+        assert!(!b.dominates(&a, true));
+        // incomparable
+        assert!(!a.dominates(&c, true));
+        assert!(!c.dominates(&a, true));
+        // not strictly better
+        assert!(!a.dominates(&a, true));
+    }
+
+    #[test]
+    fn dominates_maximize() {
+        let a = solution(vec![1.into(), 2.into()]);
+        let b = solution(vec![1.into(), 3.into()]);
+        assert!(b.dominates(&a, false));
+        assert!(!a.dominates(&b, false));
+    }
+}
@@ -5,6 +5,7 @@ use crate::common::*;
 use crate::expr::*;
 use crate::model::*;
 use crate::typing::*;
+use crate::Args;
 use fraction::Fraction;
 use regex::Regex;
 use smt_sb::*;
@@ -14,23 +15,54 @@ pub struct Solver<'a> {
     transitions: usize,
     with_loop: bool,
     smt: SmtBridge,
+    /// process id of the solver, to interrupt it
+    pid: u32,
+    cancel: Cancel,
+    named: usize,
+    /// trace aggregates of the model, with an accumulator by state
+    traces: Vec<Expr>,
+    /// objectives without bound given to the optimizer
+    unbounded: Vec<usize>,
+    /// the assertions are named to get an unsat core
+    unsat_core: bool,
 }
 
 impl<'a> Solver<'a> {
-    pub fn new(model: &'a Model, log_file: Option<String>) -> Self {
-        let mut smt = SmtBridge::new("z3", vec!["-in"], log_file).unwrap();
+    pub fn new(model: &'a Model, args: &Args, log_file: Option<String>) -> Self {
+        Self::create(model, args, log_file, false)
+    }
+
+    /// Solver whose unsat answers are explained by an unsat core
+    pub fn with_unsat_core(model: &'a Model, args: &Args, log_file: Option<String>) -> Self {
+        Self::create(model, args, log_file, true)
+    }
+
+    fn create(model: &'a Model, args: &Args, log_file: Option<String>, unsat_core: bool) -> Self {
+        // the shell prints its pid before becoming z3
+        let mut smt = SmtBridge::new("sh", vec!["-c", "echo $$; exec z3 -in"], log_file).unwrap();
+        let pid = smt.read_line().unwrap().parse().unwrap();
         smt.set_option("print-success", "false").unwrap();
+        if unsat_core {
+            smt.set_option("produce-unsat-cores", "true").unwrap();
+        }
         //
         Self {
             model,
             transitions: 0,
             with_loop: false,
             smt,
+            pid,
+            cancel: args.cancellation().clone(),
+            named: 0,
+            traces: model.trace_aggregates(),
+            unbounded: vec![],
+            unsat_core,
         }
     }
 
     pub fn exit(&mut self) {
-        self.smt.exit().unwrap();
+        // an interrupted solver is already gone
+        let _ = self.smt.exit();
     }
 
     pub fn model(&self) -> &Model {
@@ -58,8 +90,14 @@ impl<'a> Solver<'a> {
             Type::Real => "Real".to_string(),
             Type::IntInterval(_, _) => "Int".to_string(),
             Type::Interval(_) => "Int".to_string(),
+            Type::Unsigned(width) => format!("(_ BitVec {})", width),
+            Type::Signed(width) => format!("(_ BitVec {})", width),
 
             Type::Enumerate(id) => self.model.get(*id).unwrap().name().to_string(),
+            Type::Array(index, element) => {
+                format!("(Array {} {})", self.to_sort(index), self.to_sort(element))
+            }
+            Type::Set(element) => format!("(Array {} Bool)", self.to_sort(element)),
 
             Type::Undefined => panic!(),
             Type::Unresolved(_, _) => panic!(),
@@ -76,6 +114,10 @@ impl<'a> Solver<'a> {
         let e = self.model.get(id).unwrap();
         Self::enumerate_name(e)
     }
+    /// The function ('ord', 'succ' or 'pred') of an ordered enumerate
+    pub fn enumerate_fun_name(&self, op: PrefixUnaryOperator, id: EnumerateId) -> String {
+        format!("_{}_{}", op, self.enumerate_name_from_id(id))
+    }
 
     pub fn cst_dec_name(dec: &Declaration) -> String {
         dec.name().to_string()
@@ -125,10 +167,18 @@ impl<'a> Solver<'a> {
         Self::ltl_var_name(v, state)
     }
 
+    pub fn trace_name(index: usize, state: usize) -> String {
+        format!("__trace_{}_{}", index, state)
+    }
+
     pub fn loop_name(state: usize) -> String {
         format!("_l_{}", state)
     }
 
+    pub fn transition_name(transition: &Transition, state: usize) -> String {
+        format!("_t_{}_{}", transition.id().index(), state)
+    }
+
     //------------------------- Enum Declaration -------------------------
 
     fn declare_enumerate(&mut self, enumerate: &Enumerate) {
@@ -140,6 +190,31 @@ impl<'a> Solver<'a> {
         self.smt
             .declare_enumeration(enumerate.name(), &elements)
             .unwrap();
+        if enumerate.is_ordered() {
+            let sort = Self::enumerate_name(enumerate);
+            let param = format!("x {}", sort);
+            // index, successor and predecessor (the bounds are their own successor/predecessor)
+            let last = elements.len() - 1;
+            let mut ord = format!("{}", last);
+            let mut succ = elements[last].to_string();
+            let mut pred = elements[0].to_string();
+            for (i, e) in elements.iter().enumerate().rev().skip(1) {
+                ord = format!("(ite (= x {}) {} {})", e, i, ord);
+                succ = format!("(ite (= x {}) {} {})", e, elements[i + 1], succ);
+            }
+            for (i, e) in elements.iter().enumerate().skip(1).rev() {
+                pred = format!("(ite (= x {}) {} {})", e, elements[i - 1], pred);
+            }
+            let id = enumerate.id();
+            for (op, sort, expr) in [
+                (PrefixUnaryOperator::Ord, "Int", ord),
+                (PrefixUnaryOperator::Succ, sort.as_str(), succ),
+                (PrefixUnaryOperator::Pred, sort.as_str(), pred),
+            ] {
+                let name = self.enumerate_fun_name(op, id);
+                self.smt.define_fun(&name, &[&param], sort, &expr).unwrap();
+            }
+        }
     }
 
     fn declare_enumerates(&mut self) {
@@ -148,6 +223,228 @@ impl<'a> Solver<'a> {
         }
     }
 
+    //------------------------- Array -------------------------
+
+    /// Equality of two values (on the domain of the index for the arrays and the sets)
+    fn smt_eq(&self, typ: &Type, left: &str, right: &str) -> String {
+        match typ.get_type(self.model) {
+            Type::Set(element) => {
+                let typ = Type::Array(element, Box::new(Type::Bool));
+                self.smt_eq(&typ, left, right)
+            }
+            Type::Array(index, element) => {
+                let mut conj = "(and true".to_string();
+                for i in index.all(self.model) {
+                    let i = self.to_smt(&i, 0);
+                    let l = format!("(select {} {})", left, i);
+                    let r = format!("(select {} {})", right, i);
+                    conj += &format!(" {}", self.smt_eq(&element, &l, &r));
+                }
+                conj += ")";
+                conj
+            }
+            _ => format!("(= {} {})", left, right),
+        }
+    }
+
+    /// Bounds of an integer interval type (the constant expressions of an interval)
+    fn interval_bounds(&self, typ: &Type) -> Option<(Expr, Expr)> {
+        match typ {
+            Type::Interval(id) => Some(self.model.get(*id).unwrap().bounds(self.model)),
+            _ => match typ.get_type(self.model) {
+                Type::IntInterval(min, max) => Some((min.into(), max.into())),
+                _ => None,
+            },
+        }
+    }
+
+    /// Bounds of an integer interval value (for each index of the arrays)
+    fn smt_bounds(&self, typ: &Type, name: &str) -> Vec<String> {
+        if let Some((min, max)) = self.interval_bounds(typ) {
+            return vec![
+                format!("(>= {} {})", name, self.to_smt(&min, 0)),
+                format!("(<= {} {})", name, self.to_smt(&max, 0)),
+            ];
+        }
+        match typ {
+            Type::Array(index, element) => {
+                let all = index.get_type(self.model).all(self.model);
+                let first = format!("(select {} {})", name, self.to_smt(&all[0], 0));
+                let mut res = vec![];
+                for i in all.iter() {
+                    let cell = format!("(select {} {})", name, self.to_smt(i, 0));
+                    res.extend(self.smt_bounds(element, &cell));
+                    // the cells out of symbolic bounds are fixed (to the first one)
+                    if let Some(domain) = self.symbolic_domain(index, i.clone()) {
+                        let domain = self.to_smt(&domain, 0);
+                        let eq = self.smt_eq(element, &cell, &first);
+                        res.push(format!("(or {} {})", domain, eq));
+                    }
+                }
+                res
+            }
+            // the elements out of symbolic bounds are not in the set
+            Type::Set(element) => element
+                .get_type(self.model)
+                .all(self.model)
+                .into_iter()
+                .filter_map(|e| {
+                    let member = format!("(select {} {})", name, self.to_smt(&e, 0));
+                    let domain = self.symbolic_domain(element, e)?;
+                    Some(format!("(or {} (not {}))", self.to_smt(&domain, 0), member))
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// The constraint of a value of an interval with symbolic bounds
+    fn symbolic_domain(&self, typ: &Type, value: Expr) -> Option<Expr> {
+        match typ {
+            Type::Interval(id) => self.model.get(*id).unwrap().domain(self.model, value),
+            _ => None,
+        }
+    }
+
+    /// The quantified expression restricted to the parameters in an interval with symbolic bounds
+    /// (the quantifier is expanded up to the maximal bounds)
+    fn quantifier_body(&self, op: QtOperator, params: &[Parameter], e: &Expr) -> Expr {
+        let guards: Vec<Expr> = params
+            .iter()
+            .filter_map(|p| self.symbolic_domain(p.typ(), p.clone().into()))
+            .collect();
+        if guards.is_empty() {
+            return e.clone();
+        }
+        let guard = Expr::and(guards);
+        let neutral = match op {
+            QtOperator::Forall => return guard.implies(e.clone()),
+            QtOperator::Exists => return Expr::and(vec![guard, e.clone()]),
+            QtOperator::Sum => 0.into(),
+            QtOperator::Prod => 1.into(),
+            QtOperator::Min | QtOperator::Max => return e.clone(),
+        };
+        let expression = Expression::IfThenElse(
+            Box::new(guard),
+            Box::new(e.clone()),
+            vec![],
+            Box::new(neutral),
+        );
+        Expr::new(expression, None)
+    }
+
+    //------------------------- Set -------------------------
+
+    /// The set of the elements satisfying the condition on '__e'
+    fn smt_set(&self, element: &Type, condition: &str) -> String {
+        format!("(lambda ((__e {})) {})", self.to_sort(element), condition)
+    }
+
+    /// Membership of a value (false outside the domain of an interval)
+    fn smt_in(&self, element: &Type, set: &str, value: &str) -> String {
+        match element {
+            Type::IntInterval(min, max) => format!(
+                "(and (<= {} {}) (<= {} {}) (select {} {}))",
+                min, value, value, max, set, value
+            ),
+            _ => format!("(select {} {})", set, value),
+        }
+    }
+
+    //------------------------- Bit Vector -------------------------
+
+    /// The expression as a bit-vector of the type (the integers are converted)
+    fn to_smt_bv(&self, expr: &Expr, typ: &Type, state: usize) -> String {
+        let (width, _) = typ.bit_vector().unwrap();
+        if expr.get_type(self.model).is_bit_vector() {
+            return self.to_smt(expr, state);
+        }
+        match expr.expression() {
+            Expression::Int(value) => {
+                // the literal is in the range of the type: two's complement if negative
+                let value = *value as i128;
+                let value = if value < 0 {
+                    value + (1 << width)
+                } else {
+                    value
+                };
+                format!("(_ bv{} {})", value, width)
+            }
+            _ => format!("((_ int2bv {}) {})", width, self.to_smt(expr, state)),
+        }
+    }
+
+    /// The expression converted to the type (the integers to bit-vectors or reals)
+    fn to_smt_as(&self, expr: &Expr, typ: &Type, state: usize) -> String {
+        if typ.is_bit_vector() {
+            self.to_smt_bv(expr, typ, state)
+        } else if *typ == Type::Real && expr.get_type(self.model) != Type::Real {
+            match expr.expression() {
+                Expression::Int(value) if *value >= 0 => format!("{}.0", value),
+                _ => format!("(to_real {})", self.to_smt(expr, state)),
+            }
+        } else {
+            self.to_smt(expr, state)
+        }
+    }
+
+    fn bv_binary(&self, typ: &Type, op: BinaryOperator, left: &str, right: &str) -> String {
+        let (width, signed) = typ.bit_vector().unwrap();
+        let s = if signed { "s" } else { "u" };
+        let zero = format!("(_ bv0 {})", width);
+        let one = format!("(_ bv1 {})", width);
+        // the signed div and mod are Euclidean (as for Int): the remainder of bvsrem
+        // has the sign of the dividend, a negative one is corrected by |right|
+        let negative = |e: &str| format!("(bvslt {} {})", e, zero);
+        let rem = format!("(bvsrem {} {})", left, right);
+        match op {
+            BinaryOperator::Eq => format!("(= {} {})", left, right),
+            BinaryOperator::Ne => format!("(not (= {} {}))", left, right),
+            BinaryOperator::Lt => format!("(bv{}lt {} {})", s, left, right),
+            BinaryOperator::Le => format!("(bv{}le {} {})", s, left, right),
+            BinaryOperator::Ge => format!("(bv{}ge {} {})", s, left, right),
+            BinaryOperator::Gt => format!("(bv{}gt {} {})", s, left, right),
+            BinaryOperator::Min => {
+                format!("(ite (bv{}le {} {}) {} {})", s, left, right, left, right)
+            }
+            BinaryOperator::Max => {
+                format!("(ite (bv{}ge {} {}) {} {})", s, left, right, left, right)
+            }
+            BinaryOperator::BitAnd => format!("(bvand {} {})", left, right),
+            BinaryOperator::BitOr => format!("(bvor {} {})", left, right),
+            BinaryOperator::BitXor => format!("(bvxor {} {})", left, right),
+            BinaryOperator::Shl => format!("(bvshl {} {})", left, right),
+            BinaryOperator::Shr if signed => format!("(bvashr {} {})", left, right),
+            BinaryOperator::Shr => format!("(bvlshr {} {})", left, right),
+            BinaryOperator::IntDiv if signed => {
+                let div = format!("(bvsdiv {} {})", left, right);
+                format!(
+                    "(ite {} (ite {} (bvadd {} {}) (bvsub {} {})) {})",
+                    negative(&rem),
+                    negative(right),
+                    div,
+                    one,
+                    div,
+                    one,
+                    div
+                )
+            }
+            BinaryOperator::IntDiv => format!("(bvudiv {} {})", left, right),
+            BinaryOperator::Mod if signed => format!(
+                "(ite {} (ite {} (bvsub {} {}) (bvadd {} {})) {})",
+                negative(&rem),
+                negative(right),
+                rem,
+                right,
+                rem,
+                right,
+                rem
+            ),
+            BinaryOperator::Mod => format!("(bvurem {} {})", left, right),
+            _ => unreachable!("{} is not a bit-vector operator", op),
+        }
+    }
+
     //------------------------- Cst Declaration -------------------------
 
     fn declare_dec_cst(&mut self, dec: &Declaration) {
@@ -155,10 +452,6 @@ impl<'a> Solver<'a> {
         let typ = dec.get_type(self.model);
         let sort = self.to_sort(&typ);
         self.smt.declare_const(&name, &sort).unwrap();
-        if let Type::IntInterval(min, max) = typ {
-            self.smt.assert(&format!("(>= {} {})", name, min)).unwrap();
-            self.smt.assert(&format!("(<= {} {})", name, max)).unwrap();
-        }
     }
 
     fn declare_dec_csts(&mut self) {
@@ -169,8 +462,38 @@ impl<'a> Solver<'a> {
         }
     }
 
+    /// The bounds of an interval can use the other constants: asserted once they are all declared
+    fn define_cst_bounds(&mut self) {
+        for d in self.model.declarations().iter() {
+            if d.is_constant() {
+                for bound in self.smt_bounds(d.typ(), &Self::cst_dec_name(d)) {
+                    self.smt.assert(&bound).unwrap();
+                }
+            }
+        }
+        for fun in self.model.fun_decs().iter() {
+            if fun.is_constant() {
+                if let Some(bound) = self.fun_bounds(fun, 0) {
+                    self.smt.assert(&bound).unwrap();
+                }
+            }
+        }
+    }
+
     //------------------------- Cst Function -------------------------
 
+    /// Bounds of the values of an integer interval function
+    fn fun_bounds(&self, fun: &FunDec, state: usize) -> Option<String> {
+        let (min, max) = self.interval_bounds(fun.return_type())?;
+        let fun_params: Vec<Expr> = fun.parameters().iter().map(|p| p.clone().into()).collect();
+        let fun_app = Expr::apply(fun.id(), fun_params);
+        let min_e = fun_app.clone().ge(min);
+        let max_e = fun_app.le(max);
+        let e = Expr::and(vec![min_e, max_e]);
+        let e = QtOperator::Forall.new(fun.parameters().clone(), e).into();
+        Some(self.to_smt(&e, state))
+    }
+
     fn declare_fun_cst(&mut self, fun: &FunDec) {
         let name = Self::cst_fun_name(fun);
         let params = fun
@@ -182,16 +505,6 @@ impl<'a> Solver<'a> {
         let typ = fun.return_type().get_type(self.model);
         let sort = self.to_sort(&typ);
         self.smt.declare_fun(&name, &params, &sort).unwrap();
-        if let Type::IntInterval(min, max) = typ {
-            let fun_params: Vec<Expr> = fun.parameters().iter().map(|p| p.clone().into()).collect();
-            let fun_app = Expr::apply(fun.id(), fun_params);
-            let min_e = fun_app.clone().ge(min.into());
-            let max_e = fun_app.le(max.into());
-            let e = Expr::and(vec![min_e, max_e]);
-            let e = QtOperator::Forall.new(fun.parameters().clone(), e).into();
-            let smt = self.to_smt(&e, 0);
-            self.smt.assert(&smt).unwrap();
-        }
     }
 
     fn declare_fun_csts(&mut self) {
@@ -209,9 +522,8 @@ impl<'a> Solver<'a> {
         let typ = dec.get_type(self.model);
         let sort = self.to_sort(&typ);
         self.smt.declare_const(&name, &sort).unwrap();
-        if let Type::IntInterval(min, max) = typ {
-            self.smt.assert(&format!("(>= {} {})", name, min)).unwrap();
-            self.smt.assert(&format!("(<= {} {})", name, max)).unwrap();
+        for bound in self.smt_bounds(dec.typ(), &name) {
+            self.smt.assert(&bound).unwrap();
         }
     }
 
@@ -230,9 +542,8 @@ impl<'a> Solver<'a> {
         let typ = def.get_type(self.model);
         let sort = self.to_sort(&typ);
         self.smt.declare_const(&name, &sort).unwrap();
-        if let Type::IntInterval(min, max) = typ {
-            self.smt.assert(&format!("(>= {} {})", name, min)).unwrap();
-            self.smt.assert(&format!("(<= {} {})", name, max)).unwrap();
+        for bound in self.smt_bounds(def.typ(), &name) {
+            self.smt.assert(&bound).unwrap();
         }
     }
 
@@ -255,6 +566,54 @@ impl<'a> Solver<'a> {
         }
     }
 
+    //------------------------- Trace Aggregate -------------------------
+
+    fn declare_trace_vars(&mut self, state: usize) {
+        for (index, trace) in self.traces.iter().enumerate() {
+            let name = Self::trace_name(index, state);
+            let sort = self.to_sort(&trace.get_type(self.model));
+            self.smt.declare_const(&name, &sort).unwrap();
+        }
+    }
+
+    /// Value of the aggregated expression in a state
+    fn trace_value(&self, op: TraceOperator, expr: &Expr, state: usize) -> String {
+        let value = self.to_smt(expr, state);
+        match op {
+            TraceOperator::Count => format!("(ite {} 1 0)", value),
+            _ => value,
+        }
+    }
+
+    /// Accumulation of a value with the previous one
+    fn trace_accumulate(op: TraceOperator, previous: &str, value: &str) -> String {
+        match op {
+            TraceOperator::Sum | TraceOperator::Count => format!("(+ {} {})", previous, value),
+            TraceOperator::Min => {
+                format!("(ite (<= {} {}) {} {})", value, previous, value, previous)
+            }
+            TraceOperator::Max => {
+                format!("(ite (>= {} {}) {} {})", value, previous, value, previous)
+            }
+        }
+    }
+
+    /// acc(0) = e(0), acc(s) = acc(s-1) op e(s)
+    fn define_trace_vars(&mut self, state: usize) {
+        for (index, trace) in self.traces.iter().enumerate() {
+            if let Expression::Trace(op, expr) = trace.expression() {
+                let name = Self::trace_name(index, state);
+                let value = self.trace_value(*op, expr, state);
+                let acc = if state == 0 {
+                    value
+                } else {
+                    Self::trace_accumulate(*op, &Self::trace_name(index, state - 1), &value)
+                };
+                self.smt.assert(&format!("(= {} {})", name, acc)).unwrap();
+            }
+        }
+    }
+
     //------------------------- Var Function -------------------------
 
     fn declare_fun_var(&mut self, fun: &FunDec, state: usize) {
@@ -268,15 +627,8 @@ impl<'a> Solver<'a> {
         let typ = fun.return_type().get_type(self.model);
         let sort = self.to_sort(&typ);
         self.smt.declare_fun(&name, &params, &sort).unwrap();
-        if let Type::IntInterval(min, max) = typ {
-            let fun_params: Vec<Expr> = fun.parameters().iter().map(|p| p.clone().into()).collect();
-            let fun_app = Expr::apply(fun.id(), fun_params);
-            let min_e = fun_app.clone().ge(min.into());
-            let max_e = fun_app.le(max.into());
-            let e = Expr::and(vec![min_e, max_e]);
-            let e = QtOperator::Forall.new(fun.parameters().clone(), e).into();
-            let smt = self.to_smt(&e, state);
-            self.smt.assert(&smt).unwrap();
+        if let Some(bound) = self.fun_bounds(fun, state) {
+            self.smt.assert(&bound).unwrap();
         }
     }
 
@@ -296,7 +648,8 @@ impl<'a> Solver<'a> {
             // v[first] != v[second]
             let v_first = self.var_dec_name_from_id(id, first);
             let v_second = self.var_dec_name_from_id(id, second);
-            let e = format!("(not (= {} {}))", v_first, v_second);
+            let typ = self.model.get(id).unwrap().get_type(self.model);
+            let e = format!("(not {})", self.smt_eq(&typ, &v_first, &v_second));
             disj += &format!(" {}", e);
         }
         for var in self.model.ltl_variables() {
@@ -337,7 +690,8 @@ impl<'a> Solver<'a> {
             // v[first] = v[second]
             let v_first = self.var_dec_name_from_id(id, first);
             let v_second = self.var_dec_name_from_id(id, second);
-            let e = format!("(= {} {})", v_first, v_second);
+            let typ = self.model.get(id).unwrap().get_type(self.model);
+            let e = self.smt_eq(&typ, &v_first, &v_second);
             conj += &format!(" {}", e);
         }
         for id in self.model.var_function_ids() {
@@ -412,11 +766,69 @@ impl<'a> Solver<'a> {
         }
     }
 
+    //------------------------- Unsat Core -------------------------
+
+    /// Assert a named expression, the name refers to the model item
+    fn assert_tracked(&mut self, item: CoreItem, expr: &str) {
+        if !self.unsat_core {
+            self.smt.assert(expr).unwrap();
+            return;
+        }
+        let name = format!("{}__{}", item.tracker(), self.named);
+        self.named += 1;
+        self.smt
+            .assert(&format!("(! {} :named {})", expr, name))
+            .unwrap();
+    }
+
+    /// The model items of the unsat core (after an unsat check)
+    /// Raw command, the response is read until its parentheses are balanced
+    fn raw_command(&mut self, command: &str) -> String {
+        self.smt.write_line(command).unwrap();
+        self.smt.flush().unwrap();
+        let mut response = "".to_string();
+        let mut count = 0;
+        loop {
+            let s = self.smt.read_line().unwrap();
+            count += s.chars().filter(|&c| c == '(').count();
+            count -= s.chars().filter(|&c| c == ')').count();
+            response += &format!(" {}", s);
+            if count == 0 {
+                break;
+            }
+        }
+        response
+    }
+
+    pub fn unsat_core(&mut self) -> Vec<CoreItem> {
+        if !self.unsat_core {
+            return vec![];
+        }
+        let response = self.raw_command("(get-unsat-core)");
+        if response.trim().starts_with("(error") {
+            return vec![];
+        }
+        let mut res = vec![];
+        for name in response.split(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+            if let Some(item) = CoreItem::from_tracker(name) {
+                if !res.contains(&item) {
+                    res.push(item);
+                }
+            }
+        }
+        res
+    }
+
     //------------------------- Init -------------------------
 
     fn define_init(&mut self, init: &Initial) {
         let x = self.to_smt(init.expr(), 0);
-        self.smt.assert(&x).unwrap()
+        let item = if self.model.property_initial() == Some(init.id()) {
+            CoreItem::Property
+        } else {
+            CoreItem::Initial(init.id())
+        };
+        self.assert_tracked(item, &x);
     }
 
     fn define_inits(&mut self) {
@@ -429,7 +841,7 @@ impl<'a> Solver<'a> {
 
     fn define_invariant(&mut self, inv: &Invariant, state: usize) {
         let x = self.to_smt(inv.expr(), state);
-        self.smt.assert(&x).unwrap();
+        self.assert_tracked(CoreItem::Invariant(inv.id()), &x);
     }
 
     fn define_invariants(&mut self, state: usize) {
@@ -441,6 +853,10 @@ impl<'a> Solver<'a> {
     //------------------------- Transition -------------------------
 
     fn define_transitions(&mut self, state: usize) {
+        if self.unsat_core {
+            self.define_tracked_transitions(state);
+            return;
+        }
         let mut v = vec![];
         for t in self.model.transitions() {
             v.push(t.expr().clone());
@@ -448,31 +864,48 @@ impl<'a> Solver<'a> {
         let len = v.len();
         if len == 1 {
             let e = &v[0];
-            self.smt.assert(&self.to_smt(&e, state)).unwrap();
+            let x = self.to_smt(&e, state);
+            self.smt.assert(&x).unwrap();
         } else if len > 1 {
             let e = Expr::or(v);
-            self.smt.assert(&self.to_smt(&e, state)).unwrap();
+            let x = self.to_smt(&e, state);
+            self.smt.assert(&x).unwrap();
         } else {
             self.smt.assert("false").unwrap();
         }
     }
 
+    /// Each transition is selected by a boolean and tracked on its own,
+    /// so that the unsat core names the blocked transitions
+    fn define_tracked_transitions(&mut self, state: usize) {
+        let mut selectors = vec![];
+        for t in self.model.transitions() {
+            let name = Self::transition_name(t, state);
+            self.smt.declare_const(&name, "Bool").unwrap();
+            let x = self.to_smt(t.expr(), state);
+            self.assert_tracked(
+                CoreItem::Transition(t.id()),
+                &format!("(=> {} {})", name, x),
+            );
+            selectors.push(name);
+        }
+        match selectors.len() {
+            0 => self.smt.assert("false").unwrap(),
+            1 => self.smt.assert(&selectors[0]).unwrap(),
+            _ => self
+                .smt
+                .assert(&format!("(or {})", selectors.join(" ")))
+                .unwrap(),
+        }
+    }
+
     //------------------------- Trigger -------------------------
 
     fn define_triggers(&mut self, state: usize) {
-        let mut v = vec![];
+        // the conjunction is asserted trigger by trigger (for the unsat core)
         for t in self.model.triggers() {
-            v.push(t.expr().clone());
-        }
-        let len = v.len();
-        if len == 1 {
-            let e = &v[0];
-            self.smt.assert(&self.to_smt(&e, state)).unwrap();
-        } else if len > 1 {
-            let e = Expr::and(v);
-            self.smt.assert(&self.to_smt(&e, state)).unwrap();
-        } else {
-            self.smt.assert("true").unwrap();
+            let x = self.to_smt(t.expr(), state);
+            self.assert_tracked(CoreItem::Trigger(t.id()), &x);
         }
     }
 
@@ -763,7 +1196,7 @@ impl<'a> Solver<'a> {
                 .unwrap();
             let expr = prop.id().into();
             let e = self.to_smt(&expr, 0);
-            self.smt.assert(&e).unwrap();
+            self.assert_tracked(CoreItem::Property, &e);
         }
     }
 
@@ -786,14 +1219,145 @@ impl<'a> Solver<'a> {
             }
             Expression::PrefixUnary(op, kid) => match op {
                 PrefixUnaryOperator::Not => format!("(not {})", self.to_smt(kid, state)),
+                PrefixUnaryOperator::Neg if kid.get_type(self.model).is_bit_vector() => {
+                    format!("(bvneg {})", self.to_smt(kid, state))
+                }
                 PrefixUnaryOperator::Neg => format!("(- {})", self.to_smt(kid, state)),
+                PrefixUnaryOperator::BitNot => format!("(bvnot {})", self.to_smt(kid, state)),
+                PrefixUnaryOperator::Abs if kid.get_type(self.model) == Type::Real => {
+                    let e = self.to_smt(kid, state);
+                    format!("(ite (>= {} 0.0) {} (- {}))", e, e, e)
+                }
+                PrefixUnaryOperator::Abs => format!("(abs {})", self.to_smt(kid, state)),
+                PrefixUnaryOperator::ToReal => format!("(to_real {})", self.to_smt(kid, state)),
+                PrefixUnaryOperator::ToInt => format!("(to_int {})", self.to_smt(kid, state)),
+                PrefixUnaryOperator::Succ
+                | PrefixUnaryOperator::Pred
+                | PrefixUnaryOperator::Ord => match kid.get_type(self.model) {
+                    Type::Enumerate(id) => format!(
+                        "({} {})",
+                        self.enumerate_fun_name(*op, id),
+                        self.to_smt(kid, state)
+                    ),
+                    _ => panic!(),
+                },
+                PrefixUnaryOperator::Card => {
+                    let set = self.to_smt(kid, state);
+                    let mut res = "(+ 0".to_string();
+                    if let Some(element) = kid.get_type(self.model).set() {
+                        for e in element.all(self.model) {
+                            let e = self.to_smt(&e, state);
+                            res += &format!(" (ite (select {} {}) 1 0)", set, e);
+                        }
+                    }
+                    res += ")";
+                    res
+                }
             },
-            Expression::Binary(left, op, right) => {
+            Expression::Binary(left, op @ (BinaryOperator::Eq | BinaryOperator::Ne), right)
+                if matches!(
+                    right.expression(),
+                    Expression::ArrayValue(_) | Expression::SetValue(_)
+                ) =>
+            {
+                // element by element, to convert the values to the element type
+                let mut v = vec![];
+                match (right.expression(), left.get_type(self.model)) {
+                    (Expression::ArrayValue(list), _) => {
+                        for (i, value) in list.iter() {
+                            let index = Expression::Index(left.clone(), Box::new(i.clone()));
+                            v.push(Expr::from(index).eq(value.clone()));
+                        }
+                    }
+                    (Expression::SetValue(list), Type::Set(element)) => {
+                        for e in element.all(self.model) {
+                            let member: Expr =
+                                BinaryOperator::In.new(e.clone(), *left.clone()).into();
+                            if list.iter().any(|x| x.is_same(&e)) {
+                                v.push(member);
+                            } else {
+                                v.push(member.not());
+                            }
+                        }
+                    }
+                    _ => panic!(),
+                }
+                let e = Expr::and(v);
+                match op {
+                    BinaryOperator::Eq => self.to_smt(&e, state),
+                    _ => format!("(not {})", self.to_smt(&e, state)),
+                }
+            }
+            Expression::Binary(left, BinaryOperator::In, right) => {
+                let element = right.get_type(self.model).set().unwrap().clone();
+                let set = self.to_smt(right, state);
+                self.smt_in(&element, &set, &self.to_smt(left, state))
+            }
+            Expression::Binary(left, op, right)
+                if [
+                    BinaryOperator::Union,
+                    BinaryOperator::Inter,
+                    BinaryOperator::Diff,
+                ]
+                .contains(op) =>
+            {
+                let element = expr.get_type(self.model).set().unwrap().clone();
                 let left = self.to_smt(left, state);
                 let right = self.to_smt(right, state);
+                let condition = match op {
+                    BinaryOperator::Union => {
+                        format!("(or (select {} __e) (select {} __e))", left, right)
+                    }
+                    BinaryOperator::Inter => {
+                        format!("(and (select {} __e) (select {} __e))", left, right)
+                    }
+                    _ => format!("(and (select {} __e) (not (select {} __e)))", left, right),
+                };
+                self.smt_set(&element, &condition)
+            }
+            Expression::Binary(left, op, right)
+                if [
+                    BinaryOperator::Lt,
+                    BinaryOperator::Le,
+                    BinaryOperator::Ge,
+                    BinaryOperator::Gt,
+                ]
+                .contains(op)
+                    && matches!(left.get_type(self.model), Type::Enumerate(_)) =>
+            {
+                // ordered enumerate: comparison of the indexes
+                let ord = |e: &Expr| {
+                    let op = PrefixUnaryOperator::Ord;
+                    self.to_smt(&Expr::new(op.new(e.clone()), None), state)
+                };
+                format!("({} {} {})", op, ord(left), ord(right))
+            }
+            Expression::Binary(left, op, right)
+                if Expr::bit_vector_type(self.model, [left.as_ref(), right.as_ref()]).is_some() =>
+            {
+                let typ = Expr::bit_vector_type(self.model, [left.as_ref(), right.as_ref()]);
+                let typ = typ.unwrap();
+                let left = self.to_smt_bv(left, &typ, state);
+                let right = self.to_smt_bv(right, &typ, state);
+                self.bv_binary(&typ, *op, &left, &right)
+            }
+            Expression::Binary(left, op, right) => {
+                let typ = match op {
+                    BinaryOperator::Div => Type::Real,
+                    _ => {
+                        // the domain of both sets
+                        let t = left.get_type(self.model);
+                        match t.common_type(self.model, &right.get_type(self.model)) {
+                            Type::Undefined => t,
+                            common => common,
+                        }
+                    }
+                };
+                let left = self.to_smt_as(left, &typ, state);
+                let right = self.to_smt_as(right, &typ, state);
                 match op {
-                    BinaryOperator::Eq => format!("(= {} {})", left, right),
-                    BinaryOperator::Ne => format!("(not (= {} {}))", left, right),
+                    BinaryOperator::Eq => self.smt_eq(&typ, &left, &right),
+                    BinaryOperator::Ne => format!("(not {})", self.smt_eq(&typ, &left, &right)),
                     BinaryOperator::Lt => format!("(< {} {})", left, right),
                     BinaryOperator::Le => format!("(<= {} {})", left, right),
                     BinaryOperator::Ge => format!("(>= {} {})", left, right),
@@ -805,12 +1369,29 @@ impl<'a> Solver<'a> {
                     BinaryOperator::Max => {
                         format!("(ite (>= {} {}) {} {})", left, right, left, right)
                     }
+                    BinaryOperator::Div => format!("(/ {} {})", left, right),
+                    BinaryOperator::IntDiv => format!("(div {} {})", left, right),
+                    BinaryOperator::Mod => format!("(mod {} {})", left, right),
+                    _ => panic!(),
                 }
             }
+            Expression::Nary(op, list) if Expr::bit_vector_type(self.model, list).is_some() => {
+                let typ = Expr::bit_vector_type(self.model, list).unwrap();
+                let op = match op {
+                    NaryOperator::Add => "bvadd",
+                    NaryOperator::Sub => "bvsub",
+                    NaryOperator::Mul => "bvmul",
+                    _ => panic!(),
+                };
+                let mut l = list.iter().map(|e| self.to_smt_bv(e, &typ, state));
+                let first = l.next().unwrap();
+                l.fold(first, |res, e| format!("({} {} {})", op, res, e))
+            }
             Expression::Nary(op, list) => {
+                let typ = expr.get_type(self.model);
                 let mut l = "".to_string();
                 for e in list.iter() {
-                    let e = self.to_smt(e, state);
+                    let e = self.to_smt_as(e, &typ, state);
                     l += &format!(" {}", e);
                 }
                 match op {
@@ -846,13 +1427,51 @@ impl<'a> Solver<'a> {
                     } else {
                         Self::var_fun_name(f, state)
                     };
-                    for p in params.iter() {
-                        res += &format!(" {}", self.to_smt(p, state));
+                    for (p, param) in params.iter().zip(f.parameters().iter()) {
+                        let typ = param.get_type(self.model);
+                        res += &format!(" {}", self.to_smt_as(p, &typ, state));
                     }
                     format!("({})", res)
                 }
                 _ => panic!(),
             },
+            Expression::Index(array, index) => format!(
+                "(select {} {})",
+                self.to_smt(array, state),
+                self.to_smt(index, state)
+            ),
+            Expression::Update(array, index, value) => {
+                let value = match array.get_type(self.model) {
+                    Type::Array(_, element) => self.to_smt_as(value, &element, state),
+                    _ => self.to_smt(value, state),
+                };
+                format!(
+                    "(store {} {} {})",
+                    self.to_smt(array, state),
+                    self.to_smt(index, state),
+                    value
+                )
+            }
+            Expression::SetValue(list) => {
+                let element = expr.get_type(self.model).set().unwrap().clone();
+                let mut condition = "(or false".to_string();
+                for e in list.iter() {
+                    condition += &format!(" (= __e {})", self.to_smt(e, state));
+                }
+                condition += ")";
+                self.smt_set(&element, &condition)
+            }
+            Expression::ArrayValue(list) => {
+                let sort = self.to_sort(&expr.get_type(self.model));
+                let (_, first) = list.first().unwrap();
+                let mut res = format!("((as const {}) {})", sort, self.to_smt(first, state));
+                for (i, v) in list.iter() {
+                    let i = self.to_smt(i, state);
+                    let v = self.to_smt(v, state);
+                    res = format!("(store {} {} {})", res, i, v);
+                }
+                res
+            }
             Expression::As(kid, typ, default) => {
                 if let Type::IntInterval(min, max) = typ {
                     let k = self.to_smt(kid, state);
@@ -899,13 +1518,15 @@ impl<'a> Solver<'a> {
             }
             //
             Expression::IfThenElse(c, t, list, e) => {
+                let typ = expr.get_type(self.model);
+                let branch = |e: &Expr| self.to_smt_as(e, &typ, state);
                 let c = self.to_smt(c, state);
-                let t = self.to_smt(t, state);
+                let t = branch(t);
                 let l = list
                     .iter()
-                    .map(|(c, e)| (self.to_smt(c, state), self.to_smt(e, state)))
+                    .map(|(c, e)| (self.to_smt(c, state), branch(e)))
                     .collect::<Vec<_>>();
-                let e = self.to_smt(e, state);
+                let e = branch(e);
                 let mut res = e;
                 for (x, y) in l.iter().rev() {
                     res = format!("(ite {} {} {})", x, y, res);
@@ -913,8 +1534,15 @@ impl<'a> Solver<'a> {
                 res = format!("(ite {} {} {})", c, t, res);
                 res
             }
+            Expression::Match(e, cases, default) => {
+                self.to_smt(&Expr::match_to_ite(e, cases, default), state)
+            }
+            Expression::Let(p, e, body) => {
+                self.to_smt(&body.substitute(&p.clone().into(), e), state)
+            }
             Expression::Quantifier(op, params, e) => {
-                let exprs = Expr::combine_all(self.model, params, e);
+                let e = self.quantifier_body(*op, params, e);
+                let exprs = Expr::combine_all(self.model, params, &e);
                 let mut res = "(".to_string();
                 res += match op {
                     QtOperator::Forall => "and",
@@ -930,10 +1558,21 @@ impl<'a> Solver<'a> {
                 res + ")"
             }
             //
+            Expression::Trace(op, e) => {
+                match self.traces.iter().position(|x| x.is_same(expr)) {
+                    Some(index) => Self::trace_name(index, self.transitions),
+                    // not in the model (substituted): accumulated inline
+                    None => (1..self.states()).fold(self.trace_value(*op, e, 0), |acc, s| {
+                        Self::trace_accumulate(*op, &acc, &self.trace_value(*op, e, s))
+                    }),
+                }
+            }
+            //
             Expression::LTLunary(_, _) => panic!(),
             Expression::LTLbinary(_, _, _) => panic!(),
             Expression::LTLVariable(id) => Self::ltl_var_name_from_id(self, *id, state),
             //
+            Expression::First(_) | Expression::Last(_) => panic!(),
             Expression::Unresolved(_) => panic!(),
         }
     }
@@ -946,6 +1585,7 @@ impl<'a> Solver<'a> {
             .unwrap();
         self.declare_dec_csts();
         self.declare_fun_csts();
+        self.define_cst_bounds();
 
         for state in 0..number {
             self.smt
@@ -996,6 +1636,15 @@ impl<'a> Solver<'a> {
             .unwrap();
         self.declare_dec_csts();
         self.declare_fun_csts();
+        self.define_cst_bounds();
+
+        // Trace aggregates: the last accumulator is used by the property and the objectives
+        if !self.traces.is_empty() {
+            self.smt.add_comment("---------- Trace ----------").unwrap();
+            for state in 0..self.states() {
+                self.declare_trace_vars(state);
+            }
+        }
 
         for state in 0..self.states() {
             self.smt
@@ -1011,6 +1660,11 @@ impl<'a> Solver<'a> {
             self.define_def_vars(state);
         }
 
+        // Trace aggregates
+        for state in 0..self.states() {
+            self.define_trace_vars(state);
+        }
+
         // Init
         self.smt.add_comment("---------- Init ----------").unwrap();
         self.define_inits();
@@ -1060,6 +1714,9 @@ impl<'a> Solver<'a> {
         self.declare_fun_vars(state);
         // LTL Variables
         self.declare_ltl_non_loop_vars(state);
+        // Trace aggregates
+        self.declare_trace_vars(state);
+        self.define_trace_vars(state);
 
         // Invariants
         self.smt
@@ -1240,59 +1897,195 @@ impl<'a> Solver<'a> {
 
     //------------------------- Optimize -------------------------
 
+    fn objective_name(index: usize) -> String {
+        format!("__objective_{}", index)
+    }
+
+    /// The objectives are optimized in lexicographic order (z3 default priority)
     pub fn add_optimization(&mut self) {
-        if let Some(optimization) = self.model.search().search_type().optimization() {
+        let optimizations = self.model.search().search_type().optimizations();
+        if !optimizations.is_empty() {
             self.smt
                 .add_comment("---------- Optimization ----------")
                 .unwrap();
+        }
+        for (i, optimization) in optimizations.iter().enumerate() {
+            let name = Self::objective_name(i);
             let objective = &optimization.objective;
             let bound = &optimization.bound;
             let typ = objective.get_type(self.model);
             let sort = self.to_sort(&typ);
-            self.smt.declare_const("__objective", &sort).unwrap();
+            self.smt.declare_const(&name, &sort).unwrap();
             self.smt
-                .assert(&format!("(= __objective {})", self.to_smt(objective, 0)))
+                .assert(&format!("(= {} {})", name, self.to_smt(objective, 0)))
                 .unwrap();
-            if optimization.minimize {
+            let op = if optimization.minimize { ">=" } else { "<=" };
+            if let Some(bound) = bound {
                 self.smt
-                    .assert(&format!("(>= __objective {})", self.to_smt(bound, 0)))
+                    .assert(&format!("({} {} {})", op, name, self.to_smt(bound, 0)))
                     .unwrap();
-                self.smt.minimize("__objective").unwrap()
             } else {
-                self.smt
-                    .assert(&format!("(<= __objective {})", self.to_smt(bound, 0)))
-                    .unwrap();
-                self.smt.maximize("__objective").unwrap()
+                self.unbounded.push(i);
+            }
+            if optimization.minimize {
+                self.smt.minimize(&name).unwrap()
+            } else {
+                self.smt.maximize(&name).unwrap()
             }
         }
     }
 
-    pub fn add_best_objective_constraint(&mut self, best_objective: &Expr) {
-        if let Some(optimization) = self.model.search().search_type().optimization() {
-            if optimization.minimize {
-                self.smt
-                    .assert(&format!(
-                        "(< __objective {})",
-                        self.to_smt(best_objective, 0)
-                    ))
-                    .unwrap();
+    /// The first objective without bound whose optimum is infinite (after a sat check)
+    pub fn unbounded_objective(&mut self) -> Option<usize> {
+        let unbounded = self.unbounded.clone();
+        if unbounded.is_empty() {
+            return None;
+        }
+        // (objectives (__objective_0 (* (- 1) oo)) (__objective_1 3))
+        let response = self.raw_command("(get-objectives)");
+        if response.trim().starts_with("(error") {
+            return None;
+        }
+        let infinite = Self::infinite_objectives(&response);
+        unbounded
+            .into_iter()
+            .find(|i| infinite.contains(&Self::objective_name(*i)))
+    }
+
+    /// The objectives of a get-objectives response whose value has an infinite part
+    fn infinite_objectives(response: &str) -> Vec<String> {
+        let mut res = vec![];
+        for sexpr in SExpr::parse(response) {
+            // the entries may be preceded by 'objectives'
+            if let SExpr::List(entries) = sexpr {
+                for entry in entries {
+                    if let SExpr::List(pair) = entry {
+                        if let [SExpr::Atom(name), value] = pair.as_slice() {
+                            if value.infinite_part().is_some_and(|x| x != 0.0) {
+                                res.push(name.clone());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    /// Lexicographically better than the best objectives
+    pub fn add_best_objective_constraint(&mut self, best_objectives: &[Expr]) {
+        let optimizations = self.model.search().search_type().optimizations();
+        // (o1 better b1) or (o1 = b1 and o2 better b2) or ...
+        let mut disj = vec![];
+        let mut equals = vec![];
+        for (i, (optimization, best)) in optimizations.iter().zip(best_objectives).enumerate() {
+            let name = Self::objective_name(i);
+            let best = self.to_smt(best, 0);
+            let op = if optimization.minimize { "<" } else { ">" };
+            let better = format!("({} {} {})", op, name, best);
+            disj.push(format!("(and {} {})", equals.join(" "), better));
+            equals.push(format!("(= {} {})", name, best));
+        }
+        if !disj.is_empty() {
+            self.smt
+                .assert(&format!("(or {})", disj.join(" ")))
+                .unwrap();
+        }
+    }
+
+    //------------------------- Pareto -------------------------
+
+    /// The pareto objectives, without optimization by the solver
+    pub fn add_pareto_objectives(&mut self) {
+        let pareto = match self.model.search().search_type().pareto() {
+            Some(pareto) => pareto,
+            None => return,
+        };
+        self.smt
+            .add_comment("---------- Pareto ----------")
+            .unwrap();
+        for (i, objective) in pareto.objectives.iter().enumerate() {
+            let name = Self::objective_name(i);
+            let typ = objective.get_type(self.model);
+            let sort = self.to_sort(&typ);
+            self.smt.declare_const(&name, &sort).unwrap();
+            self.smt
+                .assert(&format!("(= {} {})", name, self.to_smt(objective, 0)))
+                .unwrap();
+        }
+    }
+
+    /// The pareto objectives are optimized in order: the optimum is a pareto point
+    pub fn add_pareto_optimization(&mut self) {
+        let pareto = match self.model.search().search_type().pareto() {
+            Some(pareto) => pareto,
+            None => return,
+        };
+        for i in 0..pareto.objectives.len() {
+            let name = Self::objective_name(i);
+            if pareto.minimize {
+                self.smt.minimize(&name).unwrap()
             } else {
-                self.smt
-                    .assert(&format!(
-                        "(> __objective {})",
-                        self.to_smt(best_objective, 0)
-                    ))
-                    .unwrap();
+                self.smt.maximize(&name).unwrap()
             }
+            self.unbounded.push(i);
+        }
+    }
+
+    /// The pareto objectives, compared with a point: (better or equal, strictly better)
+    fn pareto_comparisons(&self, point: &[Expr]) -> (Vec<String>, Vec<String>) {
+        let minimize = match self.model.search().search_type().pareto() {
+            Some(pareto) => pareto.minimize,
+            None => return (vec![], vec![]),
+        };
+        let (weak, strict) = if minimize { ("<=", "<") } else { (">=", ">") };
+        let mut weaks = vec![];
+        let mut stricts = vec![];
+        for (i, value) in point.iter().enumerate() {
+            let name = Self::objective_name(i);
+            let value = self.to_smt(value, 0);
+            weaks.push(format!("({} {} {})", weak, name, value));
+            stricts.push(format!("({} {} {})", strict, name, value));
+        }
+        (weaks, stricts)
+    }
+
+    /// The pareto objectives dominate the point
+    pub fn add_dominate_constraint(&mut self, point: &[Expr]) {
+        let (weaks, stricts) = self.pareto_comparisons(point);
+        if !stricts.is_empty() {
+            self.smt
+                .assert(&format!(
+                    "(and {} (or {}))",
+                    weaks.join(" "),
+                    stricts.join(" ")
+                ))
+                .unwrap();
+        }
+    }
+
+    /// The pareto objectives are neither dominated by nor equal to the point
+    pub fn add_not_dominated_constraint(&mut self, point: &[Expr]) {
+        let (_, stricts) = self.pareto_comparisons(point);
+        if !stricts.is_empty() {
+            self.smt
+                .assert(&format!("(or {})", stricts.join(" ")))
+                .unwrap();
         }
     }
 
     //-------------------------  -------------------------
 
     fn remove_solution(&mut self, solution: &Solution) {
+        self.remove_projected_solution(solution, &[]);
+    }
+
+    /// Block the values of the solution on the projection (all the declarations if empty)
+    pub fn remove_projected_solution(&mut self, solution: &Solution, projection: &[DeclarationId]) {
+        let projected = |id: &DeclarationId| projection.is_empty() || projection.contains(id);
         let mut conj = vec![];
         // Cst
-        for (id, expr) in solution.cst_dec.iter() {
+        for (id, expr) in solution.cst_dec.iter().filter(|(id, _)| projected(id)) {
             if let Some(expr) = expr {
                 let dec: Expr = (*id).into();
                 let e = dec.eq(expr.clone());
@@ -1300,7 +2093,7 @@ impl<'a> Solver<'a> {
             }
         }
         // Var
-        for (id, v) in solution.var_dec.iter() {
+        for (id, v) in solution.var_dec.iter().filter(|(id, _)| projected(id)) {
             for state in 0..solution.states {
                 if let Some(expr) = &v[state] {
                     let dec: Expr = (*id).into();
@@ -1342,6 +2135,12 @@ impl<'a> Solver<'a> {
 
     //------------------------- Incremental -------------------------
 
+    /// Assert a constraint on the constants
+    pub fn add_constraint(&mut self, expr: &Expr) {
+        let e = self.to_smt(expr, 0);
+        self.smt.assert(&e).unwrap();
+    }
+
     pub fn push(&mut self) {
         self.smt.push().unwrap();
     }
@@ -1359,14 +2158,13 @@ impl<'a> Solver<'a> {
     //------------------------- Solve -------------------------
 
     pub fn check(&mut self) -> SatResult {
-        if self.model.search().search_type().optimization().is_none() {
+        if !self.model.search().search_type().is_optimization() {
             self.smt
                 .add_comment("---------- Check Sat ----------")
                 .unwrap();
             // let tactic = "(then (repeat (then propagate-ineqs simplify propagate-values solve-eqs elim-uncnstr)) smt)";
             // let res = self.smt.check_sat_using(tactic).unwrap();
-            let res = self.smt.check_sat().unwrap();
-            res
+            self.check_sat()
         } else {
             self.smt
                 .add_comment("---------- Check Sat (Optimize) ----------")
@@ -1374,12 +2172,89 @@ impl<'a> Solver<'a> {
             let tactic =
                 "(repeat (then propagate-ineqs simplify propagate-values solve-eqs elim-uncnstr))";
             self.smt.apply(tactic).unwrap();
-            let res = self.smt.check_sat().unwrap();
-            res
+            self.check_sat()
+        }
+    }
+
+    /// Check sat, unknown if the solving is cancelled
+    fn check_sat(&mut self) -> SatResult {
+        if !self.cancel.start(self.pid) {
+            return SatResult::Unknown;
+        }
+        self.smt.write_line("(check-sat)").unwrap();
+        self.smt.flush().unwrap();
+        let response = self.smt.read_line().unwrap();
+        self.cancel.end(self.pid);
+        match response.as_str() {
+            "sat" => SatResult::Sat,
+            "unsat" => SatResult::Unsat,
+            // unknown, or no response if the solver was killed
+            _ if response == "unknown" || self.cancel.is_cancelled() => SatResult::Unknown,
+            "" => panic!("z3 stopped without answering check-sat (is z3 in the PATH?)"),
+            _ => panic!("unexpected z3 response to check-sat: {}", response),
+        }
+    }
+
+    /// Value of a bit-vector literal (#x.., #b.. or _ bvN w)
+    ///
+    /// The widths are at most 63 bits (unsigned) or 64 bits (signed), so the value fits an i64.
+    fn bit_vector_value(eval: &str, width: usize, signed: bool) -> i64 {
+        let value = if let Some(hex) = eval.strip_prefix("#x") {
+            u64::from_str_radix(hex, 16).unwrap()
+        } else if let Some(bin) = eval.strip_prefix("#b") {
+            u64::from_str_radix(bin, 2).unwrap()
+        } else {
+            let re = Regex::new(r"^_ bv(\d+) \d+$").unwrap();
+            let caps = re.captures(eval).unwrap();
+            caps[1].parse::<u64>().unwrap()
+        } as i128;
+        if signed && value >= 1 << (width - 1) {
+            (value - (1 << width)) as i64
+        } else {
+            value as i64
+        }
+    }
+
+    /// The declared type of a variable, constant or definition (with its intervals)
+    fn declared_type(&self, expr: &Expr) -> Option<&'a Type> {
+        let model: &'a Model = self.model;
+        match expr.expression() {
+            Expression::Declaration(id) => Some(model.get(*id).unwrap().typ()),
+            Expression::Definition(id) => Some(model.get(*id).unwrap().typ()),
+            _ => None,
         }
     }
 
     pub fn eval(&mut self, expr: &Expr, state: usize) -> Option<Expr> {
+        if let Type::Array(index, _) = expr.get_type(self.model) {
+            // the cells out of the symbolic bounds of the index are not values
+            let declared_index = match self.declared_type(expr) {
+                Some(Type::Array(index, _)) => Some(index.as_ref()),
+                _ => None,
+            };
+            let mut list = vec![];
+            for i in index.all(self.model) {
+                let domain = declared_index.and_then(|t| self.symbolic_domain(t, i.clone()));
+                if let Some(domain) = domain {
+                    if let Expression::Bool(false) = self.eval(&domain, state)?.expression() {
+                        continue;
+                    }
+                }
+                let e = Expression::Index(Box::new(expr.clone()), Box::new(i.clone())).into();
+                list.push((i, self.eval(&e, state)?));
+            }
+            return Some(Expression::ArrayValue(list).into());
+        }
+        if let Type::Set(element) = expr.get_type(self.model) {
+            let mut list = vec![];
+            for e in element.all(self.model) {
+                let member = BinaryOperator::In.new(e.clone(), expr.clone()).into();
+                if let Expression::Bool(true) = self.eval(&member, state)?.expression() {
+                    list.push(e);
+                }
+            }
+            return Some(Expression::SetValue(list).into());
+        }
         let e = self.to_smt(expr, state).trim().to_string();
         let eval_init = self.smt.eval(&e).unwrap().trim().to_string();
         let eval = eval_init.replace(&['(', ')'][..], "").trim().to_string();
@@ -1441,12 +2316,95 @@ impl<'a> Solver<'a> {
                 crate::typing::typ::Type::IntInterval(_, _) => {
                     eval.replace(&[' '], "").parse::<i64>().unwrap().into()
                 }
+                crate::typing::typ::Type::Unsigned(width) => {
+                    Self::bit_vector_value(&eval, width, false).into()
+                }
+                crate::typing::typ::Type::Signed(width) => {
+                    Self::bit_vector_value(&eval, width, true).into()
+                }
                 //
                 crate::typing::typ::Type::Undefined => panic!(),
                 crate::typing::typ::Type::Unresolved(_, _) => panic!(),
                 crate::typing::typ::Type::Interval(_) => panic!(),
                 crate::typing::typ::Type::Function(_, _) => panic!(),
+                crate::typing::typ::Type::Array(_, _) => panic!(),
+                crate::typing::typ::Type::Set(_) => panic!(),
             })
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bit_vector_value_unsigned() {
+        assert_eq!(Solver::bit_vector_value("#xff", 8, false), 255);
+        assert_eq!(Solver::bit_vector_value("#b0101", 4, false), 5);
+        assert_eq!(Solver::bit_vector_value("_ bv300 16", 16, false), 300);
+        assert_eq!(
+            Solver::bit_vector_value("#x7fffffffffffffff", 63, false),
+            i64::MAX
+        );
+    }
+
+    #[test]
+    fn bit_vector_value_signed() {
+        assert_eq!(Solver::bit_vector_value("#xff", 8, true), -1);
+        assert_eq!(Solver::bit_vector_value("#x7f", 8, true), 127);
+        assert_eq!(Solver::bit_vector_value("#b1000", 4, true), -8);
+        assert_eq!(Solver::bit_vector_value("_ bv65535 16", 16, true), -1);
+        assert_eq!(Solver::bit_vector_value("#xffffffffffffffff", 64, true), -1);
+        assert_eq!(
+            Solver::bit_vector_value("#x8000000000000000", 64, true),
+            i64::MIN
+        );
+    }
+
+    #[test]
+    fn bit_vector_euclidean_div_mod() {
+        // the signed div and mod are the ones of Int
+        let source = "var a, b, q, r: Signed[8]
+            var x, y, z, w: Int
+            init I {
+                a = -7 and b = 2 and q = a div b and r = a mod b and
+                x = -7 div 2 and y = -7 mod 2 and z = 7 div -2 and w = 7 mod -2
+            }
+            trans t { a' = a and b' = -b and q' = a div b' and r' = a mod b' and
+                x' = x and y' = y and z' = z and w' = w }
+            search[1..1] truncated solve";
+        if let Some((model, response)) = crate::tests::solve_source(source) {
+            let trace = |name| crate::tests::trace(&model, &response, name);
+            assert_eq!(trace("x"), vec!["-4", "-4"]);
+            assert_eq!(trace("y"), vec!["1", "1"]);
+            assert_eq!(trace("z"), vec!["-3", "-3"]);
+            assert_eq!(trace("w"), vec!["1", "1"]);
+            assert_eq!(trace("q"), vec!["-4", "4"]);
+            assert_eq!(trace("r"), vec!["1", "1"]);
+        }
+        let source = "var a, b, q, r: Unsigned[8]
+            init I { a = 250 and b = 7 and q = a div b and r = a mod b }
+            search initial solve";
+        if let Some((model, response)) = crate::tests::solve_source(source) {
+            assert_eq!(crate::tests::trace(&model, &response, "q"), vec!["35"]);
+            assert_eq!(crate::tests::trace(&model, &response, "r"), vec!["5"]);
+        }
+    }
+
+    #[test]
+    fn unbounded_objectives() {
+        // maximize without bound
+        let response = "(objectives\n (__objective_0 oo)\n (__objective_1 3)\n)";
+        assert_eq!(Solver::infinite_objectives(response), vec!["__objective_0"]);
+        // minimize without bound
+        let response = "(objectives\n (__objective_0 (* (- 1) oo))\n)";
+        assert_eq!(Solver::infinite_objectives(response), vec!["__objective_0"]);
+        // bounded, strict bounds and values containing 'oo'
+        let response = "(objectives (__objective_0 (+ 3 (* (- 1) epsilon))) (__objective_1 (- 2)) (__objective_2 foo))";
+        assert!(Solver::infinite_objectives(response).is_empty());
+        // without the 'objectives' head
+        let response = "((__objective_0 5) (__objective_1 (* (- 1) oo)))";
+        assert_eq!(Solver::infinite_objectives(response), vec!["__objective_1"]);
+    }
+}
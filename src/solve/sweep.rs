@@ -0,0 +1,184 @@
+use std::sync::mpsc::channel;
+use std::time::Instant;
+use threadpool::ThreadPool;
+
+use super::*;
+use crate::error::Error;
+use crate::model::*;
+use crate::Args;
+use crate::ToLang;
+
+#[derive(Clone, Debug)]
+pub struct SweepResult {
+    pub value: i64,
+    pub response: Response,
+    pub duration: f64,
+}
+
+/// Split a `name=min..max` sweep.
+pub fn split_sweep(sweep: &str) -> Option<(String, i64, i64)> {
+    let (name, value) = split_definition(sweep)?;
+    let (min, max) = value.split_once("..")?;
+    let min = min.trim().parse().ok()?;
+    let max = max.trim().parse().ok()?;
+    Some((name, min, max))
+}
+
+#[allow(clippy::result_large_err)]
+fn sweep_one(model: &Model, args: &Args, name: &str, value: i64) -> Result<SweepResult, Error> {
+    let mut model = model.clone();
+    let mut args = args.clone();
+    args.verbose = 0;
+    args.threads = 1;
+    args.log_folder = args.log_folder.map(|folder| {
+        let folder = format!("{}/{}_{}", folder, name, value);
+        let _ = std::fs::create_dir_all(&folder);
+        folder
+    });
+
+    let start = Instant::now();
+    model.define_constant(name, &value.to_string())?;
+    let response = resolve(&mut model, &mut d_stuff::Pretty::new(), &args);
+    let duration = start.elapsed().as_secs_f64();
+    Ok(SweepResult {
+        value,
+        response,
+        duration,
+    })
+}
+
+/// Solve the model for each value (min..max) of the integer constant 'name'.
+/// The values are solved in parallel with 'threads' > 1 (all the cpus with 0).
+#[allow(clippy::result_large_err)]
+pub fn resolve_sweep(
+    model: &Model,
+    args: &Args,
+    name: &str,
+    min: i64,
+    max: i64,
+) -> Result<Vec<SweepResult>, Error> {
+    // Check the constant
+    let mut test = model.clone();
+    test.define_constant(name, &min.to_string())?;
+    test.define_constant(name, &max.to_string())?;
+
+    let mut results = vec![];
+    if args.threads == 1 {
+        for value in min..=max {
+            results.push(sweep_one(model, args, name, value)?);
+        }
+    } else {
+        let pool_size = if args.threads == 0 {
+            num_cpus::get()
+        } else {
+            args.threads as usize
+        };
+        let pool = ThreadPool::new(pool_size);
+        let (tx, rx) = channel();
+        for value in min..=max {
+            let tx = tx.clone();
+            let model = model.clone();
+            let args = args.clone();
+            let name = name.to_string();
+            pool.execute(move || {
+                tx.send(sweep_one(&model, &args, &name, value)).unwrap();
+            });
+        }
+        drop(tx);
+        results = rx.iter().collect::<Result<_, _>>()?;
+        results.sort_by_key(|r| r.value);
+    }
+    Ok(results)
+}
+
+//------------------------- Table -------------------------
+
+pub fn sweep_to_table(model: &Model, name: &str, results: &[SweepResult]) -> String {
+    let mut lines = vec![vec![
+        name.to_string(),
+        "response".to_string(),
+        "k".to_string(),
+        "objective".to_string(),
+        "time (s)".to_string(),
+    ]];
+    for result in results.iter() {
+        let k = match &result.response {
+            Response::NoSolution(k, _) => k.to_string(),
+            _ => match result.response.solution() {
+                Some(solution) => (solution.states - 1).to_string(),
+                None => "".to_string(),
+            },
+        };
+        let objective = match result.response.solution() {
+            Some(solution) => solution
+                .objectives
+                .iter()
+                .map(|x| x.to_lang(model))
+                .collect::<Vec<_>>()
+                .join(", "),
+            None => "".to_string(),
+        };
+        lines.push(vec![
+            result.value.to_string(),
+            result.response.kind().to_string(),
+            k,
+            objective,
+            format!("{:.3}", result.duration),
+        ]);
+    }
+
+    let mut widths = [0; 5];
+    for line in lines.iter() {
+        for (i, cell) in line.iter().enumerate() {
+            widths[i] = widths[i].max(cell.len());
+        }
+    }
+    let mut res = "".to_string();
+    for (n, line) in lines.iter().enumerate() {
+        let cells: Vec<String> = line
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| format!("{:width$}", cell, width = widths[i]))
+            .collect();
+        res += &format!("{}\n", cells.join(" | ").trim_end());
+        if n == 0 {
+            let sep: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+            res += &format!("{}\n", sep.join("-|-"));
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{load_source, test_args};
+
+    #[test]
+    fn split_sweep_bounds() {
+        assert_eq!(split_sweep("n=1..5"), Some(("n".to_string(), 1, 5)));
+        assert_eq!(split_sweep(" n = -2 .. 3 "), Some(("n".to_string(), -2, 3)));
+    }
+
+    #[test]
+    fn sweep_invalid_value() {
+        let model = load_source(
+            "cst c: Unsigned[4]
+            search initial solve",
+        )
+        .ok()
+        .unwrap();
+        let args = test_args();
+        assert!(sweep_one(&model, &args, "c", 16).is_err());
+        assert!(resolve_sweep(&model, &args, "c", 0, 16).is_err());
+        assert!(resolve_sweep(&model, &args, "d", 0, 1).is_err());
+    }
+
+    #[test]
+    fn split_sweep_invalid() {
+        assert_eq!(split_sweep("n=5"), None);
+        assert_eq!(split_sweep("n=a..5"), None);
+        assert_eq!(split_sweep("=1..5"), None);
+        assert_eq!(split_sweep("n"), None);
+    }
+}
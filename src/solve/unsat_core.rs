@@ -0,0 +1,108 @@
+use super::*;
+use crate::common::*;
+use crate::model::*;
+use crate::parser::Position;
+use crate::Args;
+use smt_sb::SatResult;
+
+//------------------------- Core Item -------------------------
+
+/// A model item tracked in the solver to explain an unsat answer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoreItem {
+    Initial(InitialId),
+    Invariant(InvariantId),
+    Transition(TransitionId),
+    Trigger(TriggerId),
+    Property,
+}
+
+impl CoreItem {
+    /// Prefix of the SMT names of the assertions of the item
+    pub fn tracker(&self) -> String {
+        match self {
+            CoreItem::Initial(id) => format!("_c_init_{}", id.index()),
+            CoreItem::Invariant(id) => format!("_c_inv_{}", id.index()),
+            CoreItem::Transition(id) => format!("_c_trans_{}", id.index()),
+            CoreItem::Trigger(id) => format!("_c_trig_{}", id.index()),
+            CoreItem::Property => "_c_prop".to_string(),
+        }
+    }
+
+    /// The item of an assertion name: <tracker>__<n>
+    pub fn from_tracker(name: &str) -> Option<CoreItem> {
+        let (tracker, _) = name.rsplit_once("__")?;
+        let index = |s: &str| s.parse::<usize>().ok();
+        if tracker == "_c_prop" {
+            Some(CoreItem::Property)
+        } else if let Some(i) = tracker.strip_prefix("_c_init_") {
+            index(i).map(|i| CoreItem::Initial(InitialId(i)))
+        } else if let Some(i) = tracker.strip_prefix("_c_inv_") {
+            index(i).map(|i| CoreItem::Invariant(InvariantId(i)))
+        } else if let Some(i) = tracker.strip_prefix("_c_trans_") {
+            index(i).map(|i| CoreItem::Transition(TransitionId(i)))
+        } else if let Some(i) = tracker.strip_prefix("_c_trig_") {
+            index(i).map(|i| CoreItem::Trigger(TriggerId(i)))
+        } else {
+            None
+        }
+    }
+
+    /// Name and position of the item in the model
+    pub fn naming(&self, model: &Model) -> (String, Option<Position>) {
+        match self {
+            CoreItem::Initial(id) => {
+                let x = model.get(*id).unwrap();
+                (x.name().to_string(), x.position().clone())
+            }
+            CoreItem::Invariant(id) => {
+                let x = model.get(*id).unwrap();
+                (x.name().to_string(), x.position().clone())
+            }
+            CoreItem::Transition(id) => {
+                let x = model.get(*id).unwrap();
+                (x.name().to_string(), x.position().clone())
+            }
+            CoreItem::Trigger(id) => {
+                let x = model.get(*id).unwrap();
+                (x.name().to_string(), x.position().clone())
+            }
+            CoreItem::Property => {
+                let position = model
+                    .property_initial()
+                    .and_then(|id| model.get(id).unwrap().position().clone());
+                ("prop".to_string(), position)
+            }
+        }
+    }
+}
+
+//------------------------- Core -------------------------
+
+/// The unsat core of a problem proved unsat by a solver without tracked assertions.
+/// The tracking slows the solving down: the problem ('create') is only checked again
+/// with it, once there is no solution.
+pub fn recheck_unsat_core(
+    model: &Model,
+    args: &Args,
+    log_file: Option<String>,
+    create: impl FnOnce(&mut Solver),
+) -> Vec<CoreItem> {
+    let mut solver = Solver::with_unsat_core(model, args, log_file);
+    create(&mut solver);
+    let core = match solver.check() {
+        SatResult::Unsat => solver.unsat_core(),
+        // cancelled
+        _ => vec![],
+    };
+    solver.exit();
+    core
+}
+
+/// "a ∧ b ∧ prop"
+pub fn core_to_lang(model: &Model, core: &[CoreItem]) -> String {
+    core.iter()
+        .map(|x| x.naming(model).0)
+        .collect::<Vec<_>>()
+        .join(" ∧ ")
+}
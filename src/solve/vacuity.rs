@@ -0,0 +1,490 @@
+use super::*;
+use crate::common::*;
+use crate::expr::*;
+use crate::model::*;
+use crate::parser::Position;
+use crate::search::*;
+use crate::typing::*;
+use crate::Args;
+use smt_sb::SatResult;
+
+#[derive(Clone, Debug)]
+pub struct VacuityResult {
+    /// atomic subformula of the property
+    pub atom: String,
+    pub position: Option<Position>,
+    /// responses with the atom replaced by true and by false
+    pub with_true: Response,
+    pub with_false: Response,
+}
+
+/// true if a trace is found, false if there is none, None otherwise
+fn verdict(response: &Response) -> Option<bool> {
+    match response {
+        Response::Solution(_)
+        | Response::BestSolution(_)
+        | Response::Solutions(_)
+        | Response::Unbounded(_, _) => Some(true),
+        Response::NoSolution(_, _) => Some(false),
+        Response::Unknown | Response::BoundReached => None,
+    }
+}
+
+impl VacuityResult {
+    /// The atom does not affect the verdict
+    pub fn is_vacuous(&self, response: &Response) -> bool {
+        let v = verdict(response);
+        v.is_some() && verdict(&self.with_true) == v && verdict(&self.with_false) == v
+    }
+}
+
+//------------------------- Atoms -------------------------
+
+/// Expression containing an atom
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AtomOwner {
+    Property,
+    LtlDefinition(LtlDefinitionId),
+}
+
+/// Atom identified by its owner and the indexes of the kids followed from the
+/// root of the owner, so the occurrences of a repeated subformula are distinct
+#[derive(Clone, Debug)]
+struct Atom {
+    owner: AtomOwner,
+    path: Vec<usize>,
+    expr: Expr,
+}
+
+/// Boolean and LTL operators of the property (the atoms are the other subformulas)
+fn is_connective(expr: &Expr) -> bool {
+    matches!(
+        expr.expression(),
+        Expression::PrefixUnary(PrefixUnaryOperator::Not, _)
+            | Expression::Binary(_, BinaryOperator::Implies, _)
+            | Expression::Nary(NaryOperator::And, _)
+            | Expression::Nary(NaryOperator::Or, _)
+            | Expression::LTLunary(_, _)
+            | Expression::LTLbinary(_, _, _)
+    )
+}
+
+/// Atoms of the property (not flattened): the LTL definitions used are
+/// followed once, an atom of a shared definition is given once
+fn atoms(
+    model: &Model,
+    owner: AtomOwner,
+    expr: &Expr,
+    path: &mut Vec<usize>,
+    visited: &mut Vec<LtlDefinitionId>,
+    res: &mut Vec<Atom>,
+) {
+    match expr.expression() {
+        Expression::LtlDefinition(id) => {
+            if !visited.contains(id) {
+                visited.push(*id);
+                let def = model.get(*id).unwrap().expr();
+                atoms(
+                    model,
+                    AtomOwner::LtlDefinition(*id),
+                    def,
+                    &mut vec![],
+                    visited,
+                    res,
+                );
+            }
+        }
+        _ if is_connective(expr) => {
+            for (i, kid) in expr.kids().into_iter().enumerate() {
+                path.push(i);
+                atoms(model, owner, kid, path, visited, res);
+                path.pop();
+            }
+        }
+        _ => res.push(Atom {
+            owner,
+            path: path.clone(),
+            expr: expr.clone(),
+        }),
+    }
+}
+
+/// Replace the subformula at 'path' by 'f' of it
+fn replace_path(expr: &Expr, path: &[usize], f: &dyn Fn(&Expr) -> Expr) -> Expr {
+    let (index, path) = match path.split_first() {
+        Some(split) => split,
+        None => return f(expr),
+    };
+    let replace = |i: usize, e: &Expr| {
+        if i == *index {
+            replace_path(e, path, f)
+        } else {
+            e.clone()
+        }
+    };
+    let expression = match expr.expression() {
+        Expression::PrefixUnary(op, kid) => Expression::PrefixUnary(*op, Box::new(replace(0, kid))),
+        Expression::Binary(left, op, right) => {
+            Expression::Binary(Box::new(replace(0, left)), *op, Box::new(replace(1, right)))
+        }
+        Expression::Nary(op, list) => Expression::Nary(
+            *op,
+            list.iter()
+                .enumerate()
+                .map(|(i, e)| replace(i, e))
+                .collect(),
+        ),
+        Expression::LTLunary(op, kid) => Expression::LTLunary(*op, Box::new(replace(0, kid))),
+        Expression::LTLbinary(left, op, right) => {
+            Expression::LTLbinary(Box::new(replace(0, left)), *op, Box::new(replace(1, right)))
+        }
+        _ => unreachable!("the path of an atom only goes through connectives"),
+    };
+    Expr::new(expression, expr.position().clone())
+}
+
+//------------------------- Selection -------------------------
+
+/// Replace each atom by '(s and v) or (not s and atom)', with 's' a selector
+/// constant of the atom and 'v' the value constant shared by the atoms.
+/// Return the selectors and the value.
+fn add_selectors(model: &mut Model, atoms: &[Atom]) -> (Vec<DeclarationId>, DeclarationId) {
+    let value = model.add_declaration(Declaration::new(true, "__vacuity_value", Type::Bool, None));
+    let mut selectors = vec![];
+    for (i, atom) in atoms.iter().enumerate() {
+        let selector = model.add_declaration(Declaration::new(
+            true,
+            format!("__vacuity_{}", i),
+            Type::Bool,
+            None,
+        ));
+        selectors.push(selector);
+        let select = |e: &Expr| {
+            Expr::or(vec![
+                Expr::and(vec![selector.into(), value.into()]),
+                Expr::and(vec![Expr::from(selector).not(), e.clone()]),
+            ])
+        };
+        match atom.owner {
+            AtomOwner::Property => {
+                let phi = replace_path(model.property().as_ref().unwrap(), &atom.path, &select);
+                model.set_property(phi);
+            }
+            AtomOwner::LtlDefinition(id) => {
+                let def = model.get(id).unwrap().expr().clone();
+                model.set_ltl_definition_expr(id, replace_path(&def, &atom.path, &select));
+            }
+        }
+    }
+    (selectors, value)
+}
+
+/// Constraint selecting the atom 'target' replaced by 'value'
+fn selection(
+    selectors: &[DeclarationId],
+    value_id: DeclarationId,
+    target: usize,
+    value: bool,
+) -> Expr {
+    let mut list: Vec<Expr> = selectors
+        .iter()
+        .enumerate()
+        .map(|(i, selector)| {
+            let selector: Expr = (*selector).into();
+            if i == target {
+                selector
+            } else {
+                selector.not()
+            }
+        })
+        .collect();
+    let value_expr: Expr = value_id.into();
+    list.push(value_expr.eq(Expr::new_bool(value, None)));
+    Expr::and(list)
+}
+
+//------------------------- Check -------------------------
+
+/// Check the assertions added by 'set' in a new scope, with the solution if sat.
+/// The scope is left open if unknown: the solver may have been killed.
+fn check_scope(
+    solver: &mut Solver,
+    complete: bool,
+    set: impl FnOnce(&mut Solver),
+) -> (SatResult, Option<Solution>) {
+    solver.push();
+    set(solver);
+    let result = solver.check();
+    if result == SatResult::Unknown {
+        return (result, None);
+    }
+    let solution = (result == SatResult::Sat).then(|| Solution::from_solver(solver, complete));
+    solver.pop();
+    (result, solution)
+}
+
+/// Check the selected atom on the current path as the incremental resolution does,
+/// None if there is no verdict with this number of transitions
+fn check_path(
+    solver: &mut Solver,
+    transitions: usize,
+    infinite: bool,
+    truncated: bool,
+    finite: bool,
+    complete: bool,
+) -> Option<Response> {
+    if truncated {
+        match check_scope(solver, false, |solver| solver.set_truncated()) {
+            (SatResult::Unknown, _) => return Some(Response::Unknown),
+            (_, Some(solution)) => return Some(Response::Solution(solution)),
+            _ => {}
+        }
+    }
+    if infinite && transitions > 0 {
+        match check_scope(solver, false, |solver| solver.set_infinite()) {
+            (SatResult::Unknown, _) => return Some(Response::Unknown),
+            (_, Some(solution)) => return Some(Response::Solution(solution)),
+            _ => {}
+        }
+    }
+    if finite {
+        let mut solutions: Vec<Solution> = Vec::new();
+        loop {
+            let solution = match check_scope(solver, true, |solver| solver.set_finite(&solutions)) {
+                (SatResult::Unknown, _) => return Some(Response::Unknown),
+                (_, Some(solution)) => solution,
+                _ => break,
+            };
+            match check_scope(solver, false, |solver| solver.set_finite_future(&solution)) {
+                (SatResult::Unknown, _) => return Some(Response::Unknown),
+                (SatResult::Unsat, _) => return Some(Response::Solution(solution)),
+                _ => solutions.push(solution),
+            }
+        }
+    }
+    if complete {
+        match check_scope(solver, false, |solver| solver.set_future()) {
+            (SatResult::Unknown, _) => return Some(Response::Unknown),
+            (SatResult::Unsat, _) => return Some(Response::NoSolution(transitions, vec![])),
+            _ => {}
+        }
+    }
+    None
+}
+
+//------------------------- Vacuity -------------------------
+
+/// Solve the model (not flattened) with each atomic subformula of the property
+/// replaced by true, then by false. The atoms are selected by constants of a
+/// single solver, each replacement being checked in its own scope, until every
+/// replacement has a verdict. The objectives, the enumeration and the pareto
+/// front are not searched: only the existence of a trace is checked.
+pub fn resolve_vacuity(model: &Model, args: &Args) -> Vec<VacuityResult> {
+    let phi = match model.property() {
+        Some(phi) => phi.clone(),
+        None => return vec![],
+    };
+    let mut list = vec![];
+    atoms(
+        model,
+        AtomOwner::Property,
+        &phi,
+        &mut vec![],
+        &mut vec![],
+        &mut list,
+    );
+    if list.is_empty() {
+        return vec![];
+    }
+
+    let mut model = model.clone();
+    let (selectors, value) = add_selectors(&mut model, &list);
+    model.flatten_ltl();
+    let (infinite, truncated, finite, complete, tn) = match model.search().path_type() {
+        PathType::Initial => (false, true, false, false, TransitionNumber::new(0, Some(0))),
+        PathType::Path {
+            infinite,
+            truncated,
+            finite,
+            complete,
+        } => (
+            infinite,
+            truncated,
+            finite,
+            complete,
+            model.search().transitions(),
+        ),
+    };
+    let search = Search::new(tn, model.search().path_type(), SearchType::Solve);
+    model.set_search(search);
+
+    // responses with the atom i replaced by true (2i) and by false (2i+1)
+    let mut responses: Vec<Option<Response>> = vec![None; 2 * list.len()];
+    let mut transitions = tn.min();
+    let mut solver = Solver::new(
+        &model,
+        args,
+        log_file(args.log_folder.clone(), "vacuity", transitions),
+    );
+    solver.create_path(transitions);
+
+    'search: loop {
+        if let Some(max) = tn.max() {
+            if transitions > max {
+                for response in responses.iter_mut().filter(|r| r.is_none()) {
+                    *response = Some(Response::BoundReached);
+                }
+                break;
+            }
+        }
+        for (variant, response) in responses.iter_mut().enumerate() {
+            if response.is_some() {
+                continue;
+            }
+            if args.is_cancelled() {
+                break 'search;
+            }
+            let (target, replacement) = (variant / 2, variant % 2 == 0);
+            solver.push();
+            solver
+                .add_comment(&format!(
+                    "vacuity atom {} = {} k={}",
+                    target, replacement, transitions
+                ))
+                .unwrap();
+            solver.add_constraint(&selection(&selectors, value, target, replacement));
+            match check_path(
+                &mut solver,
+                transitions,
+                infinite,
+                truncated,
+                finite,
+                complete,
+            ) {
+                // the solver is stopped
+                Some(Response::Unknown) => break 'search,
+                Some(r) => *response = Some(r),
+                None => {}
+            }
+            solver.pop();
+        }
+        if responses.iter().all(|r| r.is_some()) {
+            break;
+        }
+        transitions += 1;
+        solver.increment_path();
+    }
+    solver.exit();
+
+    let mut results = vec![];
+    for (i, atom) in list.iter().enumerate() {
+        let response = |r: &Option<Response>| r.clone().unwrap_or(Response::Unknown);
+        results.push(VacuityResult {
+            atom: atom.expr.to_lang(&model),
+            position: atom.expr.position().clone(),
+            with_true: response(&responses[2 * i]),
+            with_false: response(&responses[2 * i + 1]),
+        });
+    }
+    results
+}
+
+//------------------------- To Entry -------------------------
+
+pub fn vacuity_to_entry(response: &Response, results: &[VacuityResult]) -> d_stuff::Entry {
+    let mut messages = vec![];
+    for result in results.iter().filter(|r| r.is_vacuous(response)) {
+        messages.push(d_stuff::Message::new(
+            Some(d_stuff::Text::new(
+                "Vacuous",
+                termion::style::Reset.to_string(),
+                termion::color::Red.fg_str(),
+            )),
+            d_stuff::Text::new(
+                format!("'{}' does not affect the verdict", result.atom),
+                termion::style::Reset.to_string(),
+                termion::color::LightBlue.fg_str(),
+            ),
+        ));
+        if let Some(position) = &result.position {
+            messages.push(position.to_message());
+        }
+    }
+    let (status, text) = if verdict(response).is_none() {
+        (d_stuff::Status::Question, "NO VERDICT".to_string())
+    } else if messages.is_empty() {
+        (d_stuff::Status::Success, "NOT VACUOUS".to_string())
+    } else {
+        (d_stuff::Status::Failure, "VACUOUS".to_string())
+    };
+    d_stuff::Entry::new(
+        status,
+        d_stuff::Text::new(
+            "Vacuity ",
+            termion::style::Bold.to_string(),
+            termion::color::Blue.fg_str(),
+        ),
+        Some(d_stuff::Text::new(
+            text,
+            termion::style::Reset.to_string(),
+            termion::color::Red.fg_str(),
+        )),
+        messages,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::load_source;
+
+    fn property_atoms(model: &Model) -> Vec<Atom> {
+        let phi = model.property().clone().unwrap();
+        let mut list = vec![];
+        atoms(
+            model,
+            AtomOwner::Property,
+            &phi,
+            &mut vec![],
+            &mut vec![],
+            &mut list,
+        );
+        list
+    }
+
+    #[test]
+    fn vacuity_atoms_by_path() {
+        let model = load_source(
+            "var x: Int
+            ltl l = G(x > 0)
+            init I { x = 0 }
+            trans t { x' = x + 1 }
+            prop = F(x = 3) and (x = 3 implies l) and not l
+            search[0..3] truncated solve",
+        )
+        .ok()
+        .unwrap();
+        let list = property_atoms(&model);
+        let atoms: Vec<_> = list
+            .iter()
+            .map(|a| (a.owner, a.path.clone(), a.expr.to_lang(&model)))
+            .collect();
+        let l = model.ltl_definitions()[0].id();
+        // the repeated 'x = 3' gives two atoms, the shared definition one
+        assert_eq!(
+            atoms,
+            vec![
+                (AtomOwner::Property, vec![0, 0], "(x = 3)".to_string()),
+                (AtomOwner::Property, vec![1, 0], "(x = 3)".to_string()),
+                (AtomOwner::LtlDefinition(l), vec![0], "(x > 0)".to_string()),
+            ]
+        );
+        // only the selected occurrence is replaced
+        let phi = model.property().clone().unwrap();
+        let replaced = replace_path(&phi, &list[1].path, &|_| Expr::new_bool(true, None));
+        assert_eq!(
+            replaced.to_lang(&model),
+            "((F (x = 3)) and (true => l) and (not l))"
+        );
+    }
+}
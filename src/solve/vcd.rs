@@ -0,0 +1,220 @@
+use super::*;
+use crate::common::*;
+use crate::expr::*;
+use crate::model::*;
+use crate::typing::*;
+use fraction::ToPrimitive;
+
+//------------------------- Signal -------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Signal {
+    Bool,
+    Integer,
+    BitVector(usize),
+    Real,
+    Enumerate,
+}
+
+impl Signal {
+    fn new(model: &Model, typ: &Type) -> Self {
+        match typ.get_type(model) {
+            Type::Bool => Signal::Bool,
+            Type::Real => Signal::Real,
+            Type::Enumerate(_) => Signal::Enumerate,
+            Type::Unsigned(width) | Type::Signed(width) => Signal::BitVector(width),
+            _ => Signal::Integer,
+        }
+    }
+
+    fn declaration(&self) -> String {
+        match self {
+            Signal::Bool => "wire 1".into(),
+            Signal::Integer => "wire 64".into(),
+            Signal::BitVector(width) => format!("wire {}", width),
+            Signal::Real => "real 64".into(),
+            Signal::Enumerate => "string 1".into(),
+        }
+    }
+
+    fn value(&self, model: &Model, value: &Option<Expr>, code: &str) -> String {
+        match (self, value.as_ref().map(|e| e.expression())) {
+            (Signal::Bool, Some(Expression::Bool(true))) => format!("1{}", code),
+            (Signal::Bool, Some(Expression::Bool(false))) => format!("0{}", code),
+            (Signal::Bool, _) => format!("x{}", code),
+            (Signal::Integer, Some(Expression::Int(i))) => format!("b{:b} {}", i, code),
+            (Signal::Integer, _) => format!("bx {}", code),
+            (Signal::BitVector(width), Some(Expression::Int(i))) => {
+                let mask = u64::MAX >> (64 - width);
+                format!("b{:b} {}", *i as u64 & mask, code)
+            }
+            (Signal::BitVector(_), _) => format!("bx {}", code),
+            (Signal::Real, Some(Expression::Real(r))) => {
+                format!("r{} {}", r.to_f64().unwrap_or(f64::NAN), code)
+            }
+            (Signal::Real, Some(Expression::Int(i))) => format!("r{} {}", i, code),
+            (Signal::Real, _) => format!("rNaN {}", code),
+            (Signal::Enumerate, Some(expression @ Expression::EnumerateElement(_))) => {
+                format!("s{} {}", expression.to_lang(model), code)
+            }
+            (Signal::Enumerate, _) => format!("sx {}", code),
+        }
+    }
+}
+
+/// VCD identifier code of the n-th signal (printable ASCII characters).
+fn vcd_code(mut n: usize) -> String {
+    let mut res = String::new();
+    loop {
+        res.push((b'!' + (n % 94) as u8) as char);
+        n /= 94;
+        if n == 0 {
+            break;
+        }
+    }
+    res
+}
+
+/// The scalar signals of a trace: one by array cell or set member (`a[i]`)
+fn scalar_traces(
+    model: &Model,
+    name: String,
+    typ: &Type,
+    values: Vec<Option<Expr>>,
+) -> Vec<(String, Signal, Vec<Option<Expr>>)> {
+    match typ.get_type(model) {
+        Type::Array(index, element) => {
+            let mut res = vec![];
+            for i in index.all(model) {
+                let cells = values
+                    .iter()
+                    .map(|value| match value.as_ref().map(|e| e.expression()) {
+                        Some(Expression::ArrayValue(list)) => list
+                            .iter()
+                            .find(|(j, _)| j.is_same(&i))
+                            .map(|(_, v)| v.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                let name = format!("{}[{}]", name, i.to_lang(model));
+                res.extend(scalar_traces(model, name, &element, cells));
+            }
+            res
+        }
+        Type::Set(element) => element
+            .all(model)
+            .into_iter()
+            .map(|e| {
+                let members = values
+                    .iter()
+                    .map(|value| match value.as_ref().map(|e| e.expression()) {
+                        Some(Expression::SetValue(list)) => {
+                            Some(list.iter().any(|x| x.is_same(&e)).into())
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                (
+                    format!("{}[{}]", name, e.to_lang(model)),
+                    Signal::Bool,
+                    members,
+                )
+            })
+            .collect(),
+        typ => vec![(name, Signal::new(model, &typ), values)],
+    }
+}
+
+//------------------------- To VCD -------------------------
+
+impl Solution {
+    /// Value Change Dump of the trace (one time unit per state).
+    /// The arrays and the sets have one signal by cell or member.
+    /// The `__loop` signal is set on the state the last state loops to.
+    pub fn to_vcd(&self, model: &Model) -> String {
+        let traces: Vec<(String, Signal, Vec<Option<Expr>>)> = self
+            .traces(model)
+            .into_iter()
+            .flat_map(|(name, typ, values)| scalar_traces(model, name, &typ, values.clone()))
+            .collect();
+        let signals: Vec<Signal> = traces.iter().map(|(_, signal, _)| *signal).collect();
+        let loop_code = vcd_code(traces.len());
+
+        let mut res = "".to_string();
+        res += &format!("$version tatam {} $end\n", env!("CARGO_PKG_VERSION"));
+        if let Some(index) = self.loop_index {
+            res += &format!("$comment loop to state {} $end\n", index);
+        }
+        res += "$timescale 1s $end\n";
+        res += "$scope module trace $end\n";
+        for (i, (name, _, _)) in traces.iter().enumerate() {
+            res += &format!(
+                "$var {} {} {} $end\n",
+                signals[i].declaration(),
+                vcd_code(i),
+                name
+            );
+        }
+        res += &format!("$var wire 1 {} __loop $end\n", loop_code);
+        res += "$upscope $end\n";
+        res += "$enddefinitions $end\n";
+
+        let mut previous: Vec<Option<String>> = vec![None; traces.len() + 1];
+        for state in 0..self.states {
+            res += &format!("#{}\n", state);
+            if state == 0 {
+                res += "$dumpvars\n";
+            }
+            for (i, (_, signal, values)) in traces.iter().enumerate() {
+                let line = signal.value(model, &values[state], &vcd_code(i));
+                if previous[i].as_ref() != Some(&line) {
+                    res += &format!("{}\n", line);
+                    previous[i] = Some(line);
+                }
+            }
+            let line = if self.loop_index == Some(state) {
+                format!("1{}", loop_code)
+            } else {
+                format!("0{}", loop_code)
+            };
+            if previous[traces.len()].as_ref() != Some(&line) {
+                res += &format!("{}\n", line);
+                previous[traces.len()] = Some(line);
+            }
+            if state == 0 {
+                res += "$end\n";
+            }
+        }
+        res += &format!("#{}\n", self.states);
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vcd_code_single_character() {
+        assert_eq!(vcd_code(0), "!");
+        assert_eq!(vcd_code(1), "\"");
+        assert_eq!(vcd_code(93), "~");
+    }
+
+    #[test]
+    fn vcd_code_several_characters() {
+        assert_eq!(vcd_code(94), "!\"");
+        assert_eq!(vcd_code(95), "\"\"");
+        assert_eq!(vcd_code(94 * 94), "!!\"");
+    }
+
+    #[test]
+    fn vcd_code_unique() {
+        let mut codes = std::collections::HashSet::new();
+        for n in 0..10000 {
+            let code = vcd_code(n);
+            assert!(code.chars().all(|c| ('!'..='~').contains(&c)));
+            assert!(codes.insert(code));
+        }
+    }
+}
@@ -22,8 +22,15 @@ pub enum Type {
     Interval(IntervalId),
     //
     IntInterval(i64, i64),
+    /// bit-vectors of the width
+    Unsigned(usize),
+    Signed(usize),
     //
     Function(Vec<Type>, Box<Type>),
+    /// index type (enumerate or interval) and element type
+    Array(Box<Type>, Box<Type>),
+    /// element type (enumerate or interval)
+    Set(Box<Type>),
 }
 
 impl Type {
@@ -34,6 +41,10 @@ impl Type {
         }
     }
 
+    pub fn is_set(&self) -> bool {
+        matches!(self, Type::Set(_))
+    }
+
     pub fn is_bool(&self) -> bool {
         match self {
             Type::Bool => true,
@@ -49,6 +60,29 @@ impl Type {
         }
     }
 
+    pub fn is_bit_vector(&self) -> bool {
+        matches!(self, Type::Unsigned(_) | Type::Signed(_))
+    }
+
+    /// The width and the signedness of a bit-vector
+    pub fn bit_vector(&self) -> Option<(usize, bool)> {
+        match self {
+            Type::Unsigned(width) => Some((*width, false)),
+            Type::Signed(width) => Some((*width, true)),
+            _ => None,
+        }
+    }
+
+    /// Smallest and greatest values of a bit-vector
+    pub fn bit_vector_range(width: usize, signed: bool) -> (i128, i128) {
+        let width = width as u32;
+        if signed {
+            (-(1 << (width - 1)), (1 << (width - 1)) - 1)
+        } else {
+            (0, (1 << width) - 1)
+        }
+    }
+
     pub fn is_real(&self) -> bool {
         match self {
             Type::Real => true,
@@ -66,10 +100,21 @@ impl Type {
             Type::Enumerate(_) => true,
             Type::Interval(_) => true,
             Type::IntInterval(_, _) => true,
+            Type::Unsigned(_) => true,
+            Type::Signed(_) => true,
             Type::Function(_, _) => false,
+            Type::Array(_, _) => false,
+            Type::Set(_) => false,
         }
     }
 
+    pub fn is_index(&self) -> bool {
+        matches!(
+            self,
+            Type::Enumerate(_) | Type::Interval(_) | Type::IntInterval(_, _)
+        )
+    }
+
     pub fn resolve(&self, types: &HashMap<String, Type>) -> Result<Type, Error> {
         match self {
             Type::Unresolved(name, position) => match types.get(name) {
@@ -80,10 +125,67 @@ impl Type {
                     position: position.clone(),
                 }),
             },
+            Type::Array(index, element) => {
+                let index = index.resolve_index(types, "array index")?;
+                let element = element.resolve(types)?;
+                Ok(Type::Array(Box::new(index), Box::new(element)))
+            }
+            Type::Set(element) => {
+                let element = element.resolve_index(types, "set element")?;
+                Ok(Type::Set(Box::new(element)))
+            }
             _ => Ok(self.clone()),
         }
     }
 
+    /// Resolve an array index or a set element (enumerate or interval)
+    fn resolve_index(&self, types: &HashMap<String, Type>, category: &str) -> Result<Type, Error> {
+        let resolved = self.resolve(types)?;
+        if resolved.is_index() {
+            return Ok(resolved);
+        }
+        let (name, position) = match self {
+            Type::Unresolved(name, position) => (name.clone(), position.clone()),
+            Type::Bool => ("Bool".to_string(), None),
+            Type::Int => ("Int".to_string(), None),
+            Type::Real => ("Real".to_string(), None),
+            Type::Set(_) => ("Set".to_string(), None),
+            _ => ("Array".to_string(), None),
+        };
+        Err(Error::Resolve {
+            category: format!("{} type (enumerate or interval)", category),
+            name,
+            position,
+        })
+    }
+
+    /// The element type of a set
+    pub fn set(&self) -> Option<&Type> {
+        match self {
+            Type::Set(element) => Some(element),
+            _ => None,
+        }
+    }
+
+    /// The intervals used in the type
+    pub fn intervals(&self) -> Vec<IntervalId> {
+        match self {
+            Type::Interval(id) => vec![*id],
+            Type::Function(params, ret) => {
+                let mut v: Vec<IntervalId> = params.iter().flat_map(|t| t.intervals()).collect();
+                v.extend(ret.intervals());
+                v
+            }
+            Type::Array(index, element) => {
+                let mut v = index.intervals();
+                v.extend(element.intervals());
+                v
+            }
+            Type::Set(element) => element.intervals(),
+            _ => vec![],
+        }
+    }
+
     pub fn get_type(&self, model: &Model) -> Type {
         match self {
             Type::Interval(id) => {
@@ -95,6 +197,11 @@ impl Type {
                 let ret = ret.get_type(model);
                 Type::Function(params, Box::new(ret))
             }
+            Type::Array(index, element) => Type::Array(
+                Box::new(index.get_type(model)),
+                Box::new(element.get_type(model)),
+            ),
+            Type::Set(element) => Type::Set(Box::new(element.get_type(model))),
             _ => self.clone(),
         }
     }
@@ -108,6 +215,13 @@ impl Type {
                 (Type::IntInterval(min1, max1), Type::IntInterval(min2, max2)) => {
                     min1 >= min2 && max1 <= max2
                 }
+                (Type::Array(i1, e1), Type::Array(i2, e2)) => i1 == i2 && e1.is_subtype_of(e2),
+                (Type::Set(e1), Type::Set(e2)) => e1.is_subtype_of(e2),
+                (Type::IntInterval(min, max), bv) if bv.is_bit_vector() => {
+                    let (width, signed) = bv.bit_vector().unwrap();
+                    let (low, high) = Self::bit_vector_range(width, signed);
+                    low <= *min as i128 && *max as i128 <= high
+                }
                 _ => false,
             }
         }
@@ -118,6 +232,10 @@ impl Type {
             (Type::IntInterval(_, _), Type::IntInterval(_, _)) => true,
             (Type::IntInterval(_, _), Type::Int) => true,
             (Type::Int, Type::IntInterval(_, _)) => true,
+            (Type::Array(i1, e1), Type::Array(i2, e2)) => i1 == i2 && e1.is_compatible_with(e2),
+            (Type::Set(e1), Type::Set(e2)) => e1.is_compatible_with(e2),
+            (bv, Type::Int | Type::IntInterval(_, _)) if bv.is_bit_vector() => true,
+            (Type::Int | Type::IntInterval(_, _), bv) if bv.is_bit_vector() => true,
             (x, y) => x == y,
         }
     }
@@ -132,6 +250,20 @@ impl Type {
                 (Type::IntInterval(min1, max1), Type::IntInterval(min2, max2)) => {
                     Type::IntInterval(*min1.min(min2), *max1.max(max2))
                 }
+                (x, y) if x.is_subtype_of(y) => y.clone(),
+                (x, y) if y.is_subtype_of(x) => x.clone(),
+                (bv, Type::Int | Type::IntInterval(_, _)) if bv.is_bit_vector() => bv.clone(),
+                (Type::Int | Type::IntInterval(_, _), bv) if bv.is_bit_vector() => bv.clone(),
+                (Type::Set(e1), Type::Set(e2)) => match e1.common_type(_model, e2) {
+                    Type::Undefined => Type::Undefined,
+                    e => Type::Set(Box::new(e)),
+                },
+                (Type::Array(i1, e1), Type::Array(i2, e2)) if i1 == i2 => {
+                    match e1.common_type(_model, e2) {
+                        Type::Undefined => Type::Undefined,
+                        e => Type::Array(i1.clone(), Box::new(e)),
+                    }
+                }
                 // (Type::Class(i1), Type::Class(i2)) => {
                 //     let c1 = problem.get(*i1).unwrap();
                 //     match c1.common_class(problem, *i2) {
@@ -185,9 +317,18 @@ impl ToLang for Type {
             Type::Real => "Real".into(),
             //
             Type::Enumerate(id) => model.get(*id).unwrap().name().to_string(),
-            Type::Interval(id) => model.get(*id).unwrap().name().to_string(),
+            Type::Interval(id) => {
+                let interval = model.get(*id).unwrap();
+                if interval.is_anonymous() {
+                    interval.to_lang(model)
+                } else {
+                    interval.name().to_string()
+                }
+            }
             //
             Type::IntInterval(min, max) => format!("{}..{}", min, max),
+            Type::Unsigned(width) => format!("Unsigned[{}]", width),
+            Type::Signed(width) => format!("Signed[{}]", width),
             //
             Type::Function(params, ret) => {
                 let mut res = "(".to_string();
@@ -200,6 +341,12 @@ impl ToLang for Type {
                 res += &format!("): {}", ret.to_lang(model));
                 res
             }
+            Type::Array(index, element) => format!(
+                "Array[{}] of {}",
+                index.to_lang(model),
+                element.to_lang(model)
+            ),
+            Type::Set(element) => format!("Set of {}", element.to_lang(model)),
         }
     }
 }